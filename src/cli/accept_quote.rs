@@ -0,0 +1,80 @@
+use std::error::Error;
+
+use chrono::Local;
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::bill_deposit::bill_deposit;
+use crate::cli::utils::get_customer_payment_terms::get_customer_payment_terms;
+use crate::cli::utils::select_quote_or_use_default::select_quote_or_use_default;
+use crate::entities::quote::{Quote, QuoteStatus};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Mark the quote as accepted and bill its products in a new invoice,
+/// or only a deposit when `deposit_percent` is given
+pub fn accept_quote(context_parameters: ContextParameters, quote_ref: &Option<String>, deposit_percent: &Option<u32>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Accept quote");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let quote_selected: Quote = select_quote_or_use_default(&file_manager, quote_ref)?;
+    let quote_ref = quote_selected.get_ref().unwrap();
+
+    let today = Local::now().date_naive();
+
+    if quote_selected.status != QuoteStatus::Pending {
+        return Err(Box::new(CliError::InvalidQuote(format!(
+            "Quote {} is already {}",
+            quote_ref, quote_selected.status
+        ))));
+    }
+
+    if quote_selected.is_expired(today) {
+        return Err(Box::new(CliError::InvalidQuote(format!(
+            "Quote {} expired on {}",
+            quote_ref, quote_selected.validity_date
+        ))));
+    }
+
+    if let Some(deposit_percent) = deposit_percent {
+        let deposit_path = bill_deposit(&file_manager, &quote_selected, *deposit_percent)?;
+
+        file_manager.save_quote(Quote {
+            status: QuoteStatus::Accepted,
+            ..quote_selected
+        })?;
+
+        println!("Quote {} accepted", quote_ref);
+
+        let mut deposit_path_output = deposit_path.clone();
+        deposit_path_output.set_extension("pdf");
+
+        file_manager.generate_invoice(deposit_path.as_path(), deposit_path_output.file_name().unwrap().to_str().unwrap())?;
+
+        return Ok(());
+    }
+
+    let payment_terms = get_customer_payment_terms(&file_manager, &quote_selected.customer_id)?;
+
+    let invoice = quote_selected.create_invoice(today, payment_terms.get_due_date(today), &[]);
+
+    let invoice_path = file_manager.create_invoice(invoice)?;
+    let invoice_ref = invoice_path.file_stem().unwrap().to_string_lossy().to_string();
+
+    file_manager.save_quote(Quote {
+        status: QuoteStatus::Accepted,
+        invoice_ref: Some(invoice_ref),
+        ..quote_selected
+    })?;
+
+    println!("Quote {} accepted, invoice created in : {}", quote_ref, invoice_path.to_string_lossy());
+
+    let mut invoice_path_output = invoice_path.clone();
+    invoice_path_output.set_extension("pdf");
+
+    file_manager.generate_invoice(invoice_path.as_path(), invoice_path_output.file_name().unwrap().to_str().unwrap())?;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use std::error::Error;
+
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::entities::numbering_audit::audit_numbering as audit_invoices_numbering;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Check the invoice store has an unbroken chronological numbering, fails on any issue
+pub fn audit_numbering(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Audit numbering");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let numbering_policy = file_manager.get_settings()?.numbering;
+    let all_invoices = file_manager.get_all_invoices()?;
+
+    let issues = audit_invoices_numbering(&all_invoices, numbering_policy.as_ref());
+
+    println!("Check numbering of {} documents\n", all_invoices.len());
+
+    issues.iter().for_each(|issue| println!("{}", issue));
+
+    if !issues.is_empty() {
+        return Err(Box::new(CliError::NumberingBroken(issues.len())));
+    }
+
+    println!("Numbering is unbroken");
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use std::error::Error;
+
+use chrono::Local;
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::get_customer_payment_terms::get_customer_payment_terms;
+use crate::cli::utils::select_quote_or_use_default::select_quote_or_use_default;
+use crate::entities::quote::{Quote, QuoteStatus};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Final invoice of an accepted quote, the deposits already billed are deducted
+pub fn bill_quote(context_parameters: ContextParameters, quote_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Bill quote");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let quote_selected: Quote = select_quote_or_use_default(&file_manager, quote_ref)?;
+    let quote_ref = quote_selected.get_ref().unwrap();
+
+    if quote_selected.status != QuoteStatus::Accepted {
+        return Err(Box::new(CliError::InvalidQuote(format!(
+            "Quote {} is {}, only accepted quotes can be billed",
+            quote_ref, quote_selected.status
+        ))));
+    }
+
+    if let Some(invoice_ref) = &quote_selected.invoice_ref {
+        return Err(Box::new(CliError::InvalidQuote(format!(
+            "Quote {} is already billed by {}",
+            quote_ref, invoice_ref
+        ))));
+    }
+
+    let today = Local::now().date_naive();
+    let payment_terms = get_customer_payment_terms(&file_manager, &quote_selected.customer_id)?;
+
+    let all_invoices = file_manager.get_all_invoices()?;
+    let deposits = quote_selected.get_deposits(&all_invoices);
+
+    deposits
+        .iter()
+        .for_each(|deposit| println!("Deduce deposit {}", deposit));
+
+    let invoice = quote_selected.create_invoice(today, payment_terms.get_due_date(today), &all_invoices);
+
+    let invoice_path = file_manager.create_invoice(invoice)?;
+    let invoice_ref = invoice_path.file_stem().unwrap().to_string_lossy().to_string();
+
+    file_manager.save_quote(Quote {
+        invoice_ref: Some(invoice_ref),
+        ..quote_selected
+    })?;
+
+    println!("Invoice of quote {} created in : {}", quote_ref, invoice_path.to_string_lossy());
+
+    let mut invoice_path_output = invoice_path.clone();
+    invoice_path_output.set_extension("pdf");
+
+    file_manager.generate_invoice(invoice_path.as_path(), invoice_path_output.file_name().unwrap().to_str().unwrap())?;
+
+    Ok(())
+}
@@ -10,4 +10,40 @@ pub enum CliError {
 
     #[error("{0}")]
     CommandNotExists(String),
-}
\ No newline at end of file
+
+    #[error("Invalid payment: {0}")]
+    InvalidPayment(String),
+
+    #[error("Invalid credit note: {0}")]
+    InvalidCreditNote(String),
+
+    #[error("Invalid invoice: {0}")]
+    InvalidInvoice(String),
+
+    #[error("Invalid quote: {0}")]
+    InvalidQuote(String),
+
+    #[error("Invalid import: {0}")]
+    InvalidImport(String),
+
+    #[error("Invalid period: {0} is after {1}")]
+    InvalidPeriod(String, String),
+
+    #[error("Invalid month: {0} (1 to 12)")]
+    InvalidMonth(u32),
+
+    #[error("Invalid year: {0} (1 to 9999)")]
+    InvalidYear(i32),
+
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    #[error("Invalid quarter: {0} (1 to 4)")]
+    InvalidQuarter(u32),
+
+    #[error("Numbering is broken: {0} issue(s) found")]
+    NumberingBroken(usize),
+
+    #[error("Invoices were tampered: {0} issue(s) found")]
+    LedgerBroken(usize),
+}
@@ -1,26 +1,39 @@
 use std::error::Error;
 
-use dialoguer::Input;
 use log::trace;
 
+use crate::cli::customer_input::CustomerInput;
+use crate::cli::utils::input_or_ask::input_or_ask;
+use crate::cli::utils::is_interactive::is_interactive;
+use crate::cli::utils::select_payment_terms::select_payment_terms;
 use crate::entities::customer::Customer;
+use crate::entities::siren::Siren;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn create_customer(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+pub fn create_customer(context_parameters: ContextParameters, customer_input: &CustomerInput) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Create customer");
 
-    let name: String =
-        Input::new().with_prompt("Enterprise name").interact_text().unwrap();
+    let customer_input = customer_input.with_file_values()?;
 
-    let address = Input::new().with_prompt("Address").interact_text().unwrap();
+    let name = input_or_ask("Enterprise name", "name", customer_input.name, None, false)?;
 
-    let city = Input::new().with_prompt("City").interact_text().unwrap();
+    let address = input_or_ask("Address", "address", customer_input.address, None, false)?;
 
-    let postal = Input::new().with_prompt("Postal code").interact_text().unwrap();
+    let city = input_or_ask("City", "city", customer_input.city, None, false)?;
 
-    let customer = Customer { name, address, postal, city };
+    let postal = input_or_ask("Postal code", "postal", customer_input.postal, None, false)?;
+
+    let payment_terms = match customer_input.payment_terms {
+        Some(payment_terms) => Some(payment_terms),
+        None if is_interactive() => select_payment_terms("Payment terms", "Default of settings")?,
+        None => None,
+    };
+
+    let siren = customer_input.siren.map(|siren| Siren::new(&siren)).transpose()?;
+
+    let customer = Customer { name, address, postal, city, payment_terms, account: customer_input.account, siren, tva: customer_input.tva };
 
     let file_manager = FileManager::new(context_parameters)?;
 
@@ -1,92 +1,136 @@
 use std::error::Error;
+use std::path::PathBuf;
 
-use chrono::Local;
-use dialoguer::{Confirm, FuzzySelect, Input};
+use chrono::{Local, NaiveDate};
+use dialoguer::{FuzzySelect, Input};
 use log::trace;
-
+use serde::Deserialize;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::ensure_interactive::ensure_interactive;
+use crate::cli::utils::input_products::input_products;
+use crate::cli::utils::read_input_file::read_input_file;
+use crate::cli::utils::warn_thresholds::warn_thresholds;
+use crate::entities::document_kind::DocumentKind;
 use crate::entities::invoice::Invoice;
 use crate::entities::product::Product;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn create_invoice(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+/// Invoice given with `--from-file`, the flags override its values
+#[derive(Deserialize, Default)]
+struct InvoiceInput {
+    customer: Option<String>,
+    title: Option<String>,
+    /// YYYY-MM-DD
+    date: Option<String>,
+    #[serde(default)]
+    products: Vec<Product>,
+}
+
+/// Create and issue a invoice, or only save it as a draft to edit later.
+/// Values missing from the flags and the input file are asked interactively.
+pub fn create_invoice(
+    context_parameters: ContextParameters,
+    draft: bool,
+    customer: &Option<String>,
+    title: &Option<String>,
+    lines: &[Product],
+    from_file: &Option<PathBuf>,
+    date: &Option<NaiveDate>,
+) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Create invoice");
 
     let file_manager = FileManager::new(context_parameters)?;
 
-    let all_customers_id: Vec<String> = file_manager
-        .get_all_customers()?
-        .into_iter()
-        .map(|(id, _customer)| id)
-        .collect();
+    let input: InvoiceInput = match from_file {
+        Some(path) => read_input_file(path)?,
+        None => InvoiceInput::default(),
+    };
 
-    let date = Local::now().date_naive();
+    let all_customers = file_manager.get_all_customers()?;
 
-    let customer_index = FuzzySelect::new()
-        .with_prompt("What is your customer?")
-        .items(&all_customers_id)
-        .interact()
-        .unwrap();
+    let settings = file_manager.get_settings()?;
+    let default_vat_rate = settings.get_default_vat_rate();
 
-    let title = Input::new().with_prompt("Invoice title").interact_text().unwrap();
+    let date = match (date, &input.date) {
+        (Some(date), _) => *date,
+        (None, Some(input_date)) => input_date.parse::<NaiveDate>().map_err(|err| {
+            CliError::InvalidInvoice(format!("date {} is not YYYY-MM-DD, {}", input_date, err))
+        })?,
+        (None, None) => Local::now().date_naive(),
+    };
 
-    let mut products = vec![];
+    let customer_id = match customer.as_ref().or(input.customer.as_ref()) {
+        Some(customer_id) => customer_id.to_owned(),
+        None => {
+            ensure_interactive("The customer (--customer)")?;
 
-    loop {
-        let product_title: String =
-            Input::new().with_prompt("Product title").interact_text().unwrap();
+            let all_customers_id: Vec<&String> = all_customers.keys().collect();
+            let customer_index = FuzzySelect::new()
+                .with_prompt("What is your customer?")
+                .items(&all_customers_id)
+                .interact()
+                .unwrap();
 
-        if product_title == "".to_string() {
-            break;
+            all_customers_id[customer_index].to_owned()
         }
+    };
+
+    let customer = all_customers
+        .get(&customer_id)
+        .ok_or_else(|| CliError::CustomerNotFound(customer_id.to_owned()))?;
+
+    let title = match title.as_ref().or(input.title.as_ref()) {
+        Some(title) => title.to_owned(),
+        None => {
+            ensure_interactive("The invoice title (--title)")?;
 
-        let product_quantity = Input::new()
-            .with_prompt("Product quantity")
-            .validate_with(|input: &String| -> Result<(), &str> {
-                input.parse::<f32>().map_err(|_| "Invalid number")?;
-                return Ok(());
-            })
-            .interact()
-            .unwrap();
-
-        let product_price = Input::new()
-            .with_prompt("Product price")
-            .validate_with(|input: &String| -> Result<(), &str> {
-                input.parse::<f32>().map_err(|_| "Invalid number")?;
-                return Ok(());
-            })
-            .interact()
-            .unwrap();
-
-        products.push(Product {
-            description: product_title,
-            quantity: product_quantity.parse::<f32>().unwrap(),
-            price: product_price.parse::<f32>().unwrap(),
-        });
-
-        let confirmation = Confirm::new()
-            .with_prompt("Do you want to add another product ?")
-            .interact()
-            .unwrap();
-
-        if !confirmation {
-            break;
+            Input::new().with_prompt("Invoice title").interact_text().unwrap()
         }
-    }
+    };
+
+    let products = if !lines.is_empty() {
+        lines.to_vec()
+    } else if !input.products.is_empty() {
+        input.products
+    } else {
+        ensure_interactive("The products (--line)")?;
+
+        input_products(&default_vat_rate)?
+    };
+
+    let payment_terms = customer.get_payment_terms(&settings.get_payment_terms());
 
     let invoice = Invoice {
         date,
-        customer_id: all_customers_id[customer_index].to_owned(),
+        due_date: Some(payment_terms.get_due_date(date)),
+        customer_id,
         title,
         products,
         invoice_day_id: None,
+        kind: DocumentKind::Invoice,
+        credited_invoice_ref: None,
+        quote_ref: None,
+        reference: None,
+        sequence: None,
     };
 
+    if draft {
+        let draft_id = file_manager.save_draft(None, &invoice)?;
+
+        println!("Draft {} saved, edit it with `invoice edit` and issue it with `invoice finalize`", draft_id);
+
+        return Ok(());
+    }
+
+    warn_thresholds(&file_manager, &invoice)?;
+
     let invoice_path = file_manager.create_invoice(invoice)?;
 
     println!("Invoice created in : {}", invoice_path.to_string_lossy());
-    
+
     let mut invoice_path_output = invoice_path.clone();
     invoice_path_output.set_extension("pdf");
 
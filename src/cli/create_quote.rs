@@ -0,0 +1,64 @@
+use std::error::Error;
+
+use chrono::{Days, Local};
+use dialoguer::{FuzzySelect, Input};
+use log::trace;
+
+use crate::cli::utils::input_products::input_products;
+use crate::entities::quote::{Quote, QuoteStatus};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub fn create_quote(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Create quote");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let all_customers_id: Vec<String> = file_manager
+        .get_all_customers()?
+        .into_keys()
+        .collect();
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+    let date = Local::now().date_naive();
+
+    let customer_index = FuzzySelect::new()
+        .with_prompt("What is your customer?")
+        .items(&all_customers_id)
+        .interact()
+        .unwrap();
+
+    let title = Input::new().with_prompt("Quote title").interact_text().unwrap();
+
+    let validity_days: u64 = Input::new()
+        .with_prompt("Validity (days)")
+        .default(Quote::DEFAULT_VALIDITY_DAYS)
+        .interact_text()
+        .unwrap();
+
+    let products = input_products(&default_vat_rate)?;
+
+    let quote = Quote {
+        date,
+        validity_date: date + Days::new(validity_days),
+        customer_id: all_customers_id[customer_index].to_owned(),
+        title,
+        quote_day_id: None,
+        products,
+        status: QuoteStatus::Pending,
+        invoice_ref: None,
+    };
+
+    let quote_path = file_manager.save_quote(quote)?;
+
+    println!("Quote created in : {}", quote_path.to_string_lossy());
+
+    let mut quote_path_output = quote_path.clone();
+    quote_path_output.set_extension("pdf");
+
+    file_manager.generate_quote(quote_path.as_path(), quote_path_output.file_name().unwrap().to_str().unwrap())?;
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::error::Error;
+
+use chrono::{Local, NaiveDate};
+use dialoguer::{Confirm, FuzzySelect, Input, Select};
+use log::trace;
+
+use crate::cli::utils::input_products::input_products;
+use crate::entities::recurring_invoice::{Cadence, RecurringInvoice};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub fn create_recurring(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Create recurring invoice");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let all_customers_id: Vec<String> = file_manager
+        .get_all_customers()?
+        .into_keys()
+        .collect();
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+    let all_recurring_invoices = file_manager.get_all_recurring_invoices()?;
+
+    let name: String = Input::new()
+        .with_prompt("Schedule name")
+        .validate_with(|input: &String| -> Result<(), String> {
+            let serialized_name = RecurringInvoice::serialize_name(input);
+
+            if all_recurring_invoices.contains_key(&serialized_name) {
+                Err(format!("The schedule {} already exists", serialized_name))
+            } else {
+                Ok(())
+            }
+        })
+        .interact_text()
+        .unwrap();
+
+    let customer_index = FuzzySelect::new()
+        .with_prompt("What is your customer?")
+        .items(&all_customers_id)
+        .interact()
+        .unwrap();
+
+    let title = Input::new()
+        .with_prompt("Invoice title ({month}, {MM} and {YYYY} are replaced by the billed period)")
+        .interact_text()
+        .unwrap();
+
+    let all_cadences = Cadence::all_cadences();
+    let cadence_index = Select::new()
+        .with_prompt("Cadence")
+        .items(&all_cadences)
+        .default(0)
+        .interact()
+        .unwrap();
+
+    let start_date: NaiveDate = Input::new()
+        .with_prompt("First invoice date (YYYY-MM-DD)")
+        .default(Local::now().date_naive())
+        .interact_text()
+        .unwrap();
+
+    let end_date: Option<NaiveDate> = if Confirm::new()
+        .with_prompt("Has the schedule an end date?")
+        .default(false)
+        .interact()
+        .unwrap()
+    {
+        Some(
+            Input::new()
+                .with_prompt("Last invoice date (YYYY-MM-DD)")
+                .interact_text()
+                .unwrap(),
+        )
+    } else {
+        None
+    };
+
+    let products = input_products(&default_vat_rate)?;
+
+    let recurring_invoice = file_manager.create_recurring_invoice(RecurringInvoice {
+        name,
+        customer_id: all_customers_id[customer_index].to_owned(),
+        title,
+        products,
+        cadence: all_cadences[cadence_index],
+        start_date,
+        end_date,
+        last_generated: None,
+    })?;
+
+    println!("Recurring invoice {} created", recurring_invoice.serialized_name());
+
+    Ok(())
+}
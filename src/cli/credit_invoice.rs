@@ -0,0 +1,102 @@
+use std::error::Error;
+
+use chrono::Local;
+use dialoguer::{Input, MultiSelect};
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::get_amount_due::get_creditable_amount;
+use crate::cli::utils::select_invoice_or_use_default::select_invoice_or_use_default;
+use crate::entities::amount::Amount;
+use crate::entities::document_kind::DocumentKind;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Credit note on selected lines of an invoice, each line fully or for an amount
+pub fn credit_invoice(context_parameters: ContextParameters, invoice_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Credit invoice");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let invoice_selected = select_invoice_or_use_default(&file_manager, invoice_ref)?;
+    let invoice_ref = invoice_selected.get_ref().unwrap();
+
+    if invoice_selected.kind == DocumentKind::CreditNote {
+        return Err(Box::new(CliError::InvalidCreditNote(format!(
+            "{} is a {}, it can't be credited",
+            invoice_ref, invoice_selected.kind
+        ))));
+    }
+
+    let creditable_amount = get_creditable_amount(&invoice_selected, &file_manager.get_all_invoices()?);
+
+    if creditable_amount <= Amount::ZERO {
+        return Err(Box::new(CliError::InvalidCreditNote(format!(
+            "Invoice {} is already fully credited",
+            invoice_ref
+        ))));
+    }
+
+    println!("Amount HT still creditable : {} €", creditable_amount);
+
+    let product_items: Vec<String> = invoice_selected
+        .products
+        .iter()
+        .map(|product| format!("{} : {} * {}€ = {}€", product.description, product.quantity, product.price, product.get_total_price()))
+        .collect();
+
+    let products_selected = MultiSelect::new()
+        .with_prompt("Products to credit")
+        .items(&product_items)
+        .interact()?;
+
+    let mut products = vec![];
+
+    for product_index in products_selected {
+        let product = &invoice_selected.products[product_index];
+        let product_total = product.get_total_price();
+
+        if product_total == Amount::ZERO {
+            println!("Nothing to credit for {}, skipped", product.description);
+            continue;
+        }
+
+        // Amount without sign, a discount line is credited as a discount
+        let amount: String = Input::new()
+            .with_prompt(format!("Amount HT to credit for {}", product.description))
+            .with_initial_text(product_total.abs().to_string())
+            .validate_with(|input: &String| -> Result<(), String> {
+                product.get_partial_credit(Amount::new(input)?)?;
+                Ok(())
+            })
+            .interact_text()?;
+
+        products.push(product.get_partial_credit(Amount::new(&amount)?)?);
+    }
+
+    if products.is_empty() {
+        return Err(Box::new(CliError::InvalidCreditNote("No product selected".to_string())));
+    }
+
+    let credit_note = invoice_selected.create_credit_note(Local::now().date_naive(), products);
+
+    if -credit_note.get_total_price() > creditable_amount {
+        return Err(Box::new(CliError::InvalidCreditNote(format!(
+            "The credit note of {} € is greater than the {} € still creditable",
+            -credit_note.get_total_price(),
+            creditable_amount
+        ))));
+    }
+
+    let credit_note_path = file_manager.create_invoice(credit_note)?;
+
+    println!("Credit note created at : {}", credit_note_path.to_string_lossy());
+
+    let mut credit_note_path_output = credit_note_path.clone();
+    credit_note_path_output.set_extension("pdf");
+
+    file_manager.generate_invoice(credit_note_path.as_path(), credit_note_path_output.file_name().unwrap().to_str().unwrap())?;
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Deserialize;
+
+use crate::cli::utils::read_input_file::read_input_file;
+use crate::entities::payment_terms::PaymentTerms;
+
+/// Customer values given by flags or by a file in the format of the customer file.
+/// Missing values are asked when a terminal is available, unknown keys of the file are errors.
+#[derive(Args, Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CustomerInput {
+    /// Enterprise name
+    #[arg(long)]
+    pub name: Option<String>,
+    #[arg(long)]
+    pub address: Option<String>,
+    #[arg(long)]
+    pub city: Option<String>,
+    /// Postal code
+    #[arg(long)]
+    pub postal: Option<String>,
+    /// on_receipt, net:DAYS or end_of_month:DAYS, default of settings if missing
+    #[arg(long)]
+    pub payment_terms: Option<PaymentTerms>,
+    /// Auxiliary account in the accounting exports, 411 and the id in capitals if missing
+    #[arg(long)]
+    pub account: Option<String>,
+    /// 9 digits, identifies the buyer in the electronic invoices
+    #[arg(long)]
+    pub siren: Option<String>,
+    /// TVA number of the buyer
+    #[arg(long)]
+    pub tva: Option<String>,
+    /// Read the customer from a YAML or JSON file, the other flags override its values
+    #[arg(long, value_name = "FILE")]
+    #[serde(skip)]
+    pub from_file: Option<PathBuf>,
+}
+
+impl CustomerInput {
+    /// Values of the flags, completed by the ones of `--from-file`
+    pub fn with_file_values(&self) -> Result<CustomerInput, Box<dyn Error + Sync + Send + 'static>> {
+        let file_input: CustomerInput = match &self.from_file {
+            Some(path) => read_input_file(path)?,
+            None => return Ok(self.clone()),
+        };
+
+        Ok(CustomerInput {
+            name: self.name.clone().or(file_input.name),
+            address: self.address.clone().or(file_input.address),
+            city: self.city.clone().or(file_input.city),
+            postal: self.postal.clone().or(file_input.postal),
+            payment_terms: self.payment_terms.clone().or(file_input.payment_terms),
+            account: self.account.clone().or(file_input.account),
+            siren: self.siren.clone().or(file_input.siren),
+            tva: self.tva.clone().or(file_input.tva),
+            from_file: self.from_file.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn customer_input_with_file_values() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let folder = temp_dir_assert_fs.path();
+
+        let customer_path = folder.join("king.json");
+        fs::write(&customer_path, r#"{"name": "King", "city": "Paris", "payment_terms": {"terms": "net", "days": 30}}"#).unwrap();
+
+        let customer_input = CustomerInput {
+            city: Some("Lyon".to_string()),
+            from_file: Some(customer_path),
+            ..CustomerInput::default()
+        }
+        .with_file_values()
+        .unwrap();
+        assert_eq!(customer_input.name, Some("King".to_string()));
+        assert_eq!(customer_input.city, Some("Lyon".to_string()));
+        assert_eq!(customer_input.payment_terms, Some(PaymentTerms::Net { days: 30 }));
+        assert_eq!(customer_input.address, None);
+
+        let customer_input = CustomerInput { name: Some("King".to_string()), ..CustomerInput::default() };
+        assert_eq!(customer_input.with_file_values().unwrap().name, Some("King".to_string()));
+
+        for (file_name, content) in [
+            ("typo.yaml", "name: King\nadress: 1 rue de la Paix\n"),
+            ("invalid_terms.yaml", "payment_terms:\n  terms: net\n  days: -5\n"),
+            ("not_a_customer.json", "[]"),
+        ] {
+            let path = folder.join(file_name);
+            fs::write(&path, content).unwrap();
+            let customer_input = CustomerInput { from_file: Some(path), ..CustomerInput::default() };
+            assert!(customer_input.with_file_values().is_err(), "{} accepted", file_name);
+        }
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+}
@@ -0,0 +1,41 @@
+use std::error::Error;
+
+use chrono::{Datelike, Local, NaiveDate};
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::select_customer_or_use_default::select_customer_or_use_default;
+use crate::entities::customer_statement::CustomerStatement;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub fn customer_statement(context_parameters: ContextParameters, customer_ref: &Option<String>, from: &Option<NaiveDate>, to: &Option<NaiveDate>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Generate customer statement");
+
+    let today = Local::now().date_naive();
+    let from = from.unwrap_or_else(|| NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap());
+    let to = to.unwrap_or(today);
+
+    if from > to {
+        return Err(Box::new(CliError::InvalidPeriod(from.to_string(), to.to_string())));
+    }
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let (customer_id, _) = select_customer_or_use_default(&file_manager, customer_ref)?;
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+    let all_payments = file_manager.get_all_payments()?;
+    let all_invoices = file_manager.get_all_invoices()?;
+
+    let statement = CustomerStatement::new(&customer_id, from, to, &all_invoices, &all_payments, &default_vat_rate);
+
+    let statement_output_name = format!("statement-{}-{}-{}.pdf", customer_id, from, to);
+
+    let output_path = file_manager.generate_statement(&statement, &statement_output_name)?;
+
+    println!("Statement generated in : {}", output_path.to_string_lossy());
+    println!("Balance due on {} : {} €", to, statement.closing_balance);
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::error::Error;
+
+use log::trace;
+use serde::Serialize;
+
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::cli::utils::select_customer_or_use_default::select_customer_or_use_default;
+use crate::entities::customer_stats::CustomerStats;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+#[derive(Serialize)]
+struct CustomerStatsOutput<'a> {
+    #[serde(flatten)]
+    stats: &'a CustomerStats,
+    last_invoice_date: Option<String>,
+}
+
+pub fn customer_stats(context_parameters: ContextParameters, output_format: OutputFormat, customer_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Get customer stats");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let (customer_id, customer_selected) = select_customer_or_use_default(&file_manager, customer_ref)?;
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+    let all_payments = file_manager.get_all_payments()?;
+    let all_invoices = file_manager.get_all_invoices()?;
+
+    let stats = CustomerStats::new(&customer_id, &all_invoices, &all_payments, &default_vat_rate);
+
+    let output = CustomerStatsOutput {
+        stats: &stats,
+        last_invoice_date: stats.last_invoice_date.map(|date| date.to_string()),
+    };
+
+    print_output(output_format, &output, &stats.years, || {
+        println!("Stats of {} ({})\n", customer_selected.name, customer_id);
+
+        println!("Invoices : {}", stats.invoices);
+        println!("Credit notes : {}", stats.credit_notes);
+        println!("Revenue : {} €", stats.revenue);
+        println!("Average invoice : {} €", stats.average_invoice);
+        if let Some(last_invoice_date) = stats.last_invoice_date {
+            println!("Last invoice : {}", last_invoice_date);
+        }
+        println!("Outstanding balance : {} €", stats.outstanding);
+
+        if !stats.years.is_empty() {
+            println!();
+        }
+        stats.years.iter().for_each(|year| {
+            println!("{} - {} invoices - {} €", year.year, year.invoices, year.revenue)
+        });
+    })
+}
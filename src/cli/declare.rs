@@ -0,0 +1,86 @@
+use std::error::Error;
+
+use chrono::{Datelike, Local, Months, NaiveDate};
+use log::trace;
+use serde::Serialize;
+
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::cli::utils::get_period_range::{get_month_range, get_quarter_range};
+use crate::entities::urssaf_declaration::UrssafDeclaration;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+#[derive(Serialize)]
+struct DeclarationOutput<'a> {
+    from: String,
+    to: String,
+    #[serde(flatten)]
+    declaration: &'a UrssafDeclaration,
+}
+
+/// Declaration of a month, the previous month by default
+pub fn declare_month(context_parameters: ContextParameters, output_format: OutputFormat, month: &Option<u32>, year: &Option<i32>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Declare month");
+
+    let previous_month = Local::now().date_naive() - Months::new(1);
+
+    let (from, to) = get_month_range(month.unwrap_or(previous_month.month()), year.unwrap_or(previous_month.year()))?;
+
+    declare(context_parameters, output_format, from, to)
+}
+
+/// Declaration of a quarter, the previous quarter by default
+pub fn declare_quarter(context_parameters: ContextParameters, output_format: OutputFormat, quarter: &Option<u32>, year: &Option<i32>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Declare quarter");
+
+    let previous_quarter = Local::now().date_naive() - Months::new(3);
+
+    let (from, to) = get_quarter_range(
+        quarter.unwrap_or((previous_quarter.month() - 1) / 3 + 1),
+        year.unwrap_or(previous_quarter.year()),
+    )?;
+
+    declare(context_parameters, output_format, from, to)
+}
+
+fn declare(context_parameters: ContextParameters, output_format: OutputFormat, from: NaiveDate, to: NaiveDate) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let settings = file_manager.get_settings()?;
+    let all_invoices = file_manager.get_all_invoices()?;
+    let all_payments = file_manager.get_all_payments()?;
+
+    let declaration = UrssafDeclaration::new(
+        from,
+        to,
+        &all_invoices,
+        &all_payments,
+        settings.get_activity(),
+        &settings.get_default_vat_rate(),
+        &settings.get_contribution_rates(),
+    );
+
+    let output = DeclarationOutput { from: from.to_string(), to: to.to_string(), declaration: &declaration };
+
+    print_output(output_format, &output, &declaration.lines, || {
+        let mut plural_offset = "";
+        if declaration.payments > 1 {
+            plural_offset = "s";
+        }
+
+        println!("URSSAF declaration from {} to {} ({} payment{})\n", from, to, declaration.payments, plural_offset);
+
+        declaration.lines.iter().for_each(|line| {
+            println!(
+                "{} : {} € to declare - {} of contributions : {} €",
+                line.label, line.declared_revenue, line.rate, line.contributions
+            )
+        });
+
+        println!();
+        println!("Total to declare : {} €", declaration.total_declared_revenue);
+        println!("Estimated contributions : {} €", declaration.total_contributions);
+    })
+}
@@ -3,9 +3,11 @@ use std::error::Error;
 use chrono::Local;
 use log::trace;
 
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::get_amount_due::get_creditable_amount;
 use crate::cli::utils::select_invoice_or_use_default::select_invoice_or_use_default;
-use crate::entities;
-use crate::entities::product::Product;
+use crate::entities::amount::Amount;
+use crate::entities::document_kind::DocumentKind;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
@@ -17,18 +19,52 @@ pub fn cancel_invoice(context_parameters: ContextParameters, invoice_ref: &Optio
 
     let invoice_selected = select_invoice_or_use_default(&file_manager, invoice_ref)?;
 
-    let cancel_invoice = entities::invoice::Invoice {
-        date: Local::now().date_naive(),
-        customer_id: invoice_selected.customer_id.to_owned(),
-        title: format!("Cancel : {} ({})", invoice_selected.title, invoice_selected.get_ref().unwrap()),
-        invoice_day_id: None,
-        products: invoice_selected.products.iter().map(|product| Product { description: product.description.to_owned(), quantity: product.quantity, price: product.price * -1.0 }).collect(),
-    };
+    if invoice_selected.kind == DocumentKind::CreditNote {
+        return Err(Box::new(CliError::InvalidCreditNote(format!(
+            "{} is a {}, it can't be cancelled",
+            invoice_selected.get_ref().unwrap(),
+            invoice_selected.kind
+        ))));
+    }
 
+    let all_invoices = file_manager.get_all_invoices()?;
 
-    let invoice = file_manager.create_invoice(cancel_invoice)?;
+    let creditable_amount = get_creditable_amount(&invoice_selected, &all_invoices);
 
-    println!("Cancel Invoice created at : {}", invoice.to_string_lossy());
+    if creditable_amount <= Amount::ZERO {
+        return Err(Box::new(CliError::InvalidCreditNote(format!(
+            "Invoice {} is already fully credited",
+            invoice_selected.get_ref().unwrap()
+        ))));
+    }
+
+    // A partly credited invoice is cancelled for what its credit notes left
+    if creditable_amount != invoice_selected.get_total_price() {
+        println!("Amount HT still creditable : {} €", creditable_amount);
+    }
+
+    let credit_note = invoice_selected.create_credit_note(
+        Local::now().date_naive(),
+        invoice_selected.get_remaining_lines(&all_invoices, &file_manager.get_settings()?.get_default_vat_rate()),
+    );
+
+    // Credit notes on rates the invoice does not have can not be matched with its lines
+    if -credit_note.get_total_price() != creditable_amount {
+        return Err(Box::new(CliError::InvalidCreditNote(format!(
+            "The remaining lines of {} do not match the {} € still creditable, use invoice credit",
+            invoice_selected.get_ref().unwrap(),
+            creditable_amount
+        ))));
+    }
+
+    let credit_note_path = file_manager.create_invoice(credit_note)?;
+
+    println!("Credit note created at : {}", credit_note_path.to_string_lossy());
+
+    let mut credit_note_path_output = credit_note_path.clone();
+    credit_note_path_output.set_extension("pdf");
+
+    file_manager.generate_invoice(credit_note_path.as_path(), credit_note_path_output.file_name().unwrap().to_str().unwrap())?;
 
     Ok(())
 }
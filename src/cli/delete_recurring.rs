@@ -0,0 +1,38 @@
+use std::error::Error;
+
+use dialoguer::FuzzySelect;
+use log::trace;
+
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub fn delete_recurring(context_parameters: ContextParameters, recurring_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Delete recurring invoice");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let recurring_ref_selected = match recurring_ref {
+        Some(recurring_ref) => recurring_ref.to_owned(),
+        None => {
+            let all_recurring_refs: Vec<String> = file_manager
+                .get_all_recurring_invoices()?
+                .into_keys()
+                .collect();
+
+            let selection = FuzzySelect::new()
+                .with_prompt("Which recurring invoice?")
+                .items(&all_recurring_refs)
+                .interact()
+                .unwrap();
+
+            all_recurring_refs[selection].to_owned()
+        }
+    };
+
+    file_manager.remove_recurring_invoice(&recurring_ref_selected)?;
+
+    println!("Recurring invoice {} deleted, its invoices are kept", recurring_ref_selected);
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use std::error::Error;
+
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::bill_deposit::bill_deposit;
+use crate::cli::utils::select_quote_or_use_default::select_quote_or_use_default;
+use crate::entities::quote::{Quote, QuoteStatus};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Another deposit invoice for an accepted quote not billed yet
+pub fn deposit_quote(context_parameters: ContextParameters, quote_ref: &Option<String>, percent: u32) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Deposit quote");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let quote_selected: Quote = select_quote_or_use_default(&file_manager, quote_ref)?;
+
+    if quote_selected.status != QuoteStatus::Accepted || quote_selected.invoice_ref.is_some() {
+        return Err(Box::new(CliError::InvalidQuote(format!(
+            "Quote {} must be accepted and not billed yet to add a deposit",
+            quote_selected.get_ref().unwrap()
+        ))));
+    }
+
+    let deposit_path = bill_deposit(&file_manager, &quote_selected, percent)?;
+
+    let mut deposit_path_output = deposit_path.clone();
+    deposit_path_output.set_extension("pdf");
+
+    file_manager.generate_invoice(deposit_path.as_path(), deposit_path_output.file_name().unwrap().to_str().unwrap())?;
+
+    Ok(())
+}
@@ -1,31 +1,55 @@
 use std::error::Error;
 
-use dialoguer::Input;
 use log::trace;
 
+use crate::cli::customer_input::CustomerInput;
+use crate::cli::utils::input_or_ask::input_or_ask;
+use crate::cli::utils::is_interactive::is_interactive;
 use crate::cli::utils::select_customer_or_use_default::select_customer_or_use_default;
+use crate::cli::utils::select_payment_terms::select_payment_terms;
 use crate::entities::customer::Customer;
+use crate::entities::siren::Siren;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn edit_customer(context_parameters: ContextParameters, customer_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+/// Values not given by flags or file are asked, or kept without terminal
+pub fn edit_customer(context_parameters: ContextParameters, customer_ref: &Option<String>, customer_input: &CustomerInput) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Create customer");
 
     let file_manager = FileManager::new(context_parameters)?;
 
+    let customer_input = customer_input.with_file_values()?;
+
     let (customer_ref_selected, customer_to_edit) = select_customer_or_use_default(&file_manager, customer_ref)?;
 
-    let name: String =
-        Input::new().with_prompt("Enterprise name").with_initial_text(&customer_to_edit.name).interact_text().unwrap();
+    let name = input_or_ask("Enterprise name", "name", customer_input.name, Some(customer_to_edit.name), false)?;
+
+    let address = input_or_ask("Address", "address", customer_input.address, Some(customer_to_edit.address), false)?;
+
+    let city = input_or_ask("City", "city", customer_input.city, Some(customer_to_edit.city), false)?;
+
+    let postal = input_or_ask("Postal code", "postal", customer_input.postal, Some(customer_to_edit.postal), false)?;
+
+    let payment_terms = match customer_input.payment_terms {
+        Some(payment_terms) => Some(payment_terms),
+        None if is_interactive() => {
+            let payment_terms = match &customer_to_edit.payment_terms {
+                Some(payment_terms) => format!("Keep {}", payment_terms),
+                None => "Default of settings".to_string(),
+            };
+            select_payment_terms("Payment terms", &payment_terms)?.or(customer_to_edit.payment_terms)
+        }
+        None => customer_to_edit.payment_terms,
+    };
 
-    let address = Input::new().with_prompt("Address").with_initial_text(&customer_to_edit.address).interact_text().unwrap();
+    let account = customer_input.account.or(customer_to_edit.account);
 
-    let city = Input::new().with_prompt("City").with_initial_text(&customer_to_edit.city).interact_text().unwrap();
+    let siren = customer_input.siren.map(|siren| Siren::new(&siren)).transpose()?.or(customer_to_edit.siren);
 
-    let postal = Input::new().with_prompt("Postal code").with_initial_text(&customer_to_edit.postal).interact_text().unwrap();
+    let tva = customer_input.tva.or(customer_to_edit.tva);
 
-    let customer = Customer { name, address, postal, city };
+    let customer = Customer { name, address, postal, city, payment_terms, account, siren, tva };
 
 
     let customer = file_manager.edit_customer(customer_ref_selected, customer)?;
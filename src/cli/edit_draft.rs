@@ -0,0 +1,74 @@
+use std::error::Error;
+
+use dialoguer::{FuzzySelect, Input, Select};
+use log::trace;
+
+use crate::cli::utils::input_products::input_products;
+use crate::cli::utils::select_draft_or_use_default::select_draft_or_use_default;
+use crate::entities::invoice::Invoice;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Only drafts can be edited, issued invoices are immutable
+pub fn edit_draft(context_parameters: ContextParameters, draft_id: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Edit draft");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let (draft_id, draft) = select_draft_or_use_default(&file_manager, draft_id)?;
+
+    let mut all_customers_id: Vec<String> = file_manager
+        .get_all_customers()?
+        .into_keys()
+        .collect();
+    all_customers_id.sort();
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+    let customer_index = FuzzySelect::new()
+        .with_prompt("What is your customer?")
+        .items(&all_customers_id)
+        .default(all_customers_id.iter().position(|customer_id| *customer_id == draft.customer_id).unwrap_or(0))
+        .interact()
+        .unwrap();
+
+    let title = Input::new()
+        .with_prompt("Invoice title")
+        .with_initial_text(&draft.title)
+        .interact_text()
+        .unwrap();
+
+    println!("Products :");
+    draft
+        .products
+        .iter()
+        .for_each(|product| println!("{} x {} - {} €", product.quantity, product.description, product.price));
+
+    let products_action = Select::new()
+        .with_prompt("Products")
+        .items(&["Keep products", "Add products", "Replace products"])
+        .default(0)
+        .interact()
+        .unwrap();
+
+    let products = match products_action {
+        0 => draft.products,
+        1 => [draft.products, input_products(&default_vat_rate)?].concat(),
+        _ => input_products(&default_vat_rate)?,
+    };
+
+    file_manager.save_draft(
+        Some(&draft_id),
+        &Invoice {
+            customer_id: all_customers_id[customer_index].to_owned(),
+            title,
+            products,
+            ..draft
+        },
+    )?;
+
+    println!("Draft {} saved", draft_id);
+
+    Ok(())
+}
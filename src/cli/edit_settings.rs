@@ -1,55 +1,90 @@
 use std::error::Error;
 
-use dialoguer::{Editor, Input};
+use dialoguer::Editor;
 use log::trace;
 
+use crate::cli::settings_input::SettingsInput;
+use crate::cli::utils::input_or_ask::input_or_ask;
+use crate::cli::utils::input_siren::input_siren;
+use crate::cli::utils::is_interactive::is_interactive;
+use crate::cli::utils::select_numbering_policy::select_numbering_policy;
+use crate::cli::utils::select_payment_terms::select_payment_terms;
+use crate::cli::utils::select_vat_rate::select_vat_rate;
+use crate::entities::payment_terms::PaymentTerms;
 use crate::entities::settings::{Enterprise, Settings};
-use crate::entities::siren::Siren;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn edit_settings(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+/// Values not given by flags or file are asked, or kept without terminal
+pub fn edit_settings(context_parameters: ContextParameters, settings_input: &SettingsInput) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Edit settings");
 
     let file_manager = FileManager::new(context_parameters)?;
 
     let settings: Settings = file_manager.get_settings()?;
 
-    let name: String =
-        Input::new().with_prompt("Enterprise name").with_initial_text(settings.enterprise.name).interact_text().unwrap();
+    let settings_input = settings_input.with_file_values()?;
+    let enterprise = settings_input.enterprise;
 
-    let title = Input::new().with_prompt("Job title").with_initial_text(settings.enterprise.title).interact_text().unwrap();
+    let name = input_or_ask("Enterprise name", "name", enterprise.name, Some(settings.enterprise.name), false)?;
 
-    let siren = loop {
-        let siren_string: String =
-            Input::new().with_prompt("SIREN").with_initial_text(settings.enterprise.siren.to_string()).interact_text().unwrap();
+    let title = input_or_ask("Job title", "title", enterprise.title, Some(settings.enterprise.title), false)?;
 
-        match Siren::new(&siren_string) {
-            Ok(some_siren) => break some_siren,
-            Err(error) => println!("{}", error),
-        };
-    };
+    let siren = input_siren(enterprise.siren, Some(settings.enterprise.siren))?;
+
+    let email = input_or_ask("Email", "email", enterprise.email, Some(settings.enterprise.email), false)?;
 
-    let email = Input::new().with_prompt("Email").with_initial_text(settings.enterprise.email).interact_text().unwrap();
+    let address = input_or_ask("Address", "address", enterprise.address, Some(settings.enterprise.address), false)?;
 
-    let address = Input::new().with_prompt("Address").with_initial_text(settings.enterprise.address).interact_text().unwrap();
+    let city = input_or_ask("City", "city", enterprise.city, Some(settings.enterprise.city), false)?;
 
-    let city = Input::new().with_prompt("City").with_initial_text(settings.enterprise.city).interact_text().unwrap();
+    let postal = input_or_ask("Postal code", "postal", enterprise.postal, Some(settings.enterprise.postal), false)?;
 
-    let postal = Input::new().with_prompt("Postal code").with_initial_text(settings.enterprise.postal).interact_text().unwrap();
+    let phone = input_or_ask("Phone number", "phone", enterprise.phone, Some(settings.enterprise.phone), false)?;
 
-    let phone = Input::new().with_prompt("Phone number").with_initial_text(settings.enterprise.phone).interact_text().unwrap();
-    
-    let tva = Input::new().with_prompt("TVA Number").with_initial_text(settings.enterprise.tva).allow_empty(true).interact_text().unwrap();
+    let tva = input_or_ask("TVA Number", "tva", enterprise.tva, Some(settings.enterprise.tva), true)?;
 
-    let politeness = Input::new()
-        .with_prompt("Politeness")
-        .with_initial_text(settings.politeness)
-        .interact_text()
-        .unwrap();
+    let politeness = input_or_ask("Politeness", "politeness", settings_input.politeness, Some(settings.politeness), false)?;
 
-    let law_rules = Editor::new().edit(&settings.law_rules).unwrap().unwrap();
+    let law_rules = match settings_input.law_rules {
+        Some(law_rules) => law_rules,
+        None if is_interactive() => Editor::new().edit(&settings.law_rules).unwrap().unwrap(),
+        None => settings.law_rules,
+    };
+
+    let default_vat_rate = match settings_input.default_vat_rate {
+        Some(default_vat_rate) => Some(default_vat_rate),
+        None if is_interactive() => {
+            let default_vat_rate = match &settings.default_vat_rate {
+                Some(default_vat_rate) => format!("Keep {}", default_vat_rate),
+                None => "Automatic (20 % with TVA number, else exempt)".to_string(),
+            };
+            select_vat_rate("Default VAT rate", &default_vat_rate)?.or(settings.default_vat_rate)
+        }
+        None => settings.default_vat_rate,
+    };
+
+    let payment_terms = match settings_input.payment_terms {
+        Some(payment_terms) => Some(payment_terms),
+        None if is_interactive() => {
+            let payment_terms = match &settings.payment_terms {
+                Some(payment_terms) => format!("Keep {}", payment_terms),
+                None => format!("Default ({})", PaymentTerms::default()),
+            };
+            select_payment_terms("Default payment terms", &payment_terms)?.or(settings.payment_terms)
+        }
+        None => settings.payment_terms,
+    };
+
+    let numbering = match settings_input.numbering {
+        Some(numbering) => {
+            numbering.validate()?;
+            Some(numbering)
+        }
+        None if is_interactive() => select_numbering_policy(settings.numbering)?,
+        None => settings.numbering,
+    };
 
     let settings = Settings {
         enterprise: Enterprise {
@@ -61,10 +96,19 @@ pub fn edit_settings(context_parameters: ContextParameters) -> Result<(), Box<dy
             city,
             postal,
             phone,
-            tva
+            tva,
+            iban: enterprise.iban.or(settings.enterprise.iban),
         },
         law_rules,
         politeness,
+        default_vat_rate,
+        payment_terms,
+        numbering,
+        thresholds: settings_input.thresholds.or(settings.thresholds),
+        activity: settings_input.activity.or(settings.activity),
+        contribution_rates: settings_input.contribution_rates.or(settings.contribution_rates),
+        accounting: settings_input.accounting.or(settings.accounting),
+        factur_x: settings_input.factur_x.or(settings.factur_x),
     };
 
     file_manager.edit_settings(settings)?;
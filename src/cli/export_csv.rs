@@ -0,0 +1,62 @@
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use log::trace;
+use serde::Serialize;
+
+use crate::entities::csv_export::{CsvContent, CsvRowsPer, CustomerCsvRow, InvoiceCsvRow, InvoiceLineCsvRow};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Invoices sorted by date or customers as CSV, written on the standard output unless `file` is given
+pub fn export_csv(context_parameters: ContextParameters, content: &CsvContent, rows_per: &CsvRowsPer, file: &Option<PathBuf>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Export CSV");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let count = match content {
+        CsvContent::Customers => {
+            write_csv(&CustomerCsvRow::from_customers(&file_manager.get_all_customers()?), file)?
+        }
+        CsvContent::Invoices => {
+            let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+            let mut invoices = file_manager.get_all_invoices()?;
+            invoices.sort_by_key(|invoice| (invoice.date, invoice.get_ref()));
+
+            match rows_per {
+                CsvRowsPer::Invoice => write_csv(
+                    &invoices.iter().map(|invoice| InvoiceCsvRow::new(invoice, &default_vat_rate)).collect::<Vec<_>>(),
+                    file,
+                )?,
+                CsvRowsPer::Line => write_csv(&InvoiceLineCsvRow::from_invoices(&invoices), file)?,
+            }
+        }
+    };
+
+    if let Some(file) = file {
+        println!("{} rows of {} exported in : {}", count, content, file.to_string_lossy());
+    }
+
+    Ok(())
+}
+
+fn write_csv<R: Serialize>(rows: &[R], file: &Option<PathBuf>) -> Result<usize, Box<dyn Error + Sync + Send + 'static>> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    for row in rows {
+        writer.serialize(row)?;
+    }
+
+    let content = writer.into_inner().map_err(|err| err.into_error())?;
+
+    match file {
+        Some(file) => fs::write(file, content)?,
+        None => io::Write::write_all(&mut io::stdout(), &content)?,
+    }
+
+    Ok(rows.len())
+}
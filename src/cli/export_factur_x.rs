@@ -0,0 +1,40 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::select_invoice_or_use_default::select_invoice_or_use_default;
+use crate::entities::factur_x::{FacturX, FacturXProfile};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Factur-X XML of an invoice with the profile of the settings unless `profile` is given,
+/// written as `{ref}.xml` in the current directory unless `file` is given
+pub fn export_factur_x(context_parameters: ContextParameters, invoice_ref: &Option<String>, profile: &Option<FacturXProfile>, file: &Option<PathBuf>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Export Factur-X");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let invoice = select_invoice_or_use_default(&file_manager, invoice_ref)?;
+
+    let settings = file_manager.get_settings()?;
+
+    let customers = file_manager.get_all_customers()?;
+
+    let customer = customers
+        .get(&invoice.customer_id)
+        .ok_or_else(|| CliError::CustomerNotFound(invoice.customer_id.to_owned()))?;
+
+    let profile = profile.or(settings.factur_x).unwrap_or_default();
+
+    let path = file.clone().unwrap_or_else(|| PathBuf::from(invoice.get_ref().unwrap() + ".xml"));
+
+    fs::write(&path, FacturX::new(&invoice, customer, &settings, profile).to_string())?;
+
+    println!("Factur-X {} of {} exported in : {}", profile, invoice.get_ref().unwrap(), path.to_string_lossy());
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::Datelike;
+use log::trace;
+
+use crate::entities::fec::Fec;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// FEC of the year, the previous year by default, written in the current directory unless `file` is given
+pub fn export_fec(context_parameters: ContextParameters, year: &Option<i32>, file: &Option<PathBuf>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Export FEC");
+
+    let year = year.unwrap_or(chrono::Local::now().year() - 1);
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let settings = file_manager.get_settings()?;
+
+    let fec = Fec::new(
+        year,
+        &file_manager.get_all_invoices()?,
+        &file_manager.get_all_payments()?,
+        &file_manager.get_all_customers()?,
+        &settings.get_default_vat_rate(),
+        settings.get_activity(),
+        &settings.get_accounting(),
+    );
+
+    let path = file.clone().unwrap_or_else(|| PathBuf::from(Fec::get_filename(&settings.enterprise.siren.to_string(), year)));
+
+    fs::write(&path, fec.to_string())?;
+
+    println!("FEC {} exported in : {} ({} lines)", year, path.to_string_lossy(), fec.entries.len());
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::select_invoice_or_use_default::select_invoice_or_use_default;
+use crate::entities::ubl::Ubl;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Peppol BIS Billing 3.0 UBL of an invoice, written as `{ref}.ubl.xml` in the current directory
+/// unless `file` is given. Nothing is written when the invoice breaks a rule
+pub fn export_ubl(context_parameters: ContextParameters, invoice_ref: &Option<String>, file: &Option<PathBuf>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Export UBL");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let invoice = select_invoice_or_use_default(&file_manager, invoice_ref)?;
+
+    let settings = file_manager.get_settings()?;
+
+    let customers = file_manager.get_all_customers()?;
+
+    let customer = customers
+        .get(&invoice.customer_id)
+        .ok_or_else(|| CliError::CustomerNotFound(invoice.customer_id.to_owned()))?;
+
+    let ubl = Ubl::new(&invoice, customer, &settings);
+
+    let issues = ubl.validate();
+
+    if !issues.is_empty() {
+        for issue in issues.iter() {
+            println!("{}", issue);
+        }
+
+        return Err(Box::new(CliError::InvalidInvoice(format!("{} breaks Peppol rules, nothing exported", invoice.get_ref().unwrap()))));
+    }
+
+    let path = file.clone().unwrap_or_else(|| PathBuf::from(invoice.get_ref().unwrap() + ".ubl.xml"));
+
+    fs::write(&path, ubl.to_string())?;
+
+    println!("UBL of {} exported in : {}", invoice.get_ref().unwrap(), path.to_string_lossy());
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use std::error::Error;
+
+use chrono::Local;
+use log::trace;
+
+use crate::cli::utils::get_customer_payment_terms::get_customer_payment_terms;
+use crate::cli::utils::select_draft_or_use_default::select_draft_or_use_default;
+use crate::cli::utils::warn_thresholds::warn_thresholds;
+use crate::entities::invoice::Invoice;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Issue the draft: it is dated today, numbered and can not be edited anymore
+pub fn finalize_draft(context_parameters: ContextParameters, draft_id: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Finalize draft");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let (draft_id, draft) = select_draft_or_use_default(&file_manager, draft_id)?;
+
+    let date = Local::now().date_naive();
+    let payment_terms = get_customer_payment_terms(&file_manager, &draft.customer_id)?;
+
+    let invoice = Invoice {
+        date,
+        due_date: Some(payment_terms.get_due_date(date)),
+        invoice_day_id: None,
+        reference: None,
+        sequence: None,
+        ..draft
+    };
+
+    warn_thresholds(&file_manager, &invoice)?;
+
+    let invoice_path = file_manager.create_invoice(invoice)?;
+
+    file_manager.remove_draft(&draft_id)?;
+
+    println!("Draft {} issued in : {}", draft_id, invoice_path.to_string_lossy());
+
+    let mut invoice_path_output = invoice_path.clone();
+    invoice_path_output.set_extension("pdf");
+
+    file_manager.generate_invoice(invoice_path.as_path(), invoice_path_output.file_name().unwrap().to_str().unwrap())?;
+
+    Ok(())
+}
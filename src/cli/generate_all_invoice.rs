@@ -21,10 +21,10 @@ pub fn generate_all_invoice(context_parameters: ContextParameters) -> Result<(),
 
         let invoice_output_name = invoice.get_ref().unwrap() + ".pdf";
 
-        let output_path = file_manager.generate_invoice(&(invoice_input_path.as_path()), &invoice_output_name);
+        let output_path = file_manager.generate_invoice(invoice_input_path.as_path(), &invoice_output_name);
 
         println!("Invoice generated in : {}", output_path.unwrap().to_string_lossy());
     });
 
-    return Ok(());
+    Ok(())
 }
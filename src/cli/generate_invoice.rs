@@ -21,8 +21,8 @@ pub fn generate_invoice(context_parameters: ContextParameters, invoice_ref: &Opt
 
     let invoice_output_name = invoice_selected.get_ref().unwrap() + ".pdf";
 
-    let output_path = file_manager.generate_invoice(&(invoice_input_path.as_path()), &invoice_output_name)?;
+    let output_path = file_manager.generate_invoice(invoice_input_path.as_path(), &invoice_output_name)?;
 
     println!("Invoice generated in : {}", output_path.to_string_lossy());
-    return Ok(());
+    Ok(())
 }
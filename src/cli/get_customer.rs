@@ -2,21 +2,37 @@ use std::error::Error;
 
 use log::trace;
 
+use crate::cli::output::customer_row::CustomerRow;
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
 use crate::cli::utils::select_customer_or_use_default::select_customer_or_use_default;
-use crate::entities::customer::Customer;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 
-pub fn get_customer(context_parameters: ContextParameters, customer_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+pub fn get_customer(context_parameters: ContextParameters, output_format: OutputFormat, customer_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Get customer");
 
     let file_manager = FileManager::new(context_parameters)?;
 
-    let customer_selected: Customer = select_customer_or_use_default(&file_manager, customer_ref)?.1;
+    let (customer_id, customer_selected) = select_customer_or_use_default(&file_manager, customer_ref)?;
 
-    println!("Your customer : {}\n", customer_selected.name);
+    let row = CustomerRow::new(&customer_id, &customer_selected);
 
-    println!("Address : \n{}\n{} {}", customer_selected.address, customer_selected.postal, customer_selected.city);
+    print_output(output_format, &row, &[&row], || {
+        println!("Your customer : {}\n", customer_selected.name);
 
-    Ok(())
+        println!("Address : \n{}\n{} {}", customer_selected.address, customer_selected.postal, customer_selected.city);
+
+        if let Some(payment_terms) = &customer_selected.payment_terms {
+            println!("\nPayment terms : {}", payment_terms);
+        }
+
+        if let Some(siren) = &customer_selected.siren {
+            println!("SIREN : {}", siren);
+        }
+
+        if let Some(tva) = &customer_selected.tva {
+            println!("TVA Number : {}", tva);
+        }
+    })
 }
@@ -1,29 +1,118 @@
 use std::error::Error;
 
+use chrono::Local;
 use log::trace;
+use serde::Serialize;
 
+use crate::cli::output::invoice_row::InvoiceRow;
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::cli::output::product_row::ProductRow;
+use crate::cli::utils::print_totals::print_totals;
 use crate::cli::utils::select_invoice_or_use_default::select_invoice_or_use_default;
+use crate::entities::amount::Amount;
 use crate::entities::invoice::Invoice;
+use crate::entities::invoice_totals::VatLine;
+use crate::entities::payment::PaymentMethod;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn get_invoice(context_parameters: ContextParameters, invoice_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+#[derive(Serialize)]
+struct PaymentRow {
+    date: String,
+    amount: Amount,
+    method: PaymentMethod,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reference: Option<String>,
+}
+
+/// Invoice printed as JSON or YAML, its products are the CSV rows
+#[derive(Serialize)]
+struct InvoiceDetail {
+    #[serde(flatten)]
+    invoice: InvoiceRow,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    credited_invoice_ref: Option<String>,
+    credit_notes: Vec<String>,
+    vat_lines: Vec<VatLine>,
+    products: Vec<ProductRow>,
+    payments: Vec<PaymentRow>,
+}
+
+pub fn get_invoice(context_parameters: ContextParameters, output_format: OutputFormat, invoice_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Get invoice");
 
     let file_manager = FileManager::new(context_parameters.clone())?;
 
     let invoice_selected: Invoice = select_invoice_or_use_default(&file_manager, invoice_ref)?;
 
-    println!("Your invoice : {}", invoice_selected.get_ref().unwrap().to_string());
-    println!("{}\n", invoice_selected.title);
-    println!("Date : {}", invoice_selected.date.to_string());
+    let all_invoices = file_manager.get_all_invoices()?;
+    let credit_notes = invoice_selected.get_credit_notes(&all_invoices);
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+    let totals = invoice_selected.get_totals(&default_vat_rate);
+
+    let payments = file_manager.get_invoice_payments(&invoice_selected.get_ref().unwrap())?;
+
+    let total_due = invoice_selected.get_amount_due(&all_invoices, &default_vat_rate);
+    let paid: Amount = payments.iter().map(|payment| payment.amount).sum();
+    let status = invoice_selected.get_payment_status(total_due, &payments, Local::now().date_naive());
+
+    let products: Vec<ProductRow> = invoice_selected
+        .products
+        .iter()
+        .map(|product| ProductRow::new(product, &default_vat_rate))
+        .collect();
+
+    let invoice_detail = InvoiceDetail {
+        invoice: InvoiceRow::new(invoice_selected.get_ref().unwrap(), &invoice_selected, &default_vat_rate)
+            .with_payment_status(status, total_due - paid),
+        credited_invoice_ref: invoice_selected.credited_invoice_ref.clone(),
+        credit_notes: credit_notes.iter().filter_map(|credit_note| credit_note.get_ref()).collect(),
+        vat_lines: totals.vat_lines.clone(),
+        products,
+        payments: payments
+            .iter()
+            .map(|payment| PaymentRow {
+                date: payment.date.to_string(),
+                amount: payment.amount,
+                method: payment.method.clone(),
+                reference: payment.reference.clone(),
+            })
+            .collect(),
+    };
+
+    print_output(output_format, &invoice_detail, &invoice_detail.products, || {
+        println!("Your {} : {}", invoice_selected.kind, invoice_selected.get_ref().unwrap());
+        println!("{}\n", invoice_selected.title);
+        println!("Date : {}", invoice_selected.date);
+
+        if let Some(credited_invoice_ref) = &invoice_selected.credited_invoice_ref {
+            println!("Credited invoice : {}", credited_invoice_ref);
+        }
+
+        if !credit_notes.is_empty() {
+            println!("Credit notes :");
+            for credit_note in credit_notes.iter() {
+                println!(" - {}", credit_note);
+            }
+        }
 
-    println!("Products : ");
-    for product in invoice_selected.products.iter() {
-        println!(" - {} : {} * {}€ = {}€", product.description, product.quantity, product.price, product.get_total_price());
-    }
+        println!("Products : ");
+        for product in invoice_selected.products.iter() {
+            println!(" - {} : {} * {}€ = {}€ (TVA {})", product.description, product.quantity, product.price, product.get_total_price(), product.get_vat_rate(&default_vat_rate));
+        }
 
-    println!("\nTotal price : {} €", invoice_selected.get_total_price());
+        println!();
+        print_totals(&totals);
 
-    Ok(())
+        println!();
+        println!("Payments : ");
+        for payment in payments.iter() {
+            println!(" - {}", payment);
+        }
+        println!("Status : {} (due {})", status, invoice_selected.get_due_date());
+    })
 }
@@ -0,0 +1,76 @@
+use std::error::Error;
+
+use chrono::Local;
+use log::trace;
+use serde::Serialize;
+
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::cli::output::product_row::ProductRow;
+use crate::cli::output::quote_row::QuoteRow;
+use crate::cli::utils::print_totals::print_totals;
+use crate::cli::utils::select_quote_or_use_default::select_quote_or_use_default;
+use crate::entities::invoice_totals::VatLine;
+use crate::entities::quote::Quote;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Quote printed as JSON or YAML, its products are the CSV rows
+#[derive(Serialize)]
+struct QuoteDetail {
+    #[serde(flatten)]
+    quote: QuoteRow,
+    deposits: Vec<String>,
+    vat_lines: Vec<VatLine>,
+    products: Vec<ProductRow>,
+}
+
+pub fn get_quote(context_parameters: ContextParameters, output_format: OutputFormat, quote_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Get quote");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let quote_selected: Quote = select_quote_or_use_default(&file_manager, quote_ref)?;
+
+    let all_invoices = file_manager.get_all_invoices()?;
+    let deposits = quote_selected.get_deposits(&all_invoices);
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+    let totals = quote_selected.get_totals(&default_vat_rate);
+
+    let quote_detail = QuoteDetail {
+        quote: QuoteRow::new(&quote_selected, &default_vat_rate, Local::now().date_naive()),
+        deposits: deposits.iter().filter_map(|deposit| deposit.get_ref()).collect(),
+        vat_lines: totals.vat_lines.clone(),
+        products: quote_selected
+            .products
+            .iter()
+            .map(|product| ProductRow::new(product, &default_vat_rate))
+            .collect(),
+    };
+
+    print_output(output_format, &quote_detail, &quote_detail.products, || {
+        println!("Your quote : {}", quote_selected.get_ref().unwrap());
+        println!("{}\n", quote_selected.title);
+        println!("Date : {}", quote_selected.date);
+        println!("Valid until : {}", quote_selected.validity_date);
+        println!("Status : {}", quote_selected.status);
+
+        for deposit in deposits.iter() {
+            println!("Deposit : {}", deposit);
+        }
+
+        if let Some(invoice_ref) = &quote_selected.invoice_ref {
+            println!("Invoice : {}", invoice_ref);
+        }
+
+        println!("Products : ");
+        for product in quote_selected.products.iter() {
+            println!(" - {} : {} * {}€ = {}€ (TVA {})", product.description, product.quantity, product.price, product.get_total_price(), product.get_vat_rate(&default_vat_rate));
+        }
+
+        println!();
+        print_totals(&totals);
+    })
+}
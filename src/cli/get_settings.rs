@@ -2,40 +2,63 @@ use std::error::Error;
 
 use log::trace;
 
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::entities::activity::Activity;
 use crate::entities::settings::Settings;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn get_settings(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+pub fn get_settings(context_parameters: ContextParameters, output_format: OutputFormat) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Get settings");
 
     let file_manager = FileManager::new(context_parameters)?;
 
     let settings: Settings = file_manager.get_settings()?;
 
-    println!("Your settings :\n");
-
-    println!("Enterprise :");
-    println!("Name: {}", settings.enterprise.name);
-    println!("Siren Number: {}", settings.enterprise.siren); // Assuming Siren has a `number` field
-    println!("Email: {}", settings.enterprise.email);
-    println!("Address: {}", settings.enterprise.address);
-    println!("City: {}", settings.enterprise.city);
-    println!("Postal Code: {}", settings.enterprise.postal);
-    println!("Phone: {}", settings.enterprise.phone);
-    println!("Title: {}", settings.enterprise.title);
-    
-    if settings.enterprise.tva.len() > 0 {
-        println!("TVA Number: {}", settings.enterprise.tva);
-    } else {
-        println!("No TVA Number")
-    }
-
-    println!("\nInvoice clauses :");
-
-    println!("Politeness: {}", settings.politeness);
-    println!("Law rules: {}", settings.law_rules);
-
-    Ok(())
+    print_output(output_format, &settings, &[&settings], || {
+        println!("Your settings :\n");
+
+        println!("Enterprise :");
+        println!("Name: {}", settings.enterprise.name);
+        println!("Siren Number: {}", settings.enterprise.siren); // Assuming Siren has a `number` field
+        println!("Email: {}", settings.enterprise.email);
+        println!("Address: {}", settings.enterprise.address);
+        println!("City: {}", settings.enterprise.city);
+        println!("Postal Code: {}", settings.enterprise.postal);
+        println!("Phone: {}", settings.enterprise.phone);
+        println!("Title: {}", settings.enterprise.title);
+
+        if !settings.enterprise.tva.is_empty() {
+            println!("TVA Number: {}", settings.enterprise.tva);
+        } else {
+            println!("No TVA Number")
+        }
+
+        println!("\nInvoice clauses :");
+
+        match &settings.numbering {
+            Some(numbering_policy) => println!("Invoice numbering: {}", numbering_policy),
+            None => println!("Invoice numbering: dated references (YYYYMMDDNN)"),
+        }
+        println!("Payment terms: {}", settings.get_payment_terms());
+
+        match settings.factur_x {
+            Some(profile) => println!("Factur-X: {} profile embedded in the invoices", profile),
+            None => println!("Factur-X: none"),
+        }
+
+        let thresholds = settings.get_thresholds();
+        println!("VAT franchise threshold: {} € ({} € increased)", thresholds.vat_franchise, thresholds.vat_franchise_increased);
+        println!("Micro-enterprise ceiling: {} €", thresholds.micro_enterprise);
+
+        let contribution_rates = settings.get_contribution_rates();
+        println!("Activity: {}", settings.get_activity().get_label());
+        Activity::all_activities().iter().for_each(|activity| {
+            println!("Contributions {}: {}", activity.get_label(), contribution_rates.get_rate(activity))
+        });
+        println!("Politeness: {}", settings.politeness);
+        println!("Law rules: {}", settings.law_rules);
+    })
 }
@@ -0,0 +1,128 @@
+use std::error::Error;
+use std::path::Path;
+
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::read_csv_records::read_csv_records;
+use crate::entities::csv_export::CsvContent;
+use crate::entities::csv_import::{
+    parse_customers, parse_invoices, ColumnMapping, CsvRecord, RowError, CUSTOMER_FIELDS, INVOICE_FIELDS,
+};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Import customers or historical invoices, nothing is written when a row is invalid.
+/// Customers and invoices already in the folder are skipped.
+pub fn import_csv(context_parameters: ContextParameters, content: &CsvContent, file: &Path, mappings: &[ColumnMapping], dry_run: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Import CSV");
+
+    let fields: &[&str] = match content {
+        CsvContent::Customers => &CUSTOMER_FIELDS,
+        CsvContent::Invoices => &INVOICE_FIELDS,
+    };
+
+    ColumnMapping::validate(mappings, fields).map_err(CliError::InvalidImport)?;
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let records = read_csv_records(file, mappings)?;
+
+    let action = if dry_run { "to import" } else { "imported" };
+
+    let (imported, skipped) = match content {
+        CsvContent::Customers => import_customers(&file_manager, &records, dry_run, action)?,
+        CsvContent::Invoices => import_invoices(&file_manager, &records, dry_run, action)?,
+    };
+
+    println!("{} {} {}, {} skipped", imported, content, action, skipped);
+
+    if dry_run {
+        println!("Dry run : nothing was written");
+    }
+
+    Ok(())
+}
+
+fn import_customers(file_manager: &FileManager, records: &[CsvRecord], dry_run: bool, action: &str) -> Result<(usize, usize), Box<dyn Error + Sync + Send + 'static>> {
+    let customers = parse_customers(records).map_err(report_row_errors)?;
+
+    let all_customers = file_manager.get_all_customers()?;
+
+    let mut imported = 0;
+
+    for imported_customer in customers.iter() {
+        let customer = &imported_customer.customer;
+
+        if all_customers.contains_key(&customer.serialized_name()) {
+            println!("Row {}: customer {} already exists, skipped", imported_customer.row, customer.serialized_name());
+            continue;
+        }
+
+        if !dry_run {
+            file_manager.create_customer(customer.clone())?;
+        }
+
+        imported += 1;
+
+        println!("Row {}: customer {} {}", imported_customer.row, customer.name, action);
+    }
+
+    Ok((imported, customers.len() - imported))
+}
+
+fn import_invoices(file_manager: &FileManager, records: &[CsvRecord], dry_run: bool, action: &str) -> Result<(usize, usize), Box<dyn Error + Sync + Send + 'static>> {
+    let settings = file_manager.get_settings()?;
+
+    let invoices = parse_invoices(records, &file_manager.get_all_customers()?, &settings.get_payment_terms())
+        .map_err(report_row_errors)?;
+
+    // Every file to write is checked free before the first is written
+    let mut to_write = vec![];
+
+    for imported_invoice in invoices.iter() {
+        let reference = imported_invoice.invoice.get_ref();
+
+        if let Some(reference) = reference.filter(|reference| {
+            file_manager.get_invoice_path().join(reference.to_owned() + ".yaml").exists()
+        }) {
+            println!("Row {}: invoice {} already exists, skipped", imported_invoice.row, reference);
+            continue;
+        }
+
+        to_write.push(imported_invoice);
+    }
+
+    for imported_invoice in to_write.iter() {
+        let invoice = &imported_invoice.invoice;
+
+        // Invoices without reference get the next number of the folder
+        let reference = if dry_run {
+            invoice.get_ref().unwrap_or_else(|| "without reference".to_string())
+        } else {
+            let invoice_path = file_manager.create_invoice(invoice.clone())?;
+            invoice_path.file_stem().unwrap_or_default().to_string_lossy().to_string()
+        };
+
+        println!(
+            "Row {}: invoice {} of {} for {} ({} lines) {}",
+            imported_invoice.row,
+            reference,
+            invoice.date,
+            invoice.customer_id,
+            invoice.products.len(),
+            action
+        );
+    }
+
+    Ok((to_write.len(), invoices.len() - to_write.len()))
+}
+
+fn report_row_errors(errors: Vec<RowError>) -> Box<dyn Error + Sync + Send + 'static> {
+    for error in errors.iter() {
+        println!("{}", error);
+    }
+
+    Box::new(CliError::InvalidImport(format!("{} row(s) in error, nothing was imported", errors.len())))
+}
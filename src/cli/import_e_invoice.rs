@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::read_e_invoice_xml::read_e_invoice_xml;
+use crate::entities::e_invoice_import::{parse_e_invoice, ImportedEInvoice};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Import invoices from CII or UBL files or Factur-X PDF, with their buyers not yet customers.
+/// Nothing is written when a file is invalid or two files have the same ID, invoices already
+/// in the folder are skipped.
+pub fn import_e_invoice(context_parameters: ContextParameters, files: &[PathBuf], dry_run: bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Import e-invoice");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let settings = file_manager.get_settings()?;
+
+    let mut customers = file_manager.get_all_customers()?;
+
+    let mut imported_invoices: Vec<(&PathBuf, ImportedEInvoice)> = vec![];
+
+    let mut new_customers = HashMap::new();
+
+    for file in files {
+        let xml = read_e_invoice_xml(file)?;
+
+        let imported = parse_e_invoice(&xml, &customers, &settings.get_payment_terms(), &settings.get_default_vat_rate(), &settings.enterprise.siren)
+            .map_err(|err| CliError::InvalidImport(format!("{}: {}", file.to_string_lossy(), err)))?;
+
+        // Buyers of the next files are matched with the new customers too
+        if let Some(new_customer) = &imported.new_customer {
+            customers.insert(imported.invoice.customer_id.to_owned(), new_customer.clone());
+            new_customers.insert(imported.invoice.customer_id.to_owned(), new_customer.clone());
+        }
+
+        imported_invoices.push((file, imported));
+    }
+
+    // Every file to write is checked free, and each ID given once, before the first is written
+    let mut files_by_reference: HashMap<String, &PathBuf> = HashMap::new();
+
+    let mut to_write = vec![];
+
+    for (file, imported) in imported_invoices.iter() {
+        let reference = imported.invoice.get_ref().unwrap_or_default();
+
+        if let Some(other_file) = files_by_reference.insert(reference.to_owned(), file) {
+            return Err(Box::new(CliError::InvalidImport(format!(
+                "{} and {} have the same ID {}",
+                other_file.to_string_lossy(),
+                file.to_string_lossy(),
+                reference
+            ))));
+        }
+
+        if file_manager.get_invoice_path().join(reference.to_owned() + ".yaml").exists() {
+            println!("{}: invoice {} already exists, skipped", file.to_string_lossy(), reference);
+            continue;
+        }
+
+        to_write.push((file, imported));
+    }
+
+    let action = if dry_run { "to import" } else { "imported" };
+
+    for (file, imported) in to_write.iter() {
+        let invoice = &imported.invoice;
+
+        let reference = invoice.get_ref().unwrap_or_default();
+
+        // Created with the first invoice written, the others of the same buyer may be skipped
+        if let Some(new_customer) = new_customers.remove(&invoice.customer_id) {
+            if !dry_run {
+                file_manager.create_customer(new_customer.clone())?;
+            }
+
+            println!("{}: customer {} {}", file.to_string_lossy(), new_customer.name, action);
+        }
+
+        if !dry_run {
+            file_manager.create_invoice(invoice.clone())?;
+        }
+
+        println!(
+            "{}: {} invoice {} of {} for {} ({} lines) {}",
+            file.to_string_lossy(),
+            imported.syntax,
+            reference,
+            invoice.date,
+            invoice.customer_id,
+            invoice.products.len(),
+            action
+        );
+
+        for warning in imported.warnings.iter() {
+            println!("  Warning: {}", warning);
+        }
+
+        for path in imported.unmapped.iter() {
+            println!("  Not mapped: {}", path);
+        }
+    }
+
+    println!("{} invoices {}, {} skipped", to_write.len(), action, imported_invoices.len() - to_write.len());
+
+    if dry_run {
+        println!("Dry run : nothing was written");
+    }
+
+    Ok(())
+}
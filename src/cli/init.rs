@@ -1,51 +1,54 @@
 use std::error::Error;
 
-use dialoguer::{Editor, Input};
+use dialoguer::Editor;
 use log::trace;
 
+use crate::cli::settings_input::SettingsInput;
+use crate::cli::utils::input_or_ask::input_or_ask;
+use crate::cli::utils::input_siren::input_siren;
+use crate::cli::utils::is_interactive::is_interactive;
 use crate::entities::settings::{Enterprise, Settings};
-use crate::entities::siren::Siren;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn initiate_invoice_directory(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+const DEFAULT_LAW_RULES: &str = "Payment Terms: Net 30 days from the invoice date. In accordance with the terms and conditions of sale, a late payment penalty of 40€ per month (or the maximum rate permitted by law, whichever is lower) will be applied to all overdue balances. Interest will accrue daily from the due date until full payment is received. In addition to the late payment penalty, the purchaser agrees to reimburse the seller for all costs incurred in collecting any late payments, including, but not limited to, legal fees and collection agency charges.";
+
+pub fn initiate_invoice_directory(context_parameters: ContextParameters, settings_input: &SettingsInput) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Initiate invoice directory");
 
-    let name: String =
-        Input::new().with_prompt("Enterprise name").interact_text().unwrap();
+    let settings_input = settings_input.with_file_values()?;
+    let enterprise = settings_input.enterprise;
 
-    let title = Input::new().with_prompt("Job title").interact_text().unwrap();
+    let name = input_or_ask("Enterprise name", "name", enterprise.name, None, false)?;
 
-    let siren = loop {
-        let siren_string: String =
-            Input::new().with_prompt("SIREN").interact_text().unwrap();
+    let title = input_or_ask("Job title", "title", enterprise.title, None, false)?;
 
-        match Siren::new(&siren_string) {
-            Ok(some_siren) => break some_siren,
-            Err(error) => println!("{}", error),
-        };
-    };
+    let siren = input_siren(enterprise.siren, None)?;
+
+    let email = input_or_ask("Email", "email", enterprise.email, None, false)?;
 
-    let email = Input::new().with_prompt("Email").interact_text().unwrap();
+    let address = input_or_ask("Address", "address", enterprise.address, None, false)?;
 
-    let address = Input::new().with_prompt("Address").interact_text().unwrap();
+    let city = input_or_ask("City", "city", enterprise.city, None, false)?;
 
-    let city = Input::new().with_prompt("City").interact_text().unwrap();
+    let postal = input_or_ask("Postal code", "postal", enterprise.postal, None, false)?;
 
-    let postal = Input::new().with_prompt("Postal code").interact_text().unwrap();
+    let phone = input_or_ask("Phone number", "phone", enterprise.phone, None, false)?;
 
-    let phone = Input::new().with_prompt("Phone number").interact_text().unwrap();
-    
-    let tva = Input::new().with_prompt("TVA Number").allow_empty(true).interact_text().unwrap();
+    let tva = input_or_ask("TVA Number", "tva", enterprise.tva, Some(String::new()), true)?;
 
-    let politeness = Input::new()
-        .with_prompt("Politeness")
-        .with_initial_text("Thank you".to_string())
-        .interact_text()
-        .unwrap();
+    let politeness = input_or_ask("Politeness", "politeness", settings_input.politeness, Some("Thank you".to_string()), false)?;
+
+    let law_rules = match settings_input.law_rules {
+        Some(law_rules) => law_rules,
+        None if is_interactive() => Editor::new().edit(DEFAULT_LAW_RULES).unwrap().unwrap(),
+        None => DEFAULT_LAW_RULES.to_string(),
+    };
 
-    let law_rules = Editor::new().edit("Payment Terms: Net 30 days from the invoice date. In accordance with the terms and conditions of sale, a late payment penalty of 40€ per month (or the maximum rate permitted by law, whichever is lower) will be applied to all overdue balances. Interest will accrue daily from the due date until full payment is received. In addition to the late payment penalty, the purchaser agrees to reimburse the seller for all costs incurred in collecting any late payments, including, but not limited to, legal fees and collection agency charges.").unwrap().unwrap();
+    if let Some(numbering) = &settings_input.numbering {
+        numbering.validate()?;
+    }
 
     let settings = Settings {
         enterprise: Enterprise {
@@ -57,10 +60,19 @@ pub fn initiate_invoice_directory(context_parameters: ContextParameters) -> Resu
             city,
             postal,
             phone,
-            tva
+            tva,
+            iban: enterprise.iban,
         },
         law_rules,
         politeness,
+        default_vat_rate: settings_input.default_vat_rate,
+        payment_terms: settings_input.payment_terms,
+        numbering: settings_input.numbering,
+        thresholds: settings_input.thresholds,
+        activity: settings_input.activity,
+        contribution_rates: settings_input.contribution_rates,
+        accounting: settings_input.accounting,
+        factur_x: settings_input.factur_x,
     };
 
     let file_manager = FileManager::init(context_parameters)?;
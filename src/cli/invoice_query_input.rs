@@ -0,0 +1,71 @@
+use chrono::NaiveDate;
+use clap::Args;
+
+use crate::entities::amount::Amount;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice_query::{InvoiceQuery, InvoiceSort};
+use crate::entities::payment_status::PaymentStatus;
+
+/// Filters of `invoice list`
+#[derive(Args, Clone)]
+pub struct InvoiceQueryInput {
+    /// Only show invoices not fully paid
+    #[arg(long, conflicts_with = "status")]
+    pub unpaid: bool,
+    /// Customer id
+    #[arg(long)]
+    pub customer: Option<String>,
+    /// First date included (YYYY-MM-DD)
+    #[arg(long)]
+    pub from: Option<NaiveDate>,
+    /// Last date included (YYYY-MM-DD)
+    #[arg(long)]
+    pub to: Option<NaiveDate>,
+    /// Minimum TTC total
+    #[arg(long)]
+    pub min_amount: Option<Amount>,
+    /// Maximum TTC total
+    #[arg(long)]
+    pub max_amount: Option<Amount>,
+    /// Text searched in the title and the products
+    #[arg(long)]
+    pub search: Option<String>,
+    /// unpaid, partial, paid or overdue, can be repeated
+    #[arg(long)]
+    pub status: Vec<PaymentStatus>,
+    /// invoice, credit_note or deposit, can be repeated
+    #[arg(long)]
+    pub kind: Vec<DocumentKind>,
+    /// date, reference, customer or amount
+    #[arg(long, default_value_t = InvoiceSort::Date)]
+    pub sort: InvoiceSort,
+    /// Sort in descending order
+    #[arg(long)]
+    pub desc: bool,
+    /// Maximum number of invoices
+    #[arg(long)]
+    pub limit: Option<usize>,
+}
+
+impl From<&InvoiceQueryInput> for InvoiceQuery {
+    fn from(input: &InvoiceQueryInput) -> InvoiceQuery {
+        let statuses = match input.unpaid {
+            true => PaymentStatus::all_statuses().into_iter().filter(|status| !status.is_paid()).collect(),
+            false => input.status.clone(),
+        };
+
+        InvoiceQuery {
+            customer_id: input.customer.clone(),
+            from: input.from,
+            to: input.to,
+            min_amount: input.min_amount,
+            max_amount: input.max_amount,
+            text: input.search.clone(),
+            statuses,
+            kinds: input.kind.clone(),
+            sort: input.sort,
+            descending: input.desc,
+            limit: input.limit,
+        }
+    }
+}
@@ -2,27 +2,36 @@ use std::error::Error;
 
 use log::trace;
 
+use crate::cli::output::customer_row::CustomerRow;
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn list_customers(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+pub fn list_customers(context_parameters: ContextParameters, output_format: OutputFormat) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Get customers");
 
     let file_manager = FileManager::new(context_parameters)?;
 
-    let all_customers = file_manager.get_all_customers()?;
+    let mut all_customers: Vec<_> = file_manager.get_all_customers()?.into_iter().collect();
+    all_customers.sort_by(|(customer_id, _), (other_customer_id, _)| customer_id.cmp(other_customer_id));
 
-    let mut plural_offset = "";
-    if all_customers.len() > 1 {
-        plural_offset = "s";
-    }
+    let rows: Vec<CustomerRow> = all_customers
+        .iter()
+        .map(|(customer_id, customer)| CustomerRow::new(customer_id, customer))
+        .collect();
 
-    println!("Get {} customer{}\n", all_customers.len(), plural_offset);
+    print_output(output_format, &rows, &rows, || {
+        let mut plural_offset = "";
+        if all_customers.len() > 1 {
+            plural_offset = "s";
+        }
 
-    all_customers
-        .iter()
-        .for_each(|(_, customer)| println!("{}", customer.name));
+        println!("Get {} customer{}\n", all_customers.len(), plural_offset);
 
-    Ok(())
+        all_customers
+            .iter()
+            .for_each(|(_, customer)| println!("{}", customer.name));
+    })
 }
@@ -0,0 +1,39 @@
+use std::error::Error;
+
+use log::trace;
+
+use crate::cli::output::invoice_row::InvoiceRow;
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub fn list_drafts(context_parameters: ContextParameters, output_format: OutputFormat) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== List drafts");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let mut all_drafts: Vec<_> = file_manager.get_all_drafts()?.into_iter().collect();
+    all_drafts.sort_by(|(draft_id, _), (other_draft_id, _)| draft_id.cmp(other_draft_id));
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+    let rows: Vec<InvoiceRow> = all_drafts
+        .iter()
+        .map(|(draft_id, draft)| InvoiceRow::new(draft_id.to_owned(), draft, &default_vat_rate))
+        .collect();
+
+    print_output(output_format, &rows, &rows, || {
+        let mut plural_offset = "";
+        if all_drafts.len() > 1 {
+            plural_offset = "s";
+        }
+
+        println!("Get {} draft{}\n", all_drafts.len(), plural_offset);
+
+        all_drafts.iter().for_each(|(draft_id, draft)| {
+            println!("{} - {} - {} - {} €", draft_id, draft.customer_id, draft.title, draft.get_total_price())
+        });
+    })
+}
@@ -1,28 +1,65 @@
 use std::error::Error;
 
+use chrono::Local;
 use log::trace;
 
+use crate::cli::invoice_query_input::InvoiceQueryInput;
+use crate::cli::output::invoice_row::InvoiceRow;
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::entities::amount::Amount;
+use crate::entities::invoice_query::InvoiceQuery;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn list_invoices(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+pub fn list_invoices(context_parameters: ContextParameters, output_format: OutputFormat, query_input: &InvoiceQueryInput) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== List invoices");
 
     let file_manager = FileManager::new(context_parameters)?;
 
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+    let all_payments = file_manager.get_all_payments()?;
+    let today = Local::now().date_naive();
+
+    // Amounts due deduce the credit notes, even the ones filtered out
     let all_invoices = file_manager.get_all_invoices()?;
 
-    let mut plural_offset = "";
-    if all_invoices.len() > 1 {
-        plural_offset = "s";
-    }
+    let found_invoices = file_manager.search_invoices(&InvoiceQuery::from(query_input))?;
+
+    let found_invoices: Vec<_> = found_invoices
+        .iter()
+        .map(|invoice| {
+            let payments = all_payments
+                .get(&invoice.get_ref().unwrap())
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let total_due = invoice.get_amount_due(&all_invoices, &default_vat_rate);
+            let paid: Amount = payments.iter().map(|payment| payment.amount).sum();
+            let status = invoice.get_payment_status(total_due, payments, today);
 
-    println!("Get {} invoice{}\n", all_invoices.len(), plural_offset);
+            (invoice, status, total_due - paid)
+        })
+        .collect();
 
-    all_invoices
+    let rows: Vec<InvoiceRow> = found_invoices
         .iter()
-        .for_each(|invoice| println!("{}", invoice));
+        .map(|(invoice, status, remaining)| {
+            InvoiceRow::new(invoice.get_ref().unwrap(), invoice, &default_vat_rate)
+                .with_payment_status(*status, *remaining)
+        })
+        .collect();
+
+    print_output(output_format, &rows, &rows, || {
+        let mut plural_offset = "";
+        if found_invoices.len() > 1 {
+            plural_offset = "s";
+        }
+
+        println!("Get {} invoice{}\n", found_invoices.len(), plural_offset);
 
-    Ok(())
+        found_invoices
+            .iter()
+            .for_each(|(invoice, status, _)| println!("{} - {}", invoice, status));
+    })
 }
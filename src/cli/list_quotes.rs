@@ -0,0 +1,44 @@
+use std::error::Error;
+
+use chrono::Local;
+use log::trace;
+
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::cli::output::quote_row::QuoteRow;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub fn list_quotes(context_parameters: ContextParameters, output_format: OutputFormat) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== List quotes");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let all_quotes = file_manager.get_all_quotes()?;
+    let today = Local::now().date_naive();
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+    let rows: Vec<QuoteRow> = all_quotes
+        .iter()
+        .map(|quote| QuoteRow::new(quote, &default_vat_rate, today))
+        .collect();
+
+    print_output(output_format, &rows, &rows, || {
+        let mut plural_offset = "";
+        if all_quotes.len() > 1 {
+            plural_offset = "s";
+        }
+
+        println!("Get {} quote{}\n", all_quotes.len(), plural_offset);
+
+        all_quotes.iter().for_each(|quote| {
+            if quote.is_expired(today) {
+                println!("{} (expired)", quote)
+            } else {
+                println!("{}", quote)
+            }
+        });
+    })
+}
@@ -0,0 +1,67 @@
+use std::error::Error;
+
+use chrono::{Local, NaiveDate};
+use log::trace;
+use serde::Serialize;
+
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::entities::amount::Amount;
+use crate::entities::recurring_invoice::Cadence;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+#[derive(Serialize)]
+struct RecurringRow {
+    id: String,
+    name: String,
+    customer_id: String,
+    title: String,
+    cadence: Cadence,
+    start_date: String,
+    end_date: Option<String>,
+    last_generated: Option<String>,
+    pending_invoices: usize,
+    total_ht: Amount,
+}
+
+pub fn list_recurring(context_parameters: ContextParameters, output_format: OutputFormat) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Get recurring invoices");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let mut all_recurring_invoices: Vec<_> = file_manager.get_all_recurring_invoices()?.into_iter().collect();
+    all_recurring_invoices.sort_by(|(recurring_ref, _), (other_recurring_ref, _)| recurring_ref.cmp(other_recurring_ref));
+
+    let today = Local::now().date_naive();
+
+    let rows: Vec<RecurringRow> = all_recurring_invoices
+        .iter()
+        .map(|(recurring_ref, recurring_invoice)| RecurringRow {
+            id: recurring_ref.to_owned(),
+            name: recurring_invoice.name.to_owned(),
+            customer_id: recurring_invoice.customer_id.to_owned(),
+            title: recurring_invoice.title.to_owned(),
+            cadence: recurring_invoice.cadence,
+            start_date: recurring_invoice.start_date.to_string(),
+            end_date: recurring_invoice.end_date.as_ref().map(NaiveDate::to_string),
+            last_generated: recurring_invoice.last_generated.as_ref().map(NaiveDate::to_string),
+            pending_invoices: recurring_invoice.get_pending_dates(today).len(),
+            total_ht: recurring_invoice.products.iter().map(|product| product.get_total_price()).sum(),
+        })
+        .collect();
+
+    print_output(output_format, &rows, &rows, || {
+        let mut plural_offset = "";
+        if all_recurring_invoices.len() > 1 {
+            plural_offset = "s";
+        }
+
+        println!("Get {} recurring invoice{}\n", all_recurring_invoices.len(), plural_offset);
+
+        all_recurring_invoices
+            .iter()
+            .for_each(|(recurring_ref, recurring_invoice)| println!("{} : {}", recurring_ref, recurring_invoice));
+    })
+}
@@ -1,11 +1,18 @@
 pub mod cli_error;
 pub mod create_customer;
+pub mod customer_input;
 pub mod create_invoice;
+pub mod invoice_query_input;
 pub mod get_customer;
+pub mod customer_stats;
+pub mod customer_statement;
 pub mod init;
 pub mod get_invoice;
 pub mod delete_invoice;
+pub mod credit_invoice;
+pub mod pay_invoice;
 mod utils;
+pub mod output;
 pub mod list_invoices;
 pub mod list_customers;
 pub mod delete_customer;
@@ -13,7 +20,35 @@ pub mod edit_customer;
 pub mod month_stats;
 pub mod year_stats;
 pub mod day_stats;
+pub mod overdue_stats;
+pub mod range_stats;
+pub mod quarter_stats;
+pub mod thresholds_stats;
+pub mod declare;
+pub mod export_fec;
+pub mod export_csv;
+pub mod export_factur_x;
+pub mod export_ubl;
+pub mod import_csv;
+pub mod import_e_invoice;
 pub mod get_settings;
 pub mod edit_settings;
+pub mod settings_input;
 pub mod generate_invoice;
 pub mod generate_all_invoice;
+pub mod create_quote;
+pub mod list_quotes;
+pub mod get_quote;
+pub mod accept_quote;
+pub mod refuse_quote;
+pub mod deposit_quote;
+pub mod bill_quote;
+pub mod create_recurring;
+pub mod list_recurring;
+pub mod delete_recurring;
+pub mod run_recurring;
+pub mod audit_numbering;
+pub mod verify_invoices;
+pub mod edit_draft;
+pub mod finalize_draft;
+pub mod list_drafts;
@@ -3,36 +3,51 @@ use std::error::Error;
 use chrono::Datelike;
 use log::trace;
 
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::period_stats::PeriodStats;
+use crate::cli::output::print_output::print_output;
+use crate::cli::utils::get_period_range::get_month_range;
+use crate::cli::utils::print_totals::print_totals;
 use crate::entities::invoice_date::MonthString;
+use crate::entities::invoice_totals::InvoiceTotals;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn month_stats(context_parameters: ContextParameters, month: &Option<u32>, year: &Option<i32>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+pub fn month_stats(context_parameters: ContextParameters, output_format: OutputFormat, month: &Option<u32>, year: &Option<i32>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Get month stats");
 
     let year = year.unwrap_or(chrono::Local::now().year());
     let month = month.unwrap_or(chrono::Local::now().month());
 
+    get_month_range(month, year)?;
+
     let file_manager = FileManager::new(context_parameters)?;
 
     let all_month_invoices = file_manager.get_invoice_by_month(year, month)?;
 
-    let total_cost = all_month_invoices.iter()
-        .fold(0.0, |total, invoice| total + invoice.get_total_price());
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+    let totals: InvoiceTotals = all_month_invoices
+        .iter()
+        .map(|invoice| invoice.get_totals(&default_vat_rate))
+        .sum();
 
-    let mut plural_offset = "";
-    if all_month_invoices.len() > 1 {
-        plural_offset = "s";
-    }
+    let period_stats = PeriodStats::new(format!("{}-{}", year, MonthString::new(&month.to_string()).unwrap()), &all_month_invoices, totals.clone(), &default_vat_rate);
 
-    println!("Get {} invoice{} for {}/{}\n", all_month_invoices.len(), plural_offset, MonthString::new(&month.to_string()).unwrap().to_string(), year);
+    print_output(output_format, &period_stats, &period_stats.invoices, || {
+        let mut plural_offset = "";
+        if all_month_invoices.len() > 1 {
+            plural_offset = "s";
+        }
 
-    all_month_invoices
-        .iter()
-        .for_each(|invoice| println!("{}", invoice));
+        println!("Get {} invoice{} for {}/{}\n", all_month_invoices.len(), plural_offset, MonthString::new(&month.to_string()).unwrap(), year);
 
-    println!("Total : {} €", total_cost);
+        all_month_invoices
+            .iter()
+            .for_each(|invoice| println!("{}", invoice));
 
-    Ok(())
+        println!();
+        print_totals(&totals);
+    })
 }
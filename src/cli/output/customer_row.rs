@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+use crate::entities::customer::Customer;
+
+/// Customer with the id used by the other commands
+#[derive(Serialize)]
+pub(crate) struct CustomerRow<'a> {
+    id: &'a str,
+    #[serde(flatten)]
+    customer: &'a Customer,
+}
+
+impl<'a> CustomerRow<'a> {
+    pub(crate) fn new(id: &'a str, customer: &'a Customer) -> Self {
+        CustomerRow { id, customer }
+    }
+}
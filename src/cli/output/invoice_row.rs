@@ -0,0 +1,50 @@
+use serde::Serialize;
+
+use crate::entities::amount::Amount;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice::Invoice;
+use crate::entities::payment_status::PaymentStatus;
+use crate::entities::vat_rate::VatRate;
+
+/// Invoice printed by the list and stats commands, with its computed totals
+#[derive(Serialize)]
+pub(crate) struct InvoiceRow {
+    reference: String,
+    kind: DocumentKind,
+    date: String,
+    due_date: String,
+    customer_id: String,
+    title: String,
+    total_ht: Amount,
+    total_vat: Amount,
+    total_ttc: Amount,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<PaymentStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remaining: Option<Amount>,
+}
+
+impl InvoiceRow {
+    /// `reference` is given to also print drafts, which have no reference yet
+    pub(crate) fn new(reference: String, invoice: &Invoice, default_vat_rate: &VatRate) -> Self {
+        let totals = invoice.get_totals(default_vat_rate);
+
+        InvoiceRow {
+            reference,
+            kind: invoice.kind,
+            date: invoice.date.to_string(),
+            due_date: invoice.get_due_date().to_string(),
+            customer_id: invoice.customer_id.to_owned(),
+            title: invoice.title.to_owned(),
+            total_ht: totals.total_ht,
+            total_vat: totals.total_vat,
+            total_ttc: totals.total_ttc,
+            status: None,
+            remaining: None,
+        }
+    }
+
+    pub(crate) fn with_payment_status(self, status: PaymentStatus, remaining: Amount) -> Self {
+        InvoiceRow { status: Some(status), remaining: Some(remaining), ..self }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod output_format;
+pub(super) mod print_output;
+pub(super) mod invoice_row;
+pub(super) mod product_row;
+pub(super) mod quote_row;
+pub(super) mod period_stats;
+pub(super) mod customer_row;
@@ -0,0 +1,11 @@
+use clap::ValueEnum;
+
+/// Format printed by the read commands, `table` is the text for humans
+#[derive(ValueEnum, Clone, Copy, PartialEq, Debug, Default)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+}
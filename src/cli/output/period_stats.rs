@@ -0,0 +1,45 @@
+use serde::Serialize;
+
+use crate::cli::output::invoice_row::InvoiceRow;
+use crate::entities::invoice::Invoice;
+use crate::entities::invoice_totals::InvoiceTotals;
+use crate::entities::vat_rate::VatRate;
+
+/// Invoices of a day, a month or a year and the sum of their totals
+#[derive(Serialize)]
+pub(crate) struct PeriodStats {
+    period: String,
+    pub(crate) invoices: Vec<InvoiceRow>,
+    totals: InvoiceTotals,
+}
+
+impl PeriodStats {
+    pub(crate) fn new(period: String, invoices: &[Invoice], totals: InvoiceTotals, default_vat_rate: &VatRate) -> Self {
+        PeriodStats {
+            period,
+            invoices: invoices
+                .iter()
+                .map(|invoice| {
+                    InvoiceRow::new(invoice.get_ref().unwrap_or_default(), invoice, default_vat_rate)
+                })
+                .collect(),
+            totals,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn period_stats_of_empty_period() {
+        let totals: InvoiceTotals = Vec::<Invoice>::new().iter().map(|invoice| invoice.get_totals(&VatRate::Normal)).sum();
+        let period_stats = PeriodStats::new("2024-02".to_string(), &[], totals, &VatRate::Normal);
+
+        assert_eq!(
+            serde_json::to_string(&period_stats).unwrap(),
+            r#"{"period":"2024-02","invoices":[],"totals":{"total_ht":0.0,"vat_lines":[],"total_vat":0.0,"total_ttc":0.0}}"#
+        );
+    }
+}
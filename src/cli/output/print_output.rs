@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::cli::output::output_format::OutputFormat;
+
+/// Print `document` as JSON or YAML, `rows` as CSV, or call `print_table` for the text output
+pub(crate) fn print_output<T: Serialize, R: Serialize>(
+    output_format: OutputFormat,
+    document: &T,
+    rows: &[R],
+    print_table: impl FnOnce(),
+) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    match output_format {
+        OutputFormat::Table => print_table(),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(document)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(document)?),
+        OutputFormat::Csv => print!("{}", to_csv(rows)?),
+    }
+
+    Ok(())
+}
+
+/// One line by row, nested values get a column named by their path like `payment_terms.days`
+fn to_csv<R: Serialize>(rows: &[R]) -> Result<String, Box<dyn Error + Sync + Send + 'static>> {
+    let mut headers: Vec<String> = vec![];
+    let mut records: Vec<HashMap<String, String>> = vec![];
+
+    for row in rows {
+        let mut record = HashMap::new();
+        flatten_value(String::new(), serde_json::to_value(row)?, &mut headers, &mut record);
+        records.push(record);
+    }
+
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    if !headers.is_empty() {
+        writer.write_record(&headers)?;
+    }
+
+    for record in records {
+        writer.write_record(
+            headers.iter().map(|header| record.get(header).map(String::as_str).unwrap_or_default()),
+        )?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+fn flatten_value(path: String, value: Value, headers: &mut Vec<String>, record: &mut HashMap<String, String>) {
+    let child_path = |key: &str| match path.is_empty() {
+        true => key.to_string(),
+        false => format!("{}.{}", path, key),
+    };
+
+    let cell = match value {
+        Value::Object(map) => {
+            map.into_iter()
+                .for_each(|(key, value)| flatten_value(child_path(&key), value, headers, record));
+            return;
+        }
+        Value::Array(values) => {
+            values.into_iter().enumerate().for_each(|(index, value)| {
+                flatten_value(child_path(&index.to_string()), value, headers, record)
+            });
+            return;
+        }
+        Value::Null => String::new(),
+        Value::String(text) => text,
+        other => other.to_string(),
+    };
+
+    // Rows which are not structs, like a list of references
+    let path = if path.is_empty() { "value".to_string() } else { path };
+
+    if !headers.contains(&path) {
+        headers.push(path.clone());
+    }
+    record.insert(path, cell);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: String,
+        total: f64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        terms: Option<HashMap<String, u32>>,
+    }
+
+    #[test]
+    fn csv_with_nested_and_missing_values() {
+        let rows = vec![
+            Row { name: "King, Inc".to_string(), total: 350.5, terms: None },
+            Row {
+                name: "Queen".to_string(),
+                total: 10.0,
+                terms: Some(HashMap::from([("days".to_string(), 45)])),
+            },
+        ];
+
+        assert_eq!(
+            to_csv(&rows).unwrap(),
+            "name,total,terms.days\n\"King, Inc\",350.5,\nQueen,10.0,45\n"
+        );
+        assert_eq!(to_csv(&["a", "b"]).unwrap(), "value\na\nb\n");
+        assert_eq!(to_csv::<Row>(&[]).unwrap(), "");
+    }
+}
@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+use crate::entities::amount::Amount;
+use crate::entities::product::Product;
+use crate::entities::quantity::Quantity;
+use crate::entities::vat_rate::VatRate;
+
+/// Product line of a invoice or a quote, with its resolved VAT rate and total
+#[derive(Serialize)]
+pub(crate) struct ProductRow {
+    description: String,
+    quantity: Quantity,
+    price: Amount,
+    vat_rate: VatRate,
+    total: Amount,
+}
+
+impl ProductRow {
+    pub(crate) fn new(product: &Product, default_vat_rate: &VatRate) -> Self {
+        ProductRow {
+            description: product.description.to_owned(),
+            quantity: product.quantity,
+            price: product.price,
+            vat_rate: product.get_vat_rate(default_vat_rate).clone(),
+            total: product.get_total_price(),
+        }
+    }
+}
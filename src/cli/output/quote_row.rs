@@ -0,0 +1,43 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::entities::amount::Amount;
+use crate::entities::quote::{Quote, QuoteStatus};
+use crate::entities::vat_rate::VatRate;
+
+/// Quote printed by the list command, with its computed totals
+#[derive(Serialize)]
+pub(crate) struct QuoteRow {
+    reference: String,
+    status: QuoteStatus,
+    expired: bool,
+    date: String,
+    validity_date: String,
+    customer_id: String,
+    title: String,
+    total_ht: Amount,
+    total_vat: Amount,
+    total_ttc: Amount,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    invoice_ref: Option<String>,
+}
+
+impl QuoteRow {
+    pub(crate) fn new(quote: &Quote, default_vat_rate: &VatRate, today: NaiveDate) -> Self {
+        let totals = quote.get_totals(default_vat_rate);
+
+        QuoteRow {
+            reference: quote.get_ref().unwrap_or_default(),
+            status: quote.status,
+            expired: quote.is_expired(today),
+            date: quote.date.to_string(),
+            validity_date: quote.validity_date.to_string(),
+            customer_id: quote.customer_id.to_owned(),
+            title: quote.title.to_owned(),
+            total_ht: totals.total_ht,
+            total_vat: totals.total_vat,
+            total_ttc: totals.total_ttc,
+            invoice_ref: quote.invoice_ref.clone(),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::error::Error;
+
+use chrono::Local;
+use log::trace;
+use serde::Serialize;
+
+use crate::cli::output::invoice_row::InvoiceRow;
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::entities::amount::Amount;
+use crate::entities::payment_status::PaymentStatus;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+#[derive(Serialize)]
+struct OverdueStats {
+    date: String,
+    invoices: Vec<InvoiceRow>,
+    total_remaining: Amount,
+}
+
+pub fn overdue_stats(context_parameters: ContextParameters, output_format: OutputFormat) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Get overdue stats");
+
+    let today = Local::now().date_naive();
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+    let all_payments = file_manager.get_all_payments()?;
+
+    let all_invoices = file_manager.get_all_invoices()?;
+
+    let overdue_invoices: Vec<_> = all_invoices
+        .iter()
+        .filter_map(|invoice| {
+            let payments = all_payments
+                .get(&invoice.get_ref().unwrap())
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let total_due = invoice.get_amount_due(&all_invoices, &default_vat_rate);
+
+            match invoice.get_payment_status(total_due, payments, today) {
+                PaymentStatus::Overdue => {
+                    let paid: Amount = payments.iter().map(|payment| payment.amount).sum();
+                    Some((invoice, total_due - paid))
+                }
+                _ => None,
+            }
+        })
+        .collect();
+
+    let total_remaining: Amount = overdue_invoices.iter().map(|(_, remaining)| *remaining).sum();
+
+    let overdue_stats = OverdueStats {
+        date: today.to_string(),
+        invoices: overdue_invoices
+            .iter()
+            .map(|(invoice, remaining)| {
+                InvoiceRow::new(invoice.get_ref().unwrap(), invoice, &default_vat_rate)
+                    .with_payment_status(PaymentStatus::Overdue, *remaining)
+            })
+            .collect(),
+        total_remaining,
+    };
+
+    print_output(output_format, &overdue_stats, &overdue_stats.invoices, || {
+        let mut plural_offset = "";
+        if overdue_invoices.len() > 1 {
+            plural_offset = "s";
+        }
+
+        println!("Get {} overdue invoice{} on {}\n", overdue_invoices.len(), plural_offset, today);
+
+        overdue_invoices.iter().for_each(|(invoice, remaining)| {
+            println!(
+                "{} - due {} ({} days late) - {} € remaining",
+                invoice,
+                invoice.get_due_date(),
+                (today - invoice.get_due_date()).num_days(),
+                remaining
+            )
+        });
+
+        println!();
+        println!("Total overdue : {} €", total_remaining);
+    })
+}
@@ -0,0 +1,109 @@
+use std::error::Error;
+
+use chrono::{Local, NaiveDate};
+use dialoguer::{Input, Select};
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::select_invoice_or_use_default::select_invoice_or_use_default;
+use crate::entities::amount::Amount;
+use crate::entities::payment::{Payment, PaymentMethod};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub fn pay_invoice(
+    context_parameters: ContextParameters,
+    invoice_ref: &Option<String>,
+    amount: &Option<Amount>,
+    date: &Option<NaiveDate>,
+    method: &Option<PaymentMethod>,
+    reference: &Option<String>,
+) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Pay invoice");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let invoice_selected = select_invoice_or_use_default(&file_manager, invoice_ref)?;
+    let invoice_ref = invoice_selected.get_ref().unwrap();
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+    let total_due = invoice_selected.get_amount_due(&file_manager.get_all_invoices()?, &default_vat_rate);
+    let already_paid: Amount = file_manager
+        .get_invoice_payments(&invoice_ref)?
+        .iter()
+        .map(|payment| payment.amount)
+        .sum();
+    let remaining = total_due - already_paid;
+
+    if remaining <= Amount::ZERO {
+        return Err(Box::new(CliError::InvalidPayment(format!(
+            "Invoice {} is already paid",
+            invoice_ref
+        ))));
+    }
+
+    let amount = match amount {
+        Some(amount) => *amount,
+        None => {
+            let amount: String = Input::new()
+                .with_prompt("Payment amount")
+                .with_initial_text(remaining.to_string())
+                .validate_with(|input: &String| -> Result<(), String> {
+                    Amount::new(input)?;
+                    Ok(())
+                })
+                .interact_text()?;
+
+            Amount::new(&amount)?
+        }
+    };
+
+    if amount <= Amount::ZERO {
+        return Err(Box::new(CliError::InvalidPayment(
+            "The payment amount must be positive".to_string(),
+        )));
+    }
+
+    if amount > remaining {
+        return Err(Box::new(CliError::InvalidPayment(format!(
+            "The payment amount {} € is greater than the remaining {} €",
+            amount, remaining
+        ))));
+    }
+
+    let method = match method {
+        Some(method) => method.clone(),
+        None => {
+            let methods = PaymentMethod::all_methods();
+            let method_index = Select::new()
+                .with_prompt("Payment method")
+                .items(&methods)
+                .default(0)
+                .interact()?;
+
+            methods[method_index].clone()
+        }
+    };
+
+    let payment = Payment {
+        date: date.unwrap_or_else(|| Local::now().date_naive()),
+        amount,
+        method,
+        reference: reference.to_owned(),
+    };
+
+    let payment_description = payment.to_string();
+
+    let payments = file_manager.add_payment(&invoice_ref, payment)?;
+
+    println!("Payment saved : {}", payment_description);
+
+    println!(
+        "Invoice {} is {}",
+        invoice_ref,
+        invoice_selected.get_payment_status(total_due, &payments, Local::now().date_naive())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use std::error::Error;
+
+use chrono::Datelike;
+use log::trace;
+
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::range_stats::range_stats;
+use crate::cli::utils::get_period_range::get_quarter_range;
+use crate::entities::range_stats::Breakdown;
+use crate::file_manager::context_parameters::ContextParameters;
+
+pub fn quarter_stats(context_parameters: ContextParameters, output_format: OutputFormat, quarter: &Option<u32>, year: &Option<i32>, breakdown: &Breakdown) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Get quarter stats");
+
+    let today = chrono::Local::now().date_naive();
+
+    let year = year.unwrap_or(today.year());
+    let quarter = quarter.unwrap_or((today.month() - 1) / 3 + 1);
+
+    let (from, to) = get_quarter_range(quarter, year)?;
+
+    range_stats(context_parameters, output_format, &from, &to, breakdown)
+}
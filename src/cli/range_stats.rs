@@ -0,0 +1,80 @@
+use std::error::Error;
+
+use chrono::NaiveDate;
+use log::trace;
+use serde::Serialize;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::cli::utils::print_totals::print_totals;
+use crate::entities::range_stats::{get_previous_year_date, Breakdown, RangeStats};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+#[derive(Serialize)]
+struct RangeStatsOutput<'a> {
+    from: String,
+    to: String,
+    #[serde(flatten)]
+    stats: &'a RangeStats,
+}
+
+fn format_evolution(evolution: Option<f64>) -> String {
+    match evolution {
+        Some(evolution) => format!("{:+.1} %", evolution),
+        None => "-".to_string(),
+    }
+}
+
+pub fn range_stats(context_parameters: ContextParameters, output_format: OutputFormat, from: &NaiveDate, to: &NaiveDate, breakdown: &Breakdown) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Get range stats");
+
+    if from > to {
+        return Err(Box::new(CliError::InvalidPeriod(from.to_string(), to.to_string())));
+    }
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+    // One scan for the range and the same range last year
+    let all_invoices = file_manager.get_invoice_by_range(get_previous_year_date(*from), *to)?;
+
+    let stats = RangeStats::new(*from, *to, *breakdown, &all_invoices, &default_vat_rate);
+
+    let output = RangeStatsOutput { from: from.to_string(), to: to.to_string(), stats: &stats };
+
+    print_output(output_format, &output, &stats.breakdown, || {
+        let mut plural_offset = "";
+        if stats.invoices > 1 {
+            plural_offset = "s";
+        }
+
+        println!("Get {} invoice{} from {} to {}\n", stats.invoices, plural_offset, from, to);
+
+        println!("{:<20} {:>8} {:>12} {:>12} {:>10}", breakdown.to_string(), "invoices", "total HT", "last year", "evolution");
+        stats.breakdown.iter().for_each(|line| {
+            println!(
+                "{:<20} {:>8} {:>12} {:>12} {:>10}",
+                line.key,
+                line.invoices,
+                line.total_ht.to_string(),
+                line.previous_total_ht.to_string(),
+                format_evolution(line.evolution)
+            )
+        });
+
+        println!();
+        print_totals(&stats.totals);
+
+        println!();
+        println!(
+            "Last year : {} invoices - {} € HT ({})",
+            stats.previous_invoices,
+            stats.previous_totals.total_ht,
+            format_evolution(stats.evolution)
+        );
+    })
+}
@@ -0,0 +1,35 @@
+use std::error::Error;
+
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::select_quote_or_use_default::select_quote_or_use_default;
+use crate::entities::quote::{Quote, QuoteStatus};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub fn refuse_quote(context_parameters: ContextParameters, quote_ref: &Option<String>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Refuse quote");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let quote_selected: Quote = select_quote_or_use_default(&file_manager, quote_ref)?;
+    let quote_ref = quote_selected.get_ref().unwrap();
+
+    if quote_selected.status != QuoteStatus::Pending {
+        return Err(Box::new(CliError::InvalidQuote(format!(
+            "Quote {} is already {}",
+            quote_ref, quote_selected.status
+        ))));
+    }
+
+    file_manager.save_quote(Quote {
+        status: QuoteStatus::Refused,
+        ..quote_selected
+    })?;
+
+    println!("Quote {} refused", quote_ref);
+
+    Ok(())
+}
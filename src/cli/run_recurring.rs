@@ -0,0 +1,50 @@
+use std::error::Error;
+
+use chrono::Local;
+use log::trace;
+
+use crate::cli::utils::get_customer_payment_terms::get_customer_payment_terms;
+use crate::entities::recurring_invoice::RecurringInvoice;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Create every invoice due until today. The invoices are dated today, even for a
+/// missed period, so their numbers stay in the order of their dates. The last period
+/// billed is saved after each invoice, so running it again never bills a period twice.
+pub fn run_recurring(context_parameters: ContextParameters) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Run recurring invoices");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let today = Local::now().date_naive();
+    let mut invoice_count = 0;
+
+    for (_, recurring_invoice) in file_manager.get_all_recurring_invoices()? {
+        let payment_terms = get_customer_payment_terms(&file_manager, &recurring_invoice.customer_id)?;
+
+        for period in recurring_invoice.get_pending_dates(today) {
+            let invoice = recurring_invoice.create_invoice(period, today, payment_terms.get_due_date(today));
+
+            let invoice_path = file_manager.create_invoice(invoice)?;
+
+            file_manager.save_recurring_invoice(RecurringInvoice {
+                last_generated: Some(period),
+                ..recurring_invoice.clone()
+            })?;
+
+            println!("Invoice of {} created in : {}", recurring_invoice.name, invoice_path.to_string_lossy());
+
+            let mut invoice_path_output = invoice_path.clone();
+            invoice_path_output.set_extension("pdf");
+
+            file_manager.generate_invoice(invoice_path.as_path(), invoice_path_output.file_name().unwrap().to_str().unwrap())?;
+
+            invoice_count += 1;
+        }
+    }
+
+    println!("{} recurring invoice(s) created", invoice_count);
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Deserialize;
+
+use crate::cli::utils::read_input_file::read_input_file;
+use crate::entities::accounting_settings::AccountingSettings;
+use crate::entities::activity::{Activity, ContributionRates};
+use crate::entities::factur_x::FacturXProfile;
+use crate::entities::numbering_policy::NumberingPolicy;
+use crate::entities::payment_terms::PaymentTerms;
+use crate::entities::revenue_thresholds::RevenueThresholds;
+use crate::entities::vat_rate::VatRate;
+
+#[derive(Args, Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EnterpriseInput {
+    /// Enterprise name
+    #[arg(long)]
+    pub name: Option<String>,
+    /// Job title
+    #[arg(long)]
+    pub title: Option<String>,
+    /// 9 digits
+    #[arg(long)]
+    pub siren: Option<String>,
+    #[arg(long)]
+    pub email: Option<String>,
+    #[arg(long)]
+    pub address: Option<String>,
+    #[arg(long)]
+    pub city: Option<String>,
+    /// Postal code
+    #[arg(long)]
+    pub postal: Option<String>,
+    /// Phone number
+    #[arg(long)]
+    pub phone: Option<String>,
+    /// TVA number, empty under "franchise en base"
+    #[arg(long)]
+    pub tva: Option<String>,
+    /// IBAN of the credit transfers in the electronic invoices
+    #[arg(long)]
+    pub iban: Option<String>,
+}
+
+/// Settings given by flags or by a file in the format of the settings file.
+/// Missing values are asked when a terminal is available, unknown keys of the file are errors.
+#[derive(Args, Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SettingsInput {
+    #[command(flatten)]
+    #[serde(default)]
+    pub enterprise: EnterpriseInput,
+    #[arg(long)]
+    pub politeness: Option<String>,
+    /// Legal mentions printed on the invoices
+    #[arg(long)]
+    pub law_rules: Option<String>,
+    /// 20, 10, 5.5, 2.1 or 0, automatic if missing
+    #[arg(long)]
+    pub default_vat_rate: Option<VatRate>,
+    /// on_receipt, net:DAYS or end_of_month:DAYS
+    #[arg(long)]
+    pub payment_terms: Option<PaymentTerms>,
+    /// Only read from the file, its pattern needs `{SEQ}`
+    #[arg(skip)]
+    pub numbering: Option<NumberingPolicy>,
+    /// Only read from the file, missing thresholds keep their default value
+    #[arg(skip)]
+    pub thresholds: Option<RevenueThresholds>,
+    /// sale, service_bic or service_bnc, of the lines without activity
+    #[arg(long)]
+    pub activity: Option<Activity>,
+    /// Only read from the file, missing rates keep their default value
+    #[arg(skip)]
+    pub contribution_rates: Option<ContributionRates>,
+    /// Only read from the file, missing accounts keep their default value
+    #[arg(skip)]
+    pub accounting: Option<AccountingSettings>,
+    /// minimum, basic or en16931, embeds a Factur-X XML in the invoice PDFs
+    #[arg(long)]
+    pub factur_x: Option<FacturXProfile>,
+    /// Read the settings from a YAML or JSON file, the other flags override its values
+    #[arg(long, value_name = "FILE")]
+    #[serde(skip)]
+    pub from_file: Option<PathBuf>,
+}
+
+impl SettingsInput {
+    /// Values of the flags, completed by the ones of `--from-file`
+    pub fn with_file_values(&self) -> Result<SettingsInput, Box<dyn Error + Sync + Send + 'static>> {
+        let file_input: SettingsInput = match &self.from_file {
+            Some(path) => read_input_file(path)?,
+            None => return Ok(self.clone()),
+        };
+
+        let enterprise = &self.enterprise;
+        let file_enterprise = file_input.enterprise;
+
+        Ok(SettingsInput {
+            enterprise: EnterpriseInput {
+                name: enterprise.name.clone().or(file_enterprise.name),
+                title: enterprise.title.clone().or(file_enterprise.title),
+                siren: enterprise.siren.clone().or(file_enterprise.siren),
+                email: enterprise.email.clone().or(file_enterprise.email),
+                address: enterprise.address.clone().or(file_enterprise.address),
+                city: enterprise.city.clone().or(file_enterprise.city),
+                postal: enterprise.postal.clone().or(file_enterprise.postal),
+                phone: enterprise.phone.clone().or(file_enterprise.phone),
+                tva: enterprise.tva.clone().or(file_enterprise.tva),
+                iban: enterprise.iban.clone().or(file_enterprise.iban),
+            },
+            politeness: self.politeness.clone().or(file_input.politeness),
+            law_rules: self.law_rules.clone().or(file_input.law_rules),
+            default_vat_rate: self.default_vat_rate.clone().or(file_input.default_vat_rate),
+            payment_terms: self.payment_terms.clone().or(file_input.payment_terms),
+            numbering: file_input.numbering,
+            thresholds: file_input.thresholds,
+            activity: self.activity.or(file_input.activity),
+            contribution_rates: file_input.contribution_rates,
+            accounting: file_input.accounting,
+            factur_x: self.factur_x.or(file_input.factur_x),
+            from_file: self.from_file.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::entities::settings::Settings;
+
+    use super::*;
+
+    #[test]
+    fn settings_input_with_file_values() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let folder = temp_dir_assert_fs.path();
+
+        // A settings file of an invoice folder can set up another one
+        let settings_path = folder.join("settings.yaml");
+        fs::write(&settings_path, serde_yaml::to_string(&Settings::generate_simple_settings()).unwrap()).unwrap();
+
+        let settings_input = SettingsInput {
+            politeness: Some("Regards".to_string()),
+            from_file: Some(settings_path),
+            ..SettingsInput::default()
+        }
+        .with_file_values()
+        .unwrap();
+        assert_eq!(settings_input.politeness, Some("Regards".to_string()));
+        assert_eq!(settings_input.law_rules, Some("Example Law".to_string()));
+        assert_eq!(settings_input.enterprise.siren, Some("123456789".to_string()));
+        assert_eq!(settings_input.enterprise.iban, None);
+
+        let empty_path = folder.join("empty.json");
+        fs::write(&empty_path, "{}").unwrap();
+        let settings_input = SettingsInput { from_file: Some(empty_path), ..SettingsInput::default() };
+        assert!(settings_input.with_file_values().unwrap().enterprise.name.is_none());
+
+        for (file_name, content) in [
+            ("typo.yaml", "enterprise:\n  adress: 1 rue de la Paix\n"),
+            ("unknown.yaml", "currency: EUR\n"),
+            ("invalid_terms.yaml", "payment_terms:\n  terms: soon\n"),
+            ("invalid_vat.json", r#"{"default_vat_rate": "19"}"#),
+        ] {
+            let path = folder.join(file_name);
+            fs::write(&path, content).unwrap();
+            let settings_input = SettingsInput { from_file: Some(path), ..SettingsInput::default() };
+            assert!(settings_input.with_file_values().is_err(), "{} accepted", file_name);
+        }
+
+        let settings_input = SettingsInput { from_file: Some(folder.join("missing.yaml")), ..SettingsInput::default() };
+        assert!(settings_input.with_file_values().is_err());
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+}
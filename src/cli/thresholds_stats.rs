@@ -0,0 +1,49 @@
+use std::error::Error;
+
+use chrono::{Datelike, Local, NaiveDate};
+use log::trace;
+
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::print_output::print_output;
+use crate::entities::revenue_thresholds::{ThresholdLevel, ThresholdReport};
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub fn thresholds_stats(context_parameters: ContextParameters, output_format: OutputFormat, year: &Option<i32>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Get thresholds stats");
+
+    let today = Local::now().date_naive();
+    let year = year.unwrap_or(today.year());
+
+    let end_of_year = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
+    let date = if year == today.year() { today } else { end_of_year };
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    let thresholds = file_manager.get_settings()?.get_thresholds();
+    let all_year_invoices = file_manager.get_invoice_by_year(year)?;
+
+    let report = ThresholdReport::new(&thresholds, &all_year_invoices, date);
+
+    print_output(output_format, &report, &report.thresholds, || {
+        println!("Revenue {} : {} € HT", year, report.revenue);
+        if date != end_of_year {
+            println!("Projected at the end of the year : {} € HT", report.projected_revenue);
+        }
+
+        println!();
+        report.thresholds.iter().for_each(|threshold| {
+            let level = match threshold.level {
+                ThresholdLevel::Under => "",
+                ThresholdLevel::Close => " - close",
+                ThresholdLevel::Exceeded => " - EXCEEDED",
+            };
+
+            println!(
+                "{} : {} € - {} % reached, {} € remaining{}",
+                threshold.name, threshold.limit, threshold.usage, threshold.remaining, level
+            )
+        });
+    })
+}
@@ -0,0 +1,47 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use chrono::Local;
+
+use crate::cli::cli_error::CliError;
+use crate::cli::utils::get_customer_payment_terms::get_customer_payment_terms;
+use crate::entities::quote::Quote;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Create the deposit invoice of `percent` % of the quote. Its PDF is generated
+/// by the caller once the quote is saved, a failed generation then bills nothing twice.
+pub(crate) fn bill_deposit(file_manager: &FileManager, quote: &Quote, percent: u32) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>> {
+    let quote_ref = quote.get_ref().unwrap();
+
+    if !(1..=100).contains(&percent) {
+        return Err(Box::new(CliError::InvalidQuote(format!(
+            "The deposit must be between 1 and 100 %, not {} %",
+            percent
+        ))));
+    }
+
+    let today = Local::now().date_naive();
+    let payment_terms = get_customer_payment_terms(file_manager, &quote.customer_id)?;
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+    let deposit = quote.create_deposit(today, payment_terms.get_due_date(today), percent, &default_vat_rate);
+
+    let all_invoices = file_manager.get_all_invoices()?;
+    let remaining = quote.get_remaining_for_deposits(&all_invoices);
+
+    if deposit.get_total_price() > remaining {
+        return Err(Box::new(CliError::InvalidQuote(format!(
+            "Deposits of quote {} would exceed its total : {} € already billed on {} €",
+            quote_ref,
+            quote.get_total_price() - remaining,
+            quote.get_total_price()
+        ))));
+    }
+
+    let deposit_path = file_manager.create_invoice(deposit)?;
+
+    println!("Deposit invoice created in : {}", deposit_path.to_string_lossy());
+
+    Ok(deposit_path)
+}
@@ -4,4 +4,16 @@ use thiserror::Error;
 pub enum CliUtilsError {
     #[error("No invoice already created found")]
     NoInvoiceFound(),
-}
\ No newline at end of file
+
+    #[error("No quote already created found")]
+    NoQuoteFound(),
+
+    #[error("No draft saved found")]
+    EmptyDraftFolder(),
+
+    #[error("{0} is missing and can not be asked without a terminal")]
+    NotInteractive(String),
+
+    #[error("Invalid input file {0}: {1}")]
+    InvalidInputFile(String, String),
+}
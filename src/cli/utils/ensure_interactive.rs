@@ -0,0 +1,13 @@
+use std::error::Error;
+
+use crate::cli::utils::cli_utils_error::CliUtilsError;
+use crate::cli::utils::is_interactive::is_interactive;
+
+/// Fail instead of prompting for `missing` when the command is run from a script or a pipe
+pub(crate) fn ensure_interactive(missing: &str) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    if is_interactive() {
+        Ok(())
+    } else {
+        Err(Box::new(CliUtilsError::NotInteractive(missing.to_string())))
+    }
+}
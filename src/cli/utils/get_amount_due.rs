@@ -0,0 +1,12 @@
+use crate::entities::amount::Amount;
+use crate::entities::invoice::Invoice;
+
+/// HT amount of the invoice not yet cancelled by a credit note
+pub(crate) fn get_creditable_amount(invoice: &Invoice, all_invoices: &[Invoice]) -> Amount {
+    invoice.get_total_price()
+        + invoice
+            .get_credit_notes(all_invoices)
+            .iter()
+            .map(|credit_note| credit_note.get_total_price())
+            .sum()
+}
@@ -0,0 +1,16 @@
+use std::error::Error;
+
+use crate::cli::cli_error::CliError;
+use crate::entities::payment_terms::PaymentTerms;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Terms of the customer, or the default terms of the settings
+pub(crate) fn get_customer_payment_terms(file_manager: &FileManager, customer_id: &str) -> Result<PaymentTerms, Box<dyn Error + Sync + Send + 'static>> {
+    let default_payment_terms = file_manager.get_settings()?.get_payment_terms();
+
+    match file_manager.get_all_customers()?.get(customer_id) {
+        Some(customer) => Ok(customer.get_payment_terms(&default_payment_terms)),
+        None => Err(Box::new(CliError::CustomerNotFound(customer_id.to_string()))),
+    }
+}
@@ -0,0 +1,79 @@
+use std::error::Error;
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::cli::cli_error::CliError;
+
+/// Years of four digits, like the dates of the references
+fn get_first_day_of_year(year: i32) -> Result<NaiveDate, CliError> {
+    if !(1..=9999).contains(&year) {
+        return Err(CliError::InvalidYear(year));
+    }
+
+    Ok(NaiveDate::from_ymd_opt(year, 1, 1).unwrap())
+}
+
+pub(crate) fn get_day(day: u32, month: u32, year: i32) -> Result<NaiveDate, Box<dyn Error + Sync + Send + 'static>> {
+    let (from, _) = get_month_range(month, year)?;
+
+    Ok(from.with_day(day).ok_or(CliError::InvalidDate(format!("{}/{:02}/{}", day, month, year)))?)
+}
+
+/// First and last days of the month
+pub(crate) fn get_month_range(month: u32, year: i32) -> Result<(NaiveDate, NaiveDate), Box<dyn Error + Sync + Send + 'static>> {
+    let from = get_first_day_of_year(year)?.with_month(month).ok_or(CliError::InvalidMonth(month))?;
+
+    Ok((from, from + Months::new(1) - Days::new(1)))
+}
+
+/// First and last days of the quarter, from 1 to 4
+pub(crate) fn get_quarter_range(quarter: u32, year: i32) -> Result<(NaiveDate, NaiveDate), Box<dyn Error + Sync + Send + 'static>> {
+    if !(1..=4).contains(&quarter) {
+        return Err(Box::new(CliError::InvalidQuarter(quarter)));
+    }
+
+    let from = get_first_day_of_year(year)?.with_month((quarter - 1) * 3 + 1).unwrap();
+
+    Ok((from, from + Months::new(3) - Days::new(1)))
+}
+
+/// First and last days of the year
+pub(crate) fn get_year_range(year: i32) -> Result<(NaiveDate, NaiveDate), Box<dyn Error + Sync + Send + 'static>> {
+    let from = get_first_day_of_year(year)?;
+
+    Ok((from, from + Months::new(12) - Days::new(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn period_ranges() {
+        assert_eq!(get_month_range(2, 2024).unwrap(), (date(2024, 2, 1), date(2024, 2, 29)));
+        assert_eq!(get_month_range(12, 2023).unwrap(), (date(2023, 12, 1), date(2023, 12, 31)));
+        assert_eq!(get_quarter_range(4, 9999).unwrap(), (date(9999, 10, 1), date(9999, 12, 31)));
+        assert_eq!(get_year_range(2024).unwrap(), (date(2024, 1, 1), date(2024, 12, 31)));
+        assert_eq!(get_day(29, 2, 2024).unwrap(), date(2024, 2, 29));
+        assert_eq!(get_day(31, 12, 2024).unwrap(), date(2024, 12, 31));
+    }
+
+    #[test]
+    fn invalid_periods() {
+        assert_eq!(get_month_range(0, 2024).unwrap_err().to_string(), "Invalid month: 0 (1 to 12)");
+        assert!(get_month_range(13, 2024).is_err());
+        assert_eq!(get_quarter_range(5, 2024).unwrap_err().to_string(), "Invalid quarter: 5 (1 to 4)");
+        assert!(get_quarter_range(0, 2024).is_err());
+        assert_eq!(get_year_range(10000).unwrap_err().to_string(), "Invalid year: 10000 (1 to 9999)");
+        assert!(get_year_range(0).is_err());
+        assert!(get_month_range(1, -1).is_err());
+        assert_eq!(get_day(29, 2, 2023).unwrap_err().to_string(), "Invalid date: 29/02/2023");
+        assert!(get_day(0, 1, 2024).is_err());
+        assert!(get_day(32, 1, 2024).is_err());
+        assert!(get_day(1, 13, 2024).is_err());
+    }
+}
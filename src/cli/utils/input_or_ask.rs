@@ -0,0 +1,32 @@
+use std::error::Error;
+
+use dialoguer::Input;
+
+use crate::cli::utils::cli_utils_error::CliUtilsError;
+use crate::cli::utils::is_interactive::is_interactive;
+
+/// Use the value given by flag or file, else ask it starting from `default`.
+/// Without terminal `default` is kept, and a missing value without default is an error.
+pub(crate) fn input_or_ask(
+    prompt: &str,
+    flag: &str,
+    value: Option<String>,
+    default: Option<String>,
+    allow_empty: bool,
+) -> Result<String, Box<dyn Error + Sync + Send + 'static>> {
+    match (value, default) {
+        (Some(value), _) => Ok(value),
+        (None, default) if is_interactive() => {
+            let input = Input::<String>::new().with_prompt(prompt).allow_empty(allow_empty);
+
+            match default {
+                Some(default) => Ok(input.with_initial_text(default).interact_text()?),
+                None => Ok(input.interact_text()?),
+            }
+        }
+        (None, Some(default)) => Ok(default),
+        (None, None) => {
+            Err(Box::new(CliUtilsError::NotInteractive(format!("{} (--{})", prompt, flag))))
+        }
+    }
+}
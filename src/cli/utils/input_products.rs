@@ -0,0 +1,62 @@
+use std::error::Error;
+
+use dialoguer::{Confirm, Input};
+
+use crate::cli::utils::select_vat_rate::select_vat_rate;
+use crate::entities::amount::Amount;
+use crate::entities::product::Product;
+use crate::entities::quantity::Quantity;
+use crate::entities::vat_rate::VatRate;
+
+/// Ask product lines until an empty title or the user stops
+pub(crate) fn input_products(default_vat_rate: &VatRate) -> Result<Vec<Product>, Box<dyn Error + Sync + Send + 'static>> {
+    let mut products = vec![];
+
+    loop {
+        let product_title: String =
+            Input::new().with_prompt("Product title").interact_text().unwrap();
+
+        if product_title.is_empty() {
+            break;
+        }
+
+        let product_quantity = Input::new()
+            .with_prompt("Product quantity")
+            .validate_with(|input: &String| -> Result<(), String> {
+                Quantity::new(input)?;
+                Ok(())
+            })
+            .interact()
+            .unwrap();
+
+        let product_price = Input::new()
+            .with_prompt("Product price")
+            .validate_with(|input: &String| -> Result<(), String> {
+                Amount::new(input)?;
+                Ok(())
+            })
+            .interact()
+            .unwrap();
+
+        let vat = select_vat_rate("Product VAT rate", &format!("Default ({})", default_vat_rate))?;
+
+        products.push(Product {
+            description: product_title,
+            quantity: Quantity::new(&product_quantity)?,
+            price: Amount::new(&product_price)?,
+            vat,
+            activity: None,
+        });
+
+        let confirmation = Confirm::new()
+            .with_prompt("Do you want to add another product ?")
+            .interact()
+            .unwrap();
+
+        if !confirmation {
+            break;
+        }
+    }
+
+    Ok(products)
+}
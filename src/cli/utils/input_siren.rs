@@ -0,0 +1,28 @@
+use std::error::Error;
+
+use dialoguer::Input;
+
+use crate::cli::utils::cli_utils_error::CliUtilsError;
+use crate::cli::utils::is_interactive::is_interactive;
+use crate::entities::siren::Siren;
+
+/// Same as `input_or_ask`, asking again until the SIREN is valid
+pub(crate) fn input_siren(value: Option<String>, default: Option<Siren>) -> Result<Siren, Box<dyn Error + Sync + Send + 'static>> {
+    match (value, default) {
+        (Some(value), _) => Ok(Siren::new(&value)?),
+        (None, default) if is_interactive() => loop {
+            let input = Input::<String>::new().with_prompt("SIREN");
+            let siren_string = match &default {
+                Some(default) => input.with_initial_text(default.to_string()).interact_text()?,
+                None => input.interact_text()?,
+            };
+
+            match Siren::new(&siren_string) {
+                Ok(some_siren) => break Ok(some_siren),
+                Err(error) => println!("{}", error),
+            };
+        },
+        (None, Some(default)) => Ok(default),
+        (None, None) => Err(Box::new(CliUtilsError::NotInteractive("SIREN (--siren)".to_string()))),
+    }
+}
@@ -0,0 +1,6 @@
+use std::io::{stdin, IsTerminal};
+
+/// Prompts are only shown when the command is run from a terminal, not from a script or a pipe
+pub(crate) fn is_interactive() -> bool {
+    stdin().is_terminal()
+}
@@ -2,4 +2,24 @@ pub(super) mod select_invoice;
 pub(super) mod select_customer;
 pub(super) mod select_customer_or_use_default;
 mod cli_utils_error;
-pub(super) mod select_invoice_or_use_default;
\ No newline at end of file
+pub(super) mod select_invoice_or_use_default;
+pub(super) mod select_vat_rate;
+pub(super) mod print_totals;
+pub(super) mod select_payment_terms;
+pub(super) mod get_amount_due;
+pub(super) mod input_products;
+pub(super) mod select_quote;
+pub(super) mod select_quote_or_use_default;
+pub(super) mod get_customer_payment_terms;
+pub(super) mod bill_deposit;
+pub(super) mod select_numbering_policy;
+pub(super) mod select_draft_or_use_default;
+pub(super) mod ensure_interactive;
+pub(super) mod read_input_file;
+pub(super) mod is_interactive;
+pub(super) mod input_or_ask;
+pub(super) mod input_siren;
+pub(super) mod warn_thresholds;
+pub(super) mod get_period_range;
+pub(super) mod read_csv_records;
+pub(super) mod read_e_invoice_xml;
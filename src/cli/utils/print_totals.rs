@@ -0,0 +1,13 @@
+use crate::entities::invoice_totals::InvoiceTotals;
+
+pub(crate) fn print_totals(totals: &InvoiceTotals) {
+    println!("Total HT : {} €", totals.total_ht);
+
+    totals
+        .vat_lines
+        .iter()
+        .filter(|vat_line| vat_line.rate.get_exemption_reason().is_none())
+        .for_each(|vat_line| println!("TVA {} (base {} €) : {} €", vat_line.rate, vat_line.base, vat_line.vat));
+
+    println!("Total TTC : {} €", totals.total_ttc);
+}
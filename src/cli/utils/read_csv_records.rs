@@ -0,0 +1,77 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::cli::utils::cli_utils_error::CliUtilsError;
+use crate::entities::csv_import::{ColumnMapping, CsvRecord};
+
+/// Rows of a CSV file with a header line, separated by `;` when the header has no `,`
+pub(crate) fn read_csv_records(path: &Path, mappings: &[ColumnMapping]) -> Result<Vec<CsvRecord>, Box<dyn Error + Sync + Send + 'static>> {
+    let invalid_file = |reason: String| {
+        Box::new(CliUtilsError::InvalidInputFile(path.to_string_lossy().to_string(), reason))
+    };
+
+    let content = fs::read_to_string(path).map_err(|err| invalid_file(err.to_string()))?;
+
+    let header_line = content.lines().next().unwrap_or_default();
+
+    let delimiter = if header_line.contains(';') && !header_line.contains(',') { b';' } else { b',' };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(content.trim_start_matches('\u{feff}').as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|err| invalid_file(err.to_string()))?
+        .iter()
+        .map(str::to_string)
+        .collect();
+
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|err| invalid_file(err.to_string()))?;
+
+            let row = record.position().map(|position| position.line()).unwrap_or_default();
+
+            let values: Vec<String> = record.iter().map(str::to_string).collect();
+
+            Ok(CsvRecord::new(row, &headers, &values, mappings))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::entities::csv_import::parse_customers;
+
+    use super::*;
+
+    #[test]
+    fn read_comma_and_semicolon_files() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let folder = temp_dir_assert_fs.path();
+
+        let comma_path = folder.join("comma.csv");
+        fs::write(&comma_path, "name,address,city,postal\nKing SARL,\"1 rue des champs, bât. A\",Paris,75000\n").unwrap();
+        let semicolon_path = folder.join("semicolon.csv");
+        fs::write(&semicolon_path, "\u{feff}Nom;address;city;postal\nKing SARL;1 rue;Paris;75000\n").unwrap();
+
+        let records = read_csv_records(&comma_path, &[]).unwrap();
+        let customers = parse_customers(&records).unwrap();
+        assert_eq!(customers[0].row, 2);
+        assert_eq!(customers[0].customer.address, "1 rue des champs, bât. A");
+
+        let mappings = vec![ColumnMapping::from_str("Nom=name").unwrap()];
+        let records = read_csv_records(&semicolon_path, &mappings).unwrap();
+        let customers = parse_customers(&records).unwrap();
+        assert_eq!(customers[0].row, 2);
+        assert_eq!(customers[0].customer.name, "King SARL");
+
+        assert!(read_csv_records(&folder.join("missing.csv"), &[]).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::cli::utils::cli_utils_error::CliUtilsError;
+
+/// XML of a CII or UBL file, or the one embedded in a Factur-X PDF
+pub(crate) fn read_e_invoice_xml(path: &Path) -> Result<String, Box<dyn Error + Sync + Send + 'static>> {
+    let invalid_file = |reason: &str| {
+        Box::new(CliUtilsError::InvalidInputFile(path.to_string_lossy().to_string(), reason.to_string()))
+    };
+
+    let content = fs::read(path).map_err(|err| invalid_file(&err.to_string()))?;
+
+    if !content.starts_with(b"%PDF") {
+        return String::from_utf8(content).map_err(|_| invalid_file("not an UTF-8 XML file").into());
+    }
+
+    get_pdf_streams(&content)
+        .into_iter()
+        .filter_map(|stream| String::from_utf8(stream).ok())
+        .find(|stream| {
+            stream.trim_start_matches('\u{feff}').trim_start().starts_with("<?xml")
+                && (stream.contains("CrossIndustryInvoice") || stream.contains("urn:oasis:names:specification:ubl:schema:xsd"))
+        })
+        .ok_or_else(|| invalid_file("no Factur-X XML embedded in the PDF").into())
+}
+
+/// Content of the streams of a PDF, inflated when compressed with FlateDecode
+fn get_pdf_streams(pdf: &[u8]) -> Vec<Vec<u8>> {
+    let mut streams = vec![];
+    let mut position = 0;
+
+    while let Some(found) = find_bytes(&pdf[position..], b"stream") {
+        let keyword = position + found;
+        position = keyword + b"stream".len();
+
+        if pdf[..keyword].ends_with(b"end") {
+            continue;
+        }
+
+        let data_start = if pdf[position..].starts_with(b"\r\n") { position + 2 } else { position + 1 };
+
+        let Some(data_length) = pdf.get(data_start..).and_then(|data| find_bytes(data, b"endstream")) else {
+            break;
+        };
+
+        let data = &pdf[data_start..data_start + data_length];
+        let dictionary = &pdf[rfind_bytes(&pdf[..keyword], b"obj").unwrap_or_default()..keyword];
+
+        let stream = if find_bytes(dictionary, b"/FlateDecode").is_some() {
+            miniz_oxide::inflate::decompress_to_vec_zlib(data).ok()
+        } else {
+            Some(data.to_vec())
+        };
+
+        streams.extend(stream);
+        position = data_start + data_length + b"endstream".len();
+    }
+
+    streams
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+fn rfind_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_xml_embedded_in_pdf() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let folder = temp_dir_assert_fs.path();
+
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rsm:CrossIndustryInvoice/>\n";
+        let metadata = "<?xml version=\"1.0\"?><x:xmpmeta xmlns:x=\"adobe:ns:meta/\"/>";
+        let mut pdf = b"%PDF-1.7\n1 0 obj\n<< /Type /Metadata >>\nstream\n".to_vec();
+        pdf.extend(metadata.as_bytes());
+        pdf.extend(b"\nendstream\nendobj\n2 0 obj\n<< /Type /EmbeddedFile /Filter /FlateDecode >>\nstream\r\n");
+        pdf.extend(miniz_oxide::deflate::compress_to_vec_zlib(xml.as_bytes(), 6));
+        pdf.extend(b"\r\nendstream\nendobj\n%%EOF\n");
+
+        let pdf_path = folder.join("invoice.pdf");
+        fs::write(&pdf_path, &pdf).unwrap();
+        let xml_path = folder.join("invoice.xml");
+        fs::write(&xml_path, xml).unwrap();
+
+        assert_eq!(read_e_invoice_xml(&pdf_path).unwrap(), xml);
+        assert_eq!(read_e_invoice_xml(&xml_path).unwrap(), xml);
+
+        let empty_pdf_path = folder.join("empty.pdf");
+        fs::write(&empty_pdf_path, b"%PDF-1.7\n%%EOF\n").unwrap();
+        assert!(read_e_invoice_xml(&empty_pdf_path).is_err());
+    }
+}
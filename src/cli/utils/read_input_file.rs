@@ -0,0 +1,53 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+use crate::cli::utils::cli_utils_error::CliUtilsError;
+
+/// Read a JSON file when its extension is `.json`, a YAML file otherwise
+pub(crate) fn read_input_file<T: DeserializeOwned>(path: &Path) -> Result<T, Box<dyn Error + Sync + Send + 'static>> {
+    let invalid_file = |reason: String| {
+        Box::new(CliUtilsError::InvalidInputFile(path.to_string_lossy().to_string(), reason))
+    };
+
+    let content = fs::read_to_string(path).map_err(|err| invalid_file(err.to_string()))?;
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => serde_json::from_str(&content).map_err(|err| invalid_file(err.to_string()).into()),
+        _ => serde_yaml::from_str(&content).map_err(|err| invalid_file(err.to_string()).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Example {
+        name: String,
+        count: u32,
+    }
+
+    #[test]
+    fn read_json_and_yaml_files() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let folder = temp_dir_assert_fs.path();
+
+        let json_path = folder.join("example.json");
+        fs::write(&json_path, r#"{"name": "king", "count": 2}"#).unwrap();
+        let yaml_path = folder.join("example.yaml");
+        fs::write(&yaml_path, "name: king\ncount: 2\n").unwrap();
+        let invalid_path = folder.join("invalid.json");
+        fs::write(&invalid_path, "name: king\n").unwrap();
+
+        let expected = Example { name: "king".to_string(), count: 2 };
+        assert_eq!(read_input_file::<Example>(&json_path).unwrap(), expected);
+        assert_eq!(read_input_file::<Example>(&yaml_path).unwrap(), expected);
+        assert!(read_input_file::<Example>(&invalid_path).is_err());
+        assert!(read_input_file::<Example>(&folder.join("missing.yaml")).is_err());
+    }
+}
@@ -17,7 +17,7 @@ pub(crate) fn select_customer(file_manager: &FileManager) -> Result<(String, Cus
         .interact()
         .unwrap();
 
-    return Ok((all_customers[customer_index].0.to_string(), all_customers[customer_index].1.clone()));
+    Ok((all_customers[customer_index].0.to_string(), all_customers[customer_index].1.clone()))
 }
 
 #[cfg(test)]
@@ -29,10 +29,11 @@ mod tests {
     use crate::entities::customer::Customer;
     use crate::invoice_manager::invoice_manager::InvoiceManager;
 
+    #[allow(dead_code)]
     pub(crate) fn mock_select_customer(file_manager: &impl InvoiceManager) -> Result<Customer, Box<dyn Error + Sync + Send + 'static>> {
         let all_customers: HashMap<String, Customer> = file_manager.get_all_customers()?;
 
-        if all_customers.len() == 0 {
+        if all_customers.is_empty() {
             return Err(Box::new(CliUtilsError::NoInvoiceFound()));
         }
 
@@ -1,6 +1,7 @@
 use std::error::Error;
 
 use crate::cli::cli_error::CliError;
+use crate::cli::utils::ensure_interactive::ensure_interactive;
 use crate::cli::utils::select_customer::select_customer;
 use crate::entities::customer::Customer;
 use crate::file_manager::file_manager::FileManager;
@@ -20,6 +21,8 @@ pub(crate) fn select_customer_or_use_default(file_manager: &FileManager, custome
             }
         }
     } else {
-        select_customer(&file_manager)
+        ensure_interactive("The customer")?;
+
+        select_customer(file_manager)
     }
 }
@@ -0,0 +1,36 @@
+use std::error::Error;
+
+use dialoguer::FuzzySelect;
+
+use crate::cli::utils::cli_utils_error::CliUtilsError;
+use crate::entities::invoice::Invoice;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Draft given by its id, or chosen among all drafts, with its id
+pub(crate) fn select_draft_or_use_default(file_manager: &FileManager, draft_id: &Option<String>) -> Result<(String, Invoice), Box<dyn Error + Sync + Send + 'static>> {
+    if let Some(draft_preselected) = draft_id {
+        return Ok((draft_preselected.to_owned(), file_manager.get_draft(draft_preselected)?));
+    }
+
+    let mut all_drafts: Vec<(String, Invoice)> = file_manager.get_all_drafts()?.into_iter().collect();
+
+    if all_drafts.is_empty() {
+        return Err(Box::new(CliUtilsError::EmptyDraftFolder()));
+    }
+
+    all_drafts.sort_by(|(draft_id, _), (other_draft_id, _)| draft_id.cmp(other_draft_id));
+
+    let draft_labels: Vec<String> = all_drafts
+        .iter()
+        .map(|(draft_id, draft)| format!("{} - {} - {}", draft_id, draft.customer_id, draft.title))
+        .collect();
+
+    let draft_index = FuzzySelect::new()
+        .with_prompt("What is your draft?")
+        .items(&draft_labels)
+        .interact()
+        .unwrap();
+
+    Ok(all_drafts.swap_remove(draft_index))
+}
@@ -10,7 +10,7 @@ use crate::invoice_manager::invoice_manager::InvoiceManager;
 pub(crate) fn select_invoice(file_manager: &FileManager) -> Result<Invoice, Box<dyn Error + Sync + Send + 'static>> {
     let all_invoices: Vec<Invoice> = file_manager.get_all_invoices()?;
 
-    if all_invoices.len() == 0 {
+    if all_invoices.is_empty() {
         return Err(Box::new(CliUtilsError::NoInvoiceFound()));
     }
 
@@ -20,7 +20,7 @@ pub(crate) fn select_invoice(file_manager: &FileManager) -> Result<Invoice, Box<
         .interact()
         .unwrap();
 
-    return Ok(all_invoices[invoice_index].clone());
+    Ok(all_invoices[invoice_index].clone())
 }
 
 #[cfg(test)]
@@ -31,14 +31,14 @@ mod tests {
     use crate::entities::invoice::Invoice;
     use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-
+    #[allow(dead_code)]
     pub(crate) fn mock_select_invoice(file_manager: &impl InvoiceManager) -> Result<Invoice, Box<dyn Error + Sync + Send + 'static>> {
         let all_invoices: Vec<Invoice> = file_manager.get_all_invoices()?;
 
-        if all_invoices.len() == 0 {
+        if all_invoices.is_empty() {
             return Err(Box::new(CliUtilsError::NoInvoiceFound()));
         }
 
-        Ok(all_invoices.get(0).unwrap().clone())
+        Ok(all_invoices.first().unwrap().clone())
     }
 }
\ No newline at end of file
@@ -9,6 +9,6 @@ pub(crate) fn select_invoice_or_use_default(file_manager: &FileManager, invoice_
     if let Some(invoice_preselected) = invoice_ref {
         file_manager.get_invoice_by_ref(invoice_preselected)
     } else {
-        select_invoice(&file_manager)
+        select_invoice(file_manager)
     }
 }
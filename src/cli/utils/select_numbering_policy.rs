@@ -0,0 +1,62 @@
+use std::error::Error;
+
+use dialoguer::{Input, Select};
+
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::numbering_policy::{CounterReset, NumberingPolicy};
+
+/// Ask how new invoices are numbered, `None` keeps the dated references
+pub(crate) fn select_numbering_policy(numbering_policy: Option<NumberingPolicy>) -> Result<Option<NumberingPolicy>, Box<dyn Error + Sync + Send + 'static>> {
+    let keep_label = match &numbering_policy {
+        Some(numbering_policy) => format!("Keep {}", numbering_policy),
+        None => "Keep dated references (YYYYMMDDNN)".to_string(),
+    };
+    let items = [keep_label.as_str(), "Dated references (YYYYMMDDNN)", "Custom pattern"];
+
+    let numbering_index = Select::new()
+        .with_prompt("Invoice numbering")
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    match numbering_index {
+        0 => return Ok(numbering_policy),
+        1 => return Ok(None),
+        _ => {}
+    }
+
+    let all_counter_resets = CounterReset::all_counter_resets();
+
+    let mut new_numbering_policy = loop {
+        let pattern: String = Input::new()
+            .with_prompt("Pattern ({PREFIX}, {YYYY}, {YY}, {MM}, {DD}, {SEQ} or {SEQ:n})")
+            .with_initial_text("FAC-{YYYY}-{SEQ:5}")
+            .interact_text()?;
+
+        let counter_index = Select::new()
+            .with_prompt("Counter")
+            .items(&all_counter_resets)
+            .default(0)
+            .interact()?;
+
+        match NumberingPolicy::new(&pattern, all_counter_resets[counter_index]) {
+            Ok(new_numbering_policy) => break new_numbering_policy,
+            Err(error) => println!("{}", error),
+        }
+    };
+
+    for kind in [DocumentKind::Invoice, DocumentKind::CreditNote, DocumentKind::Deposit] {
+        let prefix: String = Input::new()
+            .with_prompt(format!("Prefix of {}", kind))
+            .with_initial_text(new_numbering_policy.get_prefix(kind))
+            .interact_text()?;
+
+        match kind {
+            DocumentKind::Invoice => new_numbering_policy.invoice_prefix = Some(prefix),
+            DocumentKind::CreditNote => new_numbering_policy.credit_note_prefix = Some(prefix),
+            DocumentKind::Deposit => new_numbering_policy.deposit_prefix = Some(prefix),
+        }
+    }
+
+    Ok(Some(new_numbering_policy))
+}
@@ -0,0 +1,30 @@
+use std::error::Error;
+
+use dialoguer::{Input, Select};
+
+use crate::entities::payment_terms::PaymentTerms;
+
+/// Ask for payment terms. The first choice keeps the default and returns `None`.
+pub(crate) fn select_payment_terms(prompt: &str, default_label: &str) -> Result<Option<PaymentTerms>, Box<dyn Error + Sync + Send + 'static>> {
+    let items = [default_label, "On receipt", "Net days", "Days end of month"];
+
+    let terms_index = Select::new()
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    if terms_index <= 1 {
+        return Ok((terms_index == 1).then_some(PaymentTerms::OnReceipt));
+    }
+
+    let days: u64 = Input::new()
+        .with_prompt("Number of days")
+        .default(PaymentTerms::DEFAULT_DAYS)
+        .interact_text()?;
+
+    match terms_index {
+        2 => Ok(Some(PaymentTerms::Net { days })),
+        _ => Ok(Some(PaymentTerms::EndOfMonth { days })),
+    }
+}
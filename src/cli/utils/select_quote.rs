@@ -0,0 +1,24 @@
+use std::error::Error;
+
+use dialoguer::FuzzySelect;
+
+use crate::cli::utils::cli_utils_error::CliUtilsError;
+use crate::entities::quote::Quote;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub(crate) fn select_quote(file_manager: &FileManager) -> Result<Quote, Box<dyn Error + Sync + Send + 'static>> {
+    let all_quotes: Vec<Quote> = file_manager.get_all_quotes()?;
+
+    if all_quotes.is_empty() {
+        return Err(Box::new(CliUtilsError::NoQuoteFound()));
+    }
+
+    let quote_index = FuzzySelect::new()
+        .with_prompt("What is your quote?")
+        .items(&all_quotes)
+        .interact()
+        .unwrap();
+
+    Ok(all_quotes[quote_index].clone())
+}
@@ -0,0 +1,14 @@
+use std::error::Error;
+
+use crate::cli::utils::select_quote::select_quote;
+use crate::entities::quote::Quote;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+pub(crate) fn select_quote_or_use_default(file_manager: &FileManager, quote_ref: &Option<String>) -> Result<Quote, Box<dyn Error + Sync + Send + 'static>> {
+    if let Some(quote_preselected) = quote_ref {
+        file_manager.get_quote_by_ref(quote_preselected)
+    } else {
+        select_quote(file_manager)
+    }
+}
@@ -0,0 +1,36 @@
+use std::error::Error;
+
+use dialoguer::{Input, Select};
+
+use crate::entities::vat_rate::VatRate;
+
+/// Ask for a VAT rate. The first choice keeps the default and returns `None`.
+pub(crate) fn select_vat_rate(prompt: &str, default_label: &str) -> Result<Option<VatRate>, Box<dyn Error + Sync + Send + 'static>> {
+    let rates = VatRate::all_rates();
+
+    let mut items: Vec<String> = vec![default_label.to_string()];
+    items.extend(rates.iter().map(|rate| rate.to_string()));
+    items.push("Exempt".to_string());
+
+    let rate_index = Select::new()
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    if rate_index == 0 {
+        return Ok(None);
+    }
+
+    match rates.get(rate_index - 1) {
+        Some(rate) => Ok(Some(rate.clone())),
+        None => {
+            let reason = Input::new()
+                .with_prompt("Exemption reason")
+                .with_initial_text(VatRate::FRANCHISE_REASON)
+                .interact_text()?;
+
+            Ok(Some(VatRate::Exempt { reason }))
+        }
+    }
+}
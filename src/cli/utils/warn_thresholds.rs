@@ -0,0 +1,34 @@
+use std::error::Error;
+
+use chrono::Datelike;
+
+use crate::entities::amount::Amount;
+use crate::entities::invoice::Invoice;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Warn when the invoice, not saved yet, makes the revenue of its year cross a threshold
+pub(crate) fn warn_thresholds(file_manager: &FileManager, invoice: &Invoice) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let thresholds = file_manager.get_settings()?.get_thresholds();
+
+    let revenue: Amount = file_manager
+        .get_invoice_by_year(invoice.date.year())?
+        .iter()
+        .map(|year_invoice| year_invoice.get_total_price())
+        .sum();
+
+    thresholds
+        .get_crossed(revenue, revenue + invoice.get_total_price())
+        .iter()
+        .for_each(|(name, limit)| {
+            println!(
+                "Warning : this invoice brings the {} revenue to {} € HT, over the {} of {} €",
+                invoice.date.year(),
+                revenue + invoice.get_total_price(),
+                name,
+                limit
+            )
+        });
+
+    Ok(())
+}
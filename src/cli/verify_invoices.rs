@@ -0,0 +1,42 @@
+use std::error::Error;
+
+use log::trace;
+
+use crate::cli::cli_error::CliError;
+use crate::entities::ledger::verify_ledger;
+use crate::file_manager::context_parameters::ContextParameters;
+use crate::file_manager::file_manager::FileManager;
+use crate::invoice_manager::invoice_manager::InvoiceManager;
+
+/// Check no invoice was modified, removed or inserted since it was issued.
+/// With `init`, seal the invoices of a folder without ledger instead.
+pub fn verify_invoices(context_parameters: ContextParameters, init: &bool) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    trace!("=== Verify invoices");
+
+    let file_manager = FileManager::new(context_parameters)?;
+
+    if *init {
+        let sealed_count = file_manager.seal_existing_invoices()?;
+
+        println!("{} invoices sealed in the ledger", sealed_count);
+
+        return Ok(());
+    }
+
+    let ledger = file_manager.get_ledger()?;
+    let all_invoices = file_manager.get_all_invoices()?;
+
+    println!("Verify {} documents against {} sealed in the ledger\n", all_invoices.len(), ledger.len());
+
+    let issues = verify_ledger(&ledger, &all_invoices);
+
+    issues.iter().for_each(|issue| println!("{}", issue));
+
+    if !issues.is_empty() {
+        return Err(Box::new(CliError::LedgerBroken(issues.len())));
+    }
+
+    println!("All invoices are untouched");
+
+    Ok(())
+}
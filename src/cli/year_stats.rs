@@ -3,34 +3,49 @@ use std::error::Error;
 use chrono::Datelike;
 use log::trace;
 
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::output::period_stats::PeriodStats;
+use crate::cli::output::print_output::print_output;
+use crate::cli::utils::get_period_range::get_year_range;
+use crate::cli::utils::print_totals::print_totals;
+use crate::entities::invoice_totals::InvoiceTotals;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::file_manager::FileManager;
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
-pub fn year_stats(context_parameters: ContextParameters, year: &Option<i32>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+pub fn year_stats(context_parameters: ContextParameters, output_format: OutputFormat, year: &Option<i32>) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
     trace!("=== Get year stats");
 
     let year = year.unwrap_or(chrono::Local::now().year());
 
+    get_year_range(year)?;
+
     let file_manager = FileManager::new(context_parameters)?;
 
     let all_year_invoices = file_manager.get_invoice_by_year(year)?;
 
-    let total_cost = all_year_invoices.iter()
-        .fold(0.0, |total, invoice| total + invoice.get_total_price());
+    let default_vat_rate = file_manager.get_settings()?.get_default_vat_rate();
+
+    let totals: InvoiceTotals = all_year_invoices
+        .iter()
+        .map(|invoice| invoice.get_totals(&default_vat_rate))
+        .sum();
 
-    let mut plural_offset = "";
-    if all_year_invoices.len() > 1 {
-        plural_offset = "s";
-    }
+    let period_stats = PeriodStats::new(year.to_string(), &all_year_invoices, totals.clone(), &default_vat_rate);
 
-    println!("Get {} invoice{} for {}\n", all_year_invoices.len(), plural_offset, year);
+    print_output(output_format, &period_stats, &period_stats.invoices, || {
+        let mut plural_offset = "";
+        if all_year_invoices.len() > 1 {
+            plural_offset = "s";
+        }
 
-    all_year_invoices
-        .iter()
-        .for_each(|invoice| println!("{}", invoice));
+        println!("Get {} invoice{} for {}\n", all_year_invoices.len(), plural_offset, year);
 
-    println!("Total : {} €", total_cost);
+        all_year_invoices
+            .iter()
+            .for_each(|invoice| println!("{}", invoice));
 
-    Ok(())
+        println!();
+        print_totals(&totals);
+    })
 }
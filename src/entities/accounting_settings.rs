@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::activity::Activity;
+
+/// Accounts and journals of the accounting exports, defaults follow the French chart of accounts
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[serde(default)]
+pub struct AccountingSettings {
+    /// Prefix of the auxiliary accounts of customers without their own account
+    pub customer_account: String,
+    /// Services
+    pub revenue_account: String,
+    /// Sale of goods
+    pub sale_account: String,
+    pub vat_account: String,
+    pub bank_account: String,
+    pub sales_journal: String,
+    pub bank_journal: String,
+}
+
+impl Default for AccountingSettings {
+    fn default() -> Self {
+        AccountingSettings {
+            customer_account: "411".to_string(),
+            revenue_account: "706".to_string(),
+            sale_account: "707".to_string(),
+            vat_account: "44571".to_string(),
+            bank_account: "512".to_string(),
+            sales_journal: "VT".to_string(),
+            bank_journal: "BQ".to_string(),
+        }
+    }
+}
+
+impl AccountingSettings {
+    pub fn get_revenue_account(&self, activity: &Activity) -> &str {
+        match activity {
+            Activity::Sale => &self.sale_account,
+            Activity::ServiceBic | Activity::ServiceBnc => &self.revenue_account,
+        }
+    }
+}
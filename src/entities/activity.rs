@@ -0,0 +1,103 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::entities::percentage::Percentage;
+
+/// Activity category of a micro-entreprise, each one declared separately to the URSSAF
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Activity {
+    /// Vente de marchandises (BIC)
+    Sale,
+    /// Prestations de services commerciales ou artisanales (BIC)
+    ServiceBic,
+    /// Prestations de services et professions libérales (BNC)
+    ServiceBnc,
+}
+
+impl Activity {
+    pub fn all_activities() -> Vec<Activity> {
+        vec![Activity::Sale, Activity::ServiceBic, Activity::ServiceBnc]
+    }
+
+    /// Line of the URSSAF declaration form
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            Activity::Sale => "Vente de marchandises (BIC)",
+            Activity::ServiceBic => "Prestations de services (BIC)",
+            Activity::ServiceBnc => "Prestations de services (BNC)",
+        }
+    }
+}
+
+impl FromStr for Activity {
+    type Err = String;
+
+    fn from_str(activity: &str) -> Result<Self, Self::Err> {
+        Activity::all_activities()
+            .into_iter()
+            .find(|candidate| candidate.to_string() == activity.trim().to_lowercase())
+            .ok_or_else(|| format!("Invalid activity: {} (sale, service_bic or service_bnc)", activity))
+    }
+}
+
+impl fmt::Display for Activity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Activity::Sale => write!(f, "sale"),
+            Activity::ServiceBic => write!(f, "service_bic"),
+            Activity::ServiceBnc => write!(f, "service_bnc"),
+        }
+    }
+}
+
+/// Social contribution rates of each activity. Defaults are the 2026 rates.
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[serde(default)]
+pub struct ContributionRates {
+    pub sale: Percentage,
+    pub service_bic: Percentage,
+    pub service_bnc: Percentage,
+}
+
+impl Default for ContributionRates {
+    fn default() -> Self {
+        ContributionRates {
+            sale: Percentage::new("12.3").unwrap(),
+            service_bic: Percentage::new("21.2").unwrap(),
+            service_bnc: Percentage::new("26.1").unwrap(),
+        }
+    }
+}
+
+impl ContributionRates {
+    pub fn get_rate(&self, activity: &Activity) -> Percentage {
+        match activity {
+            Activity::Sale => self.sale,
+            Activity::ServiceBic => self.service_bic,
+            Activity::ServiceBnc => self.service_bnc,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_from_str() {
+        assert_eq!(Activity::from_str("Service_BNC"), Ok(Activity::ServiceBnc));
+        assert!(Activity::from_str("bnc").is_err());
+    }
+
+    #[test]
+    fn test_contribution_rates_from_partial_yaml() {
+        let rates: ContributionRates = serde_yaml::from_str("service_bnc: 23.1\n").unwrap();
+
+        assert_eq!(rates.get_rate(&Activity::ServiceBnc), Percentage::new("23.1").unwrap());
+        assert_eq!(rates.get_rate(&Activity::Sale), Percentage::new("12.3").unwrap());
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::entities::fixed_point::{divide_half_up, format_fixed_point, parse_fixed_point};
+use crate::entities::quantity::Quantity;
+
+/// Money amount stored as an exact number of cents.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+pub struct Amount(i64);
+
+impl Amount {
+    pub const SCALE: u32 = 2;
+    pub const ZERO: Amount = Amount(0);
+
+    pub fn new(amount: &str) -> Result<Self, String> {
+        parse_fixed_point(amount, Self::SCALE)
+            .map(Amount)
+            .map_err(|_| format!("Invalid amount: {}", amount))
+    }
+
+    /// Part of the amount for a rate in basis points (1 % = 100), rounded half-up
+    pub fn get_percentage(&self, basis_points: i64) -> Amount {
+        Amount(divide_half_up(self.0 as i128 * basis_points as i128, 10_000) as i64)
+    }
+
+    pub fn abs(&self) -> Amount {
+        Amount(self.0.abs())
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / 10_f64.powi(Self::SCALE as i32)
+    }
+
+    /// Part of the amount in the proportion `part / total`, rounded half-up
+    pub fn get_share(&self, part: Amount, total: Amount) -> Amount {
+        Amount(divide_half_up(self.0 as i128 * part.0 as i128, total.0 as i128) as i64)
+    }
+
+    /// Amount rounded half-up to whole euros, as declared to the administration
+    pub fn round_to_unit(&self) -> Amount {
+        let unit = 10_i128.pow(Self::SCALE);
+        Amount((divide_half_up(self.0 as i128, unit) * unit) as i64)
+    }
+}
+
+impl FromStr for Amount {
+    type Err = String;
+
+    fn from_str(amount: &str) -> Result<Self, Self::Err> {
+        Amount::new(amount)
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_fixed_point(self.0, Self::SCALE))
+    }
+}
+
+impl Add for Amount {
+    type Output = Amount;
+
+    fn add(self, other: Amount) -> Amount {
+        Amount(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Amount {
+    fn add_assign(&mut self, other: Amount) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Amount {
+    type Output = Amount;
+
+    fn sub(self, other: Amount) -> Amount {
+        Amount(self.0 - other.0)
+    }
+}
+
+impl SubAssign for Amount {
+    fn sub_assign(&mut self, other: Amount) {
+        self.0 -= other.0;
+    }
+}
+
+impl Neg for Amount {
+    type Output = Amount;
+
+    fn neg(self) -> Amount {
+        Amount(-self.0)
+    }
+}
+
+/// Multiply a unit price by a quantity, rounded half-up to the cent.
+impl Mul<Quantity> for Amount {
+    type Output = Amount;
+
+    fn mul(self, quantity: Quantity) -> Amount {
+        Amount(divide_half_up(
+            self.0 as i128 * quantity.thousandths() as i128,
+            Quantity::UNIT as i128,
+        ) as i64)
+    }
+}
+
+/// Share of the amount between `count` parts, like an average, rounded half-up to the cent.
+impl Div<usize> for Amount {
+    type Output = Amount;
+
+    fn div(self, count: usize) -> Amount {
+        Amount(divide_half_up(self.0 as i128, count as i128) as i64)
+    }
+}
+
+impl Sum for Amount {
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Amount {
+        iter.fold(Amount::ZERO, |total, amount| total + amount)
+    }
+}
+
+impl<'a> Sum<&'a Amount> for Amount {
+    fn sum<I: Iterator<Item = &'a Amount>>(iter: I) -> Amount {
+        iter.fold(Amount::ZERO, |total, amount| total + *amount)
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(self.to_f64())
+    }
+}
+
+struct AmountVisitor;
+
+impl Visitor<'_> for AmountVisitor {
+    type Value = Amount;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal amount")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Amount, E> {
+        value
+            .checked_mul(10_i64.pow(Amount::SCALE))
+            .map(Amount)
+            .ok_or_else(|| E::custom(format!("Amount too large: {}", value)))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Amount, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    // Floats are read back through their shortest decimal representation so that
+    // values written by older versions (e.g. `1049.99`) keep their exact cents.
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Amount, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Amount, E> {
+        Amount::new(value).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D>(deserializer: D) -> Result<Amount, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(AmountVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_amount() {
+        assert_eq!(Amount::new("350").unwrap().0, 35_000);
+        assert_eq!(Amount::new("1049.99").unwrap().0, 104_999);
+        assert_eq!(Amount::new("0.125").unwrap().0, 13);
+        assert_eq!(Amount::new("-0.125").unwrap().0, -13);
+        assert_eq!(Amount::new("abc"), Err("Invalid amount: abc".to_string()));
+    }
+
+    #[test]
+    fn test_amount_display() {
+        assert_eq!(Amount(104_999).to_string(), "1049.99");
+        assert_eq!(Amount(-5).to_string(), "-0.05");
+        assert_eq!(Amount::ZERO.to_string(), "0.00");
+    }
+
+    #[test]
+    fn test_amount_operations() {
+        let amounts = [Amount(10), Amount(20), Amount(-5)];
+
+        assert_eq!(amounts.iter().sum::<Amount>(), Amount(25));
+        assert_eq!(Amount(10) - Amount(20), Amount(-10));
+        assert_eq!(-Amount(10), Amount(-10));
+        assert_eq!(Amount(100) / 3, Amount(33));
+        assert_eq!(Amount(5) / 2, Amount(3));
+    }
+
+    #[test]
+    fn test_amount_multiplied_by_quantity() {
+        let price = Amount::new("0.99").unwrap();
+
+        assert_eq!(price * Quantity::new("3").unwrap(), Amount::new("2.97").unwrap());
+        assert_eq!(price * Quantity::new("0.5").unwrap(), Amount::new("0.50").unwrap());
+        assert_eq!(price * Quantity::new("0.25").unwrap(), Amount::new("0.25").unwrap());
+        assert_eq!(-price * Quantity::new("0.5").unwrap(), Amount::new("-0.50").unwrap());
+    }
+
+    #[test]
+    fn test_amount_percentage() {
+        assert_eq!(Amount::new("100").unwrap().get_percentage(2_000), Amount::new("20").unwrap());
+        assert_eq!(Amount::new("10.01").unwrap().get_percentage(550), Amount::new("0.55").unwrap());
+        assert_eq!(Amount::new("0.10").unwrap().get_percentage(550), Amount::new("0.01").unwrap());
+        assert_eq!(Amount::new("-0.10").unwrap().get_percentage(550), Amount::new("-0.01").unwrap());
+    }
+
+    #[test]
+    fn test_amount_share_and_rounding() {
+        let amount = Amount::new("100").unwrap();
+
+        assert_eq!(amount.get_share(Amount::new("1").unwrap(), Amount::new("3").unwrap()), Amount::new("33.33").unwrap());
+        assert_eq!(Amount::new("1234.50").unwrap().round_to_unit(), Amount::new("1235").unwrap());
+        assert_eq!(Amount::new("1234.49").unwrap().round_to_unit(), Amount::new("1234").unwrap());
+    }
+
+    #[test]
+    fn test_amount_yaml() {
+        assert_eq!(serde_yaml::to_string(&Amount::new("350").unwrap()).unwrap(), "350.0\n");
+        assert_eq!(
+            serde_yaml::to_string(&Amount::new("1049.99").unwrap()).unwrap(),
+            "1049.99\n"
+        );
+
+        let amount: Amount = serde_yaml::from_str("1049.99").unwrap();
+        assert_eq!(amount.0, 104_999);
+
+        let amount: Amount = serde_yaml::from_str("350").unwrap();
+        assert_eq!(amount.0, 35_000);
+
+        let amount: Amount = serde_yaml::from_str("'12.30'").unwrap();
+        assert_eq!(amount.0, 1_230);
+
+        let amount: Amount = serde_yaml::from_str("0.1").unwrap();
+        assert_eq!(amount.0, 10);
+    }
+}
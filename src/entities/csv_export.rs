@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::entities::customer::Customer;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice::Invoice;
+use crate::entities::product::Product;
+use crate::entities::vat_rate::VatRate;
+
+/// Data exported or imported as CSV
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum CsvContent {
+    Invoices,
+    Customers,
+}
+
+impl CsvContent {
+    pub fn all_contents() -> Vec<CsvContent> {
+        vec![CsvContent::Invoices, CsvContent::Customers]
+    }
+}
+
+impl FromStr for CsvContent {
+    type Err = String;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        CsvContent::all_contents()
+            .into_iter()
+            .find(|candidate| candidate.to_string() == content.to_lowercase())
+            .ok_or_else(|| format!("Invalid CSV content: {} (invoices or customers)", content))
+    }
+}
+
+impl fmt::Display for CsvContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvContent::Invoices => write!(f, "invoices"),
+            CsvContent::Customers => write!(f, "customers"),
+        }
+    }
+}
+
+/// One row by invoice with its totals, or one row by product line
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum CsvRowsPer {
+    #[default]
+    Invoice,
+    Line,
+}
+
+impl CsvRowsPer {
+    pub fn all_rows_per() -> Vec<CsvRowsPer> {
+        vec![CsvRowsPer::Invoice, CsvRowsPer::Line]
+    }
+}
+
+impl FromStr for CsvRowsPer {
+    type Err = String;
+
+    fn from_str(rows_per: &str) -> Result<Self, Self::Err> {
+        CsvRowsPer::all_rows_per()
+            .into_iter()
+            .find(|candidate| candidate.to_string() == rows_per.to_lowercase())
+            .ok_or_else(|| format!("Invalid CSV rows: {} (invoice or line)", rows_per))
+    }
+}
+
+impl fmt::Display for CsvRowsPer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvRowsPer::Invoice => write!(f, "invoice"),
+            CsvRowsPer::Line => write!(f, "line"),
+        }
+    }
+}
+
+/// Customer with its id, the columns are the fields read by the import
+#[derive(Serialize, PartialEq, Debug)]
+pub struct CustomerCsvRow {
+    pub id: String,
+    pub name: String,
+    pub address: String,
+    pub city: String,
+    pub postal: String,
+    pub payment_terms: Option<String>,
+    pub account: Option<String>,
+    pub siren: Option<String>,
+    pub tva: Option<String>,
+}
+
+impl CustomerCsvRow {
+    /// Rows sorted by customer id
+    pub fn from_customers(customers: &HashMap<String, Customer>) -> Vec<CustomerCsvRow> {
+        let mut rows: Vec<CustomerCsvRow> = customers
+            .iter()
+            .map(|(customer_id, customer)| CustomerCsvRow {
+                id: customer_id.to_owned(),
+                name: customer.name.to_owned(),
+                address: customer.address.to_owned(),
+                city: customer.city.to_owned(),
+                postal: customer.postal.to_owned(),
+                payment_terms: customer.payment_terms.as_ref().map(|payment_terms| payment_terms.get_code()),
+                account: customer.account.to_owned(),
+                siren: customer.siren.as_ref().map(|siren| siren.to_string()),
+                tva: customer.tva.to_owned(),
+            })
+            .collect();
+
+        rows.sort_by(|row, other_row| row.id.cmp(&other_row.id));
+
+        rows
+    }
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct InvoiceCsvRow {
+    pub reference: String,
+    pub date: String,
+    pub due_date: String,
+    pub customer: String,
+    pub title: String,
+    pub kind: DocumentKind,
+    pub credited_invoice_ref: Option<String>,
+    pub total_ht: String,
+    pub total_vat: String,
+    pub total_ttc: String,
+}
+
+/// Product line with the columns of its invoice, the import groups the lines by reference
+#[derive(Serialize, PartialEq, Debug)]
+pub struct InvoiceLineCsvRow {
+    pub reference: String,
+    pub date: String,
+    pub due_date: String,
+    pub customer: String,
+    pub title: String,
+    pub kind: DocumentKind,
+    pub credited_invoice_ref: Option<String>,
+    pub description: String,
+    pub quantity: String,
+    pub price: String,
+    pub vat: Option<String>,
+    pub activity: Option<String>,
+    pub total_ht: String,
+}
+
+impl InvoiceCsvRow {
+    pub fn new(invoice: &Invoice, default_vat_rate: &VatRate) -> InvoiceCsvRow {
+        let totals = invoice.get_totals(default_vat_rate);
+
+        InvoiceCsvRow {
+            reference: invoice.get_ref().unwrap_or_default(),
+            date: invoice.date.format("%Y-%m-%d").to_string(),
+            due_date: invoice.get_due_date().format("%Y-%m-%d").to_string(),
+            customer: invoice.customer_id.to_owned(),
+            title: invoice.title.to_owned(),
+            kind: invoice.kind,
+            credited_invoice_ref: invoice.credited_invoice_ref.to_owned(),
+            total_ht: totals.total_ht.to_string(),
+            total_vat: totals.total_vat.to_string(),
+            total_ttc: totals.total_ttc.to_string(),
+        }
+    }
+}
+
+impl InvoiceLineCsvRow {
+    /// Rate and activity stay empty when the line uses the default of the settings, as on import
+    pub fn new(invoice: &Invoice, product: &Product) -> InvoiceLineCsvRow {
+        InvoiceLineCsvRow {
+            reference: invoice.get_ref().unwrap_or_default(),
+            date: invoice.date.format("%Y-%m-%d").to_string(),
+            due_date: invoice.get_due_date().format("%Y-%m-%d").to_string(),
+            customer: invoice.customer_id.to_owned(),
+            title: invoice.title.to_owned(),
+            kind: invoice.kind,
+            credited_invoice_ref: invoice.credited_invoice_ref.to_owned(),
+            description: product.description.to_owned(),
+            quantity: product.quantity.to_string(),
+            price: product.price.to_string(),
+            vat: product.vat.as_ref().map(VatRate::to_string),
+            activity: product.activity.map(|activity| activity.to_string()),
+            total_ht: product.get_total_price().to_string(),
+        }
+    }
+
+    pub fn from_invoices(invoices: &[Invoice]) -> Vec<InvoiceLineCsvRow> {
+        invoices
+            .iter()
+            .flat_map(|invoice| {
+                invoice
+                    .products
+                    .iter()
+                    .map(|product| InvoiceLineCsvRow::new(invoice, product))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::amount::Amount;
+    use crate::entities::payment_terms::PaymentTerms;
+
+    use super::*;
+
+    #[test]
+    fn csv_rows_of_invoices_and_customers() {
+        let mut invoice = Invoice::generate_simple_invoice_example();
+        invoice.products.push(Product {
+            description: "Reduced".to_string(),
+            quantity: "0.5".parse().unwrap(),
+            price: Amount::new("100").unwrap(),
+            vat: Some(VatRate::Reduced),
+            activity: Some(crate::entities::activity::Activity::Sale),
+        });
+
+        let row = InvoiceCsvRow::new(&invoice, &VatRate::Normal);
+        assert_eq!(row.reference, "2015031401");
+        assert_eq!(row.date, "2015-03-14");
+        assert_eq!(row.due_date, "2015-04-13");
+        assert_eq!(row.total_ht, "400.00");
+        assert_eq!(row.total_vat, "72.75");
+        assert_eq!(row.total_ttc, "472.75");
+
+        let lines = InvoiceLineCsvRow::from_invoices(&[invoice]);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].vat, None);
+        assert_eq!(lines[0].activity, None);
+        assert_eq!(lines[1].quantity, "0.5");
+        assert_eq!(lines[1].vat, Some("5.5 %".to_string()));
+        assert_eq!(lines[1].activity, Some("sale".to_string()));
+        assert_eq!(lines[1].total_ht, "50.00");
+
+        let mut customer = Customer::simple_customer();
+        customer.payment_terms = Some(PaymentTerms::EndOfMonth { days: 45 });
+        let customers = HashMap::from([
+            ("queen".to_string(), Customer::simple_with_name("Queen".to_string())),
+            ("king".to_string(), customer),
+        ]);
+
+        let rows = CustomerCsvRow::from_customers(&customers);
+        assert_eq!(rows[0].id, "king");
+        assert_eq!(rows[0].payment_terms, Some("end_of_month:45".to_string()));
+        assert_eq!(rows[1].id, "queen");
+        assert_eq!(rows[1].payment_terms, None);
+    }
+
+    #[test]
+    fn csv_content_and_rows_from_str() {
+        assert_eq!(CsvContent::from_str("Invoices"), Ok(CsvContent::Invoices));
+        assert_eq!(CsvContent::from_str("customers"), Ok(CsvContent::Customers));
+        assert!(CsvContent::from_str("payments").is_err());
+        assert_eq!(CsvRowsPer::from_str("line"), Ok(CsvRowsPer::Line));
+        assert!(CsvRowsPer::from_str("product").is_err());
+    }
+}
@@ -0,0 +1,431 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+
+use crate::entities::amount::Amount;
+use crate::entities::customer::Customer;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice::{Invoice, InvoiceDayId};
+use crate::entities::numbering_policy::validate_reference;
+use crate::entities::payment_terms::PaymentTerms;
+use crate::entities::product::Product;
+use crate::entities::quantity::Quantity;
+use crate::entities::siren::Siren;
+
+pub const CUSTOMER_FIELDS: [&str; 8] = ["name", "address", "city", "postal", "payment_terms", "account", "siren", "tva"];
+
+pub const INVOICE_FIELDS: [&str; 12] = [
+    "reference",
+    "date",
+    "due_date",
+    "customer",
+    "title",
+    "kind",
+    "credited_invoice_ref",
+    "description",
+    "quantity",
+    "price",
+    "vat",
+    "activity",
+];
+
+/// Column of the file read as a field of the import, written `column=field`
+#[derive(PartialEq, Clone, Debug)]
+pub struct ColumnMapping {
+    pub column: String,
+    pub field: String,
+}
+
+impl ColumnMapping {
+    /// Error on the first mapping to a field the import does not know
+    pub fn validate(mappings: &[ColumnMapping], fields: &[&str]) -> Result<(), String> {
+        match mappings.iter().find(|mapping| !fields.contains(&mapping.field.as_str())) {
+            Some(mapping) => Err(format!("Unknown field {} ({})", mapping.field, fields.join(", "))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for ColumnMapping {
+    type Err = String;
+
+    fn from_str(mapping: &str) -> Result<Self, Self::Err> {
+        match mapping.split_once('=') {
+            Some((column, field)) if !column.trim().is_empty() && !field.trim().is_empty() => Ok(ColumnMapping {
+                column: column.trim().to_string(),
+                field: field.trim().to_lowercase(),
+            }),
+            _ => Err(format!("Invalid column mapping: {} (column=field)", mapping)),
+        }
+    }
+}
+
+/// Values of a row by field, `row` is its line in the file
+#[derive(PartialEq, Clone, Debug)]
+pub struct CsvRecord {
+    pub row: u64,
+    values: HashMap<String, String>,
+}
+
+impl CsvRecord {
+    /// Columns without mapping are read as the field of the same name
+    pub fn new(row: u64, headers: &[String], values: &[String], mappings: &[ColumnMapping]) -> CsvRecord {
+        let values = headers
+            .iter()
+            .zip(values)
+            .map(|(header, value)| {
+                let field = mappings
+                    .iter()
+                    .find(|mapping| mapping.column == header.trim())
+                    .map(|mapping| mapping.field.to_owned())
+                    .unwrap_or_else(|| header.trim().to_lowercase());
+
+                (field, value.trim().to_string())
+            })
+            .collect();
+
+        CsvRecord { row, values }
+    }
+
+    fn get(&self, field: &str) -> Option<&str> {
+        self.values.get(field).map(String::as_str).filter(|value| !value.is_empty())
+    }
+
+    fn get_required(&self, field: &str) -> Result<&str, String> {
+        self.get(field).ok_or_else(|| format!("{} is missing", field))
+    }
+
+    fn parse<T: FromStr<Err = String>>(&self, field: &str) -> Result<Option<T>, String> {
+        self.get(field).map(T::from_str).transpose()
+    }
+
+    fn parse_date(&self, field: &str) -> Result<Option<NaiveDate>, String> {
+        self.get(field)
+            .map(|date| {
+                NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .or_else(|_| NaiveDate::parse_from_str(date, "%d/%m/%Y"))
+                    .map_err(|_| format!("Invalid {}: {} (YYYY-MM-DD or DD/MM/YYYY)", field, date))
+            })
+            .transpose()
+    }
+
+    fn error(&self, message: String) -> RowError {
+        RowError { row: self.row, message }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RowError {
+    pub row: u64,
+    pub message: String,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Row {}: {}", self.row, self.message)
+    }
+}
+
+/// Customer read on a row of the file
+#[derive(Clone)]
+pub struct ImportedCustomer {
+    pub row: u64,
+    pub customer: Customer,
+}
+
+/// Invoice read on one or several rows of the file, `row` is the first one
+#[derive(Clone)]
+pub struct ImportedInvoice {
+    pub row: u64,
+    pub invoice: Invoice,
+}
+
+/// Customers of the records, all the invalid rows are reported
+pub fn parse_customers(records: &[CsvRecord]) -> Result<Vec<ImportedCustomer>, Vec<RowError>> {
+    let mut customers: Vec<ImportedCustomer> = vec![];
+    let mut errors = vec![];
+
+    for record in records {
+        match parse_customer(record) {
+            Ok(customer) => match customers
+                .iter()
+                .find(|imported| imported.customer.serialized_name() == customer.serialized_name())
+            {
+                Some(imported) => errors.push(record.error(format!(
+                    "customer {} is already on row {}",
+                    customer.name, imported.row
+                ))),
+                None => customers.push(ImportedCustomer { row: record.row, customer }),
+            },
+            Err(message) => errors.push(record.error(message)),
+        }
+    }
+
+    if errors.is_empty() { Ok(customers) } else { Err(errors) }
+}
+
+fn parse_customer(record: &CsvRecord) -> Result<Customer, String> {
+    Ok(Customer {
+        name: record.get_required("name")?.to_string(),
+        address: record.get_required("address")?.to_string(),
+        city: record.get_required("city")?.to_string(),
+        postal: record.get_required("postal")?.to_string(),
+        payment_terms: record.parse("payment_terms")?,
+        account: record.get("account").map(str::to_string),
+        siren: record.get("siren").map(Siren::new).transpose()?,
+        tva: record.get("tva").map(str::to_string),
+    })
+}
+
+/// Invoices of the records, the rows of a same reference are the lines of one invoice
+/// and rows without reference are invoices of one line numbered on creation.
+/// Only the first row of an invoice needs a title.
+/// Without due date, the due date comes from the payment terms of the customer.
+pub fn parse_invoices(
+    records: &[CsvRecord],
+    customers: &HashMap<String, Customer>,
+    default_payment_terms: &PaymentTerms,
+) -> Result<Vec<ImportedInvoice>, Vec<RowError>> {
+    let mut invoices: Vec<ImportedInvoice> = vec![];
+    let mut errors = vec![];
+
+    for record in records {
+        let (invoice, product) = match parse_invoice_line(record, customers, default_payment_terms) {
+            Ok(invoice_line) => invoice_line,
+            Err(message) => {
+                errors.push(record.error(message));
+                continue;
+            }
+        };
+
+        let same_invoice = invoice.get_ref().and_then(|reference| {
+            invoices
+                .iter_mut()
+                .find(|imported| imported.invoice.get_ref().as_ref() == Some(&reference))
+        });
+
+        match same_invoice {
+            Some(imported)
+                if imported.invoice.date != invoice.date
+                    || imported.invoice.customer_id != invoice.customer_id
+                    || imported.invoice.kind != invoice.kind =>
+            {
+                errors.push(record.error(format!(
+                    "date, customer and kind differ from row {} of the same invoice",
+                    imported.row
+                )))
+            }
+            Some(imported) => imported.invoice.products.push(product),
+            None if invoice.title.is_empty() => errors.push(record.error("title is missing".to_string())),
+            None => invoices.push(ImportedInvoice {
+                row: record.row,
+                invoice: Invoice { products: vec![product], ..invoice },
+            }),
+        }
+    }
+
+    if errors.is_empty() { Ok(invoices) } else { Err(errors) }
+}
+
+fn parse_invoice_line(
+    record: &CsvRecord,
+    customers: &HashMap<String, Customer>,
+    default_payment_terms: &PaymentTerms,
+) -> Result<(Invoice, Product), String> {
+    let date = record.parse_date("date")?.ok_or_else(|| "date is missing".to_string())?;
+
+    let customer_id = record.get_required("customer")?;
+
+    let customer = customers
+        .get(customer_id)
+        .ok_or_else(|| format!("customer {} not found", customer_id))?;
+
+    let kind: DocumentKind = record.parse("kind")?.unwrap_or_default();
+
+    let due_date = match record.parse_date("due_date")? {
+        Some(due_date) => due_date,
+        None => customer.get_payment_terms(default_payment_terms).get_due_date(date),
+    };
+
+    let reference = record.get("reference");
+
+    // References are the file names of the invoices
+    for field in ["reference", "credited_invoice_ref"] {
+        if let Some(reference) = record.get(field) {
+            validate_reference(reference)?;
+        }
+    }
+
+    let invoice_day_id = reference.and_then(|reference| get_dated_day_id(kind, date, reference));
+
+    let invoice = Invoice {
+        date,
+        due_date: Some(due_date),
+        customer_id: customer_id.to_string(),
+        title: record.get("title").unwrap_or_default().to_string(),
+        reference: reference.filter(|_| invoice_day_id.is_none()).map(str::to_string),
+        invoice_day_id,
+        products: vec![],
+        kind,
+        credited_invoice_ref: record.get("credited_invoice_ref").map(str::to_string),
+        quote_ref: None,
+        sequence: None,
+    };
+
+    let product = Product {
+        description: record.get_required("description")?.to_string(),
+        quantity: Quantity::new(record.get_required("quantity")?)?,
+        price: Amount::new(record.get_required("price")?)?,
+        vat: record.parse("vat")?,
+        activity: record.parse("activity")?,
+    };
+
+    Ok((invoice, product))
+}
+
+/// Day id of a reference made of the prefix of the kind, the date and two digits
+pub(crate) fn get_dated_day_id(kind: DocumentKind, date: NaiveDate, reference: &str) -> Option<InvoiceDayId> {
+    reference
+        .strip_prefix(kind.get_prefix())?
+        .strip_prefix(&date.format("%Y%m%d").to_string())
+        .filter(|day_id| day_id.len() == 2)
+        .and_then(|day_id| InvoiceDayId::new(day_id).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::vat_rate::VatRate;
+
+    use super::*;
+
+    fn records(lines: &[&str], mappings: &[ColumnMapping]) -> Vec<CsvRecord> {
+        let split = |line: &str| line.split(',').map(str::to_string).collect::<Vec<String>>();
+        let headers = split(lines[0]);
+
+        lines[1..]
+            .iter()
+            .enumerate()
+            .map(|(index, line)| CsvRecord::new(index as u64 + 2, &headers, &split(line), mappings))
+            .collect()
+    }
+
+    #[test]
+    fn column_mapping_from_str() {
+        assert_eq!(
+            ColumnMapping::from_str("Raison sociale = Name"),
+            Ok(ColumnMapping { column: "Raison sociale".to_string(), field: "name".to_string() })
+        );
+        assert!(ColumnMapping::from_str("name").is_err());
+        assert!(ColumnMapping::from_str("=name").is_err());
+
+        let mappings = vec![ColumnMapping::from_str("Ville=city").unwrap()];
+        assert!(ColumnMapping::validate(&mappings, &CUSTOMER_FIELDS).is_ok());
+        assert!(ColumnMapping::validate(&mappings, &INVOICE_FIELDS).is_err());
+    }
+
+    #[test]
+    fn parse_customers_with_mapping_and_errors() {
+        let mappings = vec![ColumnMapping::from_str("Raison sociale=name").unwrap()];
+
+        let customers = parse_customers(&records(
+            &["Raison sociale,address,city,postal,payment_terms", "King SARL,1 rue,Paris,75000,net:45"],
+            &mappings,
+        ))
+        .unwrap();
+        assert_eq!(customers.len(), 1);
+        assert_eq!(customers[0].customer.name, "King SARL");
+        assert_eq!(customers[0].customer.payment_terms, Some(PaymentTerms::Net { days: 45 }));
+
+        let errors = parse_customers(&records(
+            &[
+                "name,address,city,postal,payment_terms",
+                "King SARL,1 rue,Paris,75000,",
+                "Queen,2 rue,,75000,",
+                "Jack,3 rue,Lyon,69000,soon",
+                "King SARL,4 rue,Paris,75000,",
+            ],
+            &[],
+        ))
+        .err()
+        .unwrap();
+        assert_eq!(
+            errors.iter().map(RowError::to_string).collect::<Vec<String>>(),
+            vec![
+                "Row 3: city is missing",
+                "Row 4: Invalid payment terms: soon (on_receipt, net:DAYS or end_of_month:DAYS)",
+                "Row 5: customer King SARL is already on row 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_invoices_grouped_by_reference() {
+        let customers = HashMap::from([("king".to_string(), Customer::simple_customer())]);
+        let header = "reference,date,customer,title,kind,description,quantity,price,vat";
+
+        let invoices = parse_invoices(
+            &records(
+                &[
+                    header,
+                    "2023051001,2023-05-10,king,Website,,Design,2,300,",
+                    "2023051001,2023-05-10,king,,,Hosting,1,50,10",
+                    "F-2023-12,15/06/2023,king,Support,,Support,1,100,",
+                    ",2023-07-01,king,Training,,Training,1,500,",
+                    "AV2023080101,2023-08-01,king,Refund,credit_note,Design,1,-300,",
+                ],
+                &[],
+            ),
+            &customers,
+            &PaymentTerms::default(),
+        )
+        .unwrap();
+
+        assert_eq!(invoices.len(), 4);
+        let first = &invoices[0].invoice;
+        assert_eq!(first.get_ref(), Some("2023051001".to_string()));
+        assert_eq!(first.reference, None);
+        assert_eq!(first.products.len(), 2);
+        assert_eq!(first.products[1].vat, Some(VatRate::Intermediate));
+        assert_eq!(first.due_date, NaiveDate::from_ymd_opt(2023, 6, 9));
+        assert_eq!(invoices[1].invoice.reference, Some("F-2023-12".to_string()));
+        assert_eq!(invoices[1].invoice.invoice_day_id, None);
+        assert_eq!(invoices[1].invoice.date, NaiveDate::from_ymd_opt(2023, 6, 15).unwrap());
+        assert_eq!(invoices[2].invoice.get_ref(), None);
+        assert_eq!(invoices[3].row, 6);
+        assert_eq!(invoices[3].invoice.kind, DocumentKind::CreditNote);
+        assert_eq!(invoices[3].invoice.get_ref(), Some("AV2023080101".to_string()));
+
+        let errors = parse_invoices(
+            &records(
+                &[
+                    header,
+                    "2023051001,2023-05-10,king,Website,,Design,2,300,",
+                    "2023051001,2023-05-11,king,Website,,Hosting,1,50,",
+                    "2023051002,2023-05-10,queen,Website,,Design,2,300,",
+                    "2023051003,2023-05-10,king,Website,,Design,two,300,",
+                    "2023051004,2023-13-10,king,Website,,Design,2,300,",
+                    "../../x,2023-05-10,king,Website,,Design,2,300,",
+                    "2023/001,2023-05-10,king,Website,,Design,2,300,",
+                ],
+                &[],
+            ),
+            &customers,
+            &PaymentTerms::default(),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            errors.iter().map(RowError::to_string).collect::<Vec<String>>(),
+            vec![
+                "Row 3: date, customer and kind differ from row 2 of the same invoice",
+                "Row 4: customer queen not found",
+                "Row 5: Invalid quantity: two",
+                "Row 6: Invalid date: 2023-13-10 (YYYY-MM-DD or DD/MM/YYYY)",
+                "Row 7: Reference ../../x is not a valid file name",
+                "Row 8: Reference 2023/001 is not a valid file name",
+            ]
+        );
+    }
+}
@@ -1,4 +1,6 @@
+use crate::entities::payment_terms::PaymentTerms;
 use crate::entities::serializer::serializer;
+use crate::entities::siren::Siren;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -7,12 +9,39 @@ pub struct Customer {
     pub address: String,
     pub city: String,
     pub postal: String,
+    /// Overrides the terms of the settings for this customer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_terms: Option<PaymentTerms>,
+    /// Auxiliary account of the customer in the accounting exports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// Identifiers of the buyer in the electronic invoices
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub siren: Option<Siren>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tva: Option<String>,
 }
 
 impl Customer {
     pub fn serialized_name(&self) -> String {
         serializer(&self.name)
     }
+
+    pub fn get_payment_terms(&self, default_payment_terms: &PaymentTerms) -> PaymentTerms {
+        self.payment_terms.clone().unwrap_or_else(|| default_payment_terms.clone())
+    }
+
+    /// Account set on the customer, else the customer account followed by the id in capitals
+    pub fn get_account(&self, customer_id: &str, customer_account: &str) -> String {
+        self.account.clone().unwrap_or_else(|| {
+            customer_account.to_owned()
+                + &customer_id
+                    .chars()
+                    .filter(char::is_ascii_alphanumeric)
+                    .collect::<String>()
+                    .to_uppercase()
+        })
+    }
 }
 
 #[cfg(test)]
@@ -22,20 +51,28 @@ mod tests {
 
     impl Customer {
         pub fn simple_customer() -> Customer {
-            return Customer {
+            Customer {
                 name: "King SARL".into(),
                 address: "1 rue des champs".into(),
                 city: "Paris".into(),
                 postal: "75000".into(),
-            };
+                payment_terms: None,
+                account: None,
+                siren: None,
+                tva: None,
+            }
         }
         pub fn simple_with_name(name: String) -> Customer {
-            return Customer {
+            Customer {
                 name,
                 address: "1 rue des champs".into(),
                 city: "Paris".into(),
                 postal: "75000".into(),
-            };
+                payment_terms: None,
+                account: None,
+                siren: None,
+                tva: None,
+            }
         }
     }
 
@@ -69,6 +106,33 @@ mod tests {
         assert_eq!(base_customer_example.address, customer_example.address);
         assert_eq!(base_customer_example.city, customer_example.city);
         assert_eq!(base_customer_example.postal, customer_example.postal);
+        assert_eq!(customer_example.payment_terms, None);
+    }
+
+    #[test]
+    fn customer_account() {
+        let mut customer = Customer::simple_customer();
+
+        assert_eq!(customer.get_account("king-sarl", "411"), "411KINGSARL");
+
+        customer.account = Some("4110001".into());
+        assert_eq!(customer.get_account("king-sarl", "411"), "4110001");
+    }
+
+    #[test]
+    fn customer_payment_terms() {
+        let mut customer_example = Customer::simple_customer();
+
+        assert_eq!(
+            customer_example.get_payment_terms(&PaymentTerms::default()),
+            PaymentTerms::default()
+        );
+
+        customer_example.payment_terms = Some(PaymentTerms::EndOfMonth { days: 45 });
+        assert_eq!(
+            customer_example.get_payment_terms(&PaymentTerms::default()),
+            PaymentTerms::EndOfMonth { days: 45 }
+        );
     }
 
     #[test]
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::entities::amount::Amount;
+use crate::entities::invoice::Invoice;
+use crate::entities::payment::Payment;
+use crate::entities::vat_rate::VatRate;
+
+/// Movement on the account of a customer, amounts are TTC
+#[derive(PartialEq, Clone, Debug)]
+pub struct StatementLine {
+    pub date: NaiveDate,
+    pub reference: String,
+    pub label: String,
+    pub debit: Amount,
+    pub credit: Amount,
+}
+
+/// Invoices, credit notes and payments of a customer between two dates.
+/// Movements before `from` are summed in the opening balance.
+#[derive(PartialEq, Clone, Debug)]
+pub struct CustomerStatement {
+    pub customer_id: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub opening_balance: Amount,
+    pub lines: Vec<StatementLine>,
+    pub closing_balance: Amount,
+}
+
+impl StatementLine {
+    fn balance(&self) -> Amount {
+        self.debit - self.credit
+    }
+}
+
+impl CustomerStatement {
+    pub fn new(
+        customer_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        all_invoices: &[Invoice],
+        all_payments: &HashMap<String, Vec<Payment>>,
+        default_vat_rate: &VatRate,
+    ) -> Self {
+        let mut all_lines: Vec<StatementLine> = vec![];
+
+        for invoice in all_invoices.iter().filter(|invoice| invoice.customer_id == customer_id) {
+            let reference = invoice.get_ref().unwrap_or_default();
+            let total_ttc = invoice.get_totals(default_vat_rate).total_ttc;
+
+            all_lines.push(StatementLine {
+                date: invoice.date,
+                reference: reference.clone(),
+                label: invoice.kind.get_title().to_string(),
+                debit: total_ttc.max(Amount::ZERO),
+                credit: (-total_ttc).max(Amount::ZERO),
+            });
+
+            for payment in all_payments.get(&reference).into_iter().flatten() {
+                all_lines.push(StatementLine {
+                    date: payment.date,
+                    reference: reference.clone(),
+                    label: "Règlement".to_string(),
+                    debit: Amount::ZERO,
+                    credit: payment.amount,
+                });
+            }
+        }
+
+        all_lines.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.reference.cmp(&b.reference)));
+
+        let opening_balance: Amount = all_lines
+            .iter()
+            .filter(|line| line.date < from)
+            .map(StatementLine::balance)
+            .sum();
+        let lines: Vec<StatementLine> = all_lines
+            .into_iter()
+            .filter(|line| line.date >= from && line.date <= to)
+            .collect();
+        let closing_balance = opening_balance + lines.iter().map(StatementLine::balance).sum::<Amount>();
+
+        CustomerStatement {
+            customer_id: customer_id.to_string(),
+            from,
+            to,
+            opening_balance,
+            lines,
+            closing_balance,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::payment::PaymentMethod;
+    use crate::entities::product::Product;
+
+    use super::*;
+
+    #[test]
+    fn test_customer_statement() {
+        let first = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2023, 12, 10).unwrap(),
+        );
+        let second = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2024, 2, 10).unwrap(),
+        );
+        let mut credit_note = second.create_credit_note(
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            vec![Product::from_str("Product example;1;50").unwrap()],
+        );
+        credit_note.invoice_day_id = Some(InvoiceDayId::new("01").unwrap());
+
+        let all_payments = HashMap::from([(
+            "2023121001".to_string(),
+            vec![Payment {
+                date: NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(),
+                amount: Amount::new("350").unwrap(),
+                method: PaymentMethod::Transfer,
+                reference: None,
+            }],
+        )]);
+
+        let statement = CustomerStatement::new(
+            "king",
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 28).unwrap(),
+            &[first, second, credit_note],
+            &all_payments,
+            &VatRate::Zero,
+        );
+
+        assert_eq!(statement.opening_balance, Amount::new("350").unwrap());
+        assert_eq!(statement.lines.len(), 2);
+        assert_eq!(statement.lines[0].label, "Règlement");
+        assert_eq!(statement.lines[0].reference, "2023121001");
+        assert_eq!(statement.lines[0].credit, Amount::new("350").unwrap());
+        assert_eq!(statement.lines[1].label, "Facture");
+        assert_eq!(statement.lines[1].debit, Amount::new("350").unwrap());
+        assert_eq!(statement.closing_balance, Amount::new("350").unwrap());
+    }
+}
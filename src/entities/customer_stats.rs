@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+
+use crate::entities::amount::Amount;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice::Invoice;
+use crate::entities::payment::Payment;
+use crate::entities::vat_rate::VatRate;
+
+/// Invoices of a customer issued during a year, revenue HT with credit notes deduced
+#[derive(Serialize, PartialEq, Debug)]
+pub struct YearRevenue {
+    pub year: i32,
+    pub invoices: usize,
+    pub revenue: Amount,
+}
+
+/// Lifetime figures of a customer. Invoices count invoices and deposits, not credit notes.
+#[derive(Serialize, PartialEq, Debug)]
+pub struct CustomerStats {
+    pub customer_id: String,
+    pub invoices: usize,
+    pub credit_notes: usize,
+    /// HT, credit notes deduced
+    pub revenue: Amount,
+    pub average_invoice: Amount,
+    #[serde(skip)]
+    pub last_invoice_date: Option<NaiveDate>,
+    /// TTC still to collect
+    pub outstanding: Amount,
+    pub years: Vec<YearRevenue>,
+}
+
+impl CustomerStats {
+    pub fn new(
+        customer_id: &str,
+        all_invoices: &[Invoice],
+        all_payments: &HashMap<String, Vec<Payment>>,
+        default_vat_rate: &VatRate,
+    ) -> Self {
+        let customer_invoices: Vec<&Invoice> = all_invoices
+            .iter()
+            .filter(|invoice| invoice.customer_id == customer_id)
+            .collect();
+        let (credit_notes, invoices): (Vec<&Invoice>, Vec<&Invoice>) = customer_invoices
+            .iter()
+            .partition(|invoice| invoice.kind == DocumentKind::CreditNote);
+
+        let mut years: BTreeMap<i32, YearRevenue> = BTreeMap::new();
+        for invoice in customer_invoices.iter() {
+            let year_revenue = years.entry(invoice.date.year()).or_insert(YearRevenue {
+                year: invoice.date.year(),
+                invoices: 0,
+                revenue: Amount::ZERO,
+            });
+
+            if invoice.kind != DocumentKind::CreditNote {
+                year_revenue.invoices += 1;
+            }
+            year_revenue.revenue += invoice.get_total_price();
+        }
+
+        let revenue: Amount = customer_invoices.iter().map(|invoice| invoice.get_total_price()).sum();
+
+        let outstanding: Amount = invoices
+            .iter()
+            .map(|invoice| {
+                let paid: Amount = invoice
+                    .get_ref()
+                    .and_then(|invoice_ref| all_payments.get(&invoice_ref))
+                    .map(|payments| payments.iter().map(|payment| payment.amount).sum())
+                    .unwrap_or_default();
+
+                (invoice.get_amount_due(all_invoices, default_vat_rate) - paid).max(Amount::ZERO)
+            })
+            .sum();
+
+        CustomerStats {
+            customer_id: customer_id.to_string(),
+            invoices: invoices.len(),
+            credit_notes: credit_notes.len(),
+            revenue,
+            average_invoice: if invoices.is_empty() { Amount::ZERO } else { revenue / invoices.len() },
+            last_invoice_date: invoices.iter().map(|invoice| invoice.date).max(),
+            outstanding,
+            years: years.into_values().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::payment::PaymentMethod;
+    use crate::entities::product::Product;
+
+    use super::*;
+
+    #[test]
+    fn test_customer_stats() {
+        let first = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2023, 12, 10).unwrap(),
+        );
+        let second = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2024, 2, 10).unwrap(),
+        );
+        let mut credit_note = second.create_credit_note(
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            vec![Product::from_str("Product example;1;50").unwrap()],
+        );
+        credit_note.invoice_day_id = Some(InvoiceDayId::new("01").unwrap());
+        let mut other_customer = second.clone();
+        other_customer.customer_id = "queen".to_string();
+
+        let all_payments = HashMap::from([(
+            "2023121001".to_string(),
+            vec![Payment {
+                date: NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(),
+                amount: Amount::new("350").unwrap(),
+                method: PaymentMethod::Transfer,
+                reference: None,
+            }],
+        )]);
+
+        let stats = CustomerStats::new(
+            "king",
+            &[first, second, credit_note, other_customer],
+            &all_payments,
+            &VatRate::Zero,
+        );
+
+        assert_eq!(stats.invoices, 2);
+        assert_eq!(stats.credit_notes, 1);
+        assert_eq!(stats.revenue, Amount::new("650").unwrap());
+        assert_eq!(stats.average_invoice, Amount::new("325").unwrap());
+        assert_eq!(stats.last_invoice_date, NaiveDate::from_ymd_opt(2024, 2, 10));
+        assert_eq!(stats.outstanding, Amount::new("300").unwrap());
+        assert_eq!(
+            stats.years,
+            vec![
+                YearRevenue { year: 2023, invoices: 1, revenue: Amount::new("350").unwrap() },
+                YearRevenue { year: 2024, invoices: 1, revenue: Amount::new("300").unwrap() },
+            ]
+        );
+    }
+}
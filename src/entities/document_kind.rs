@@ -0,0 +1,87 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of a numbered document. Each kind has its own numbering series.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentKind {
+    #[default]
+    Invoice,
+    /// Avoir : cancels all or part of an invoice
+    CreditNote,
+    /// Facture d'acompte : part of a quote billed before the final invoice
+    Deposit,
+}
+
+impl DocumentKind {
+    /// Prefix of the reference, empty for invoices to keep the historical references
+    pub fn get_prefix(&self) -> &'static str {
+        match self {
+            DocumentKind::Invoice => "",
+            DocumentKind::CreditNote => "AV",
+            DocumentKind::Deposit => "AC",
+        }
+    }
+
+    /// Title printed on the PDF
+    pub fn get_title(&self) -> &'static str {
+        match self {
+            DocumentKind::Invoice => "Facture",
+            DocumentKind::CreditNote => "Avoir",
+            DocumentKind::Deposit => "Facture d'acompte",
+        }
+    }
+
+    pub fn is_invoice(&self) -> bool {
+        *self == DocumentKind::Invoice
+    }
+}
+
+impl FromStr for DocumentKind {
+    type Err = String;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind.to_lowercase().as_str() {
+            "invoice" => Ok(DocumentKind::Invoice),
+            "credit_note" => Ok(DocumentKind::CreditNote),
+            "deposit" => Ok(DocumentKind::Deposit),
+            _ => Err(format!("Invalid document kind: {} (invoice, credit_note or deposit)", kind)),
+        }
+    }
+}
+
+impl fmt::Display for DocumentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocumentKind::Invoice => write!(f, "invoice"),
+            DocumentKind::CreditNote => write!(f, "credit note"),
+            DocumentKind::Deposit => write!(f, "deposit invoice"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_kind_to_string() {
+        assert_eq!(
+            serde_yaml::to_string(&DocumentKind::CreditNote).unwrap(),
+            "credit_note\n"
+        );
+
+        let document_kind: DocumentKind = serde_yaml::from_str("invoice").unwrap();
+        assert_eq!(document_kind, DocumentKind::Invoice);
+    }
+
+    #[test]
+    fn test_document_kind_prefix() {
+        assert_eq!(DocumentKind::Invoice.get_prefix(), "");
+        assert_eq!(DocumentKind::CreditNote.get_prefix(), "AV");
+        assert_eq!(DocumentKind::CreditNote.get_title(), "Avoir");
+        assert_eq!(DocumentKind::Deposit.get_prefix(), "AC");
+    }
+}
@@ -0,0 +1,652 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use chrono::NaiveDate;
+use roxmltree::{Document, Node, NodeId};
+
+use crate::entities::amount::Amount;
+use crate::entities::csv_import::get_dated_day_id;
+use crate::entities::customer::Customer;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::factur_x::{format_vat_percent, get_type_code};
+use crate::entities::invoice::Invoice;
+use crate::entities::numbering_policy::validate_reference;
+use crate::entities::payment_terms::PaymentTerms;
+use crate::entities::product::Product;
+use crate::entities::quantity::Quantity;
+use crate::entities::siren::Siren;
+use crate::entities::vat_rate::VatRate;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum EInvoiceSyntax {
+    /// Cross Industry Invoice, the XML of Factur-X
+    Cii,
+    Ubl,
+}
+
+impl fmt::Display for EInvoiceSyntax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EInvoiceSyntax::Cii => write!(f, "CII"),
+            EInvoiceSyntax::Ubl => write!(f, "UBL"),
+        }
+    }
+}
+
+pub struct ImportedEInvoice {
+    pub syntax: EInvoiceSyntax,
+    pub invoice: Invoice,
+    /// Buyer of the document when it is not one of the customers
+    pub new_customer: Option<Customer>,
+    /// Path of the elements of the document kept nowhere in the invoice or the customer
+    pub unmapped: Vec<String>,
+    /// Values read but changed or left out, and totals differing from the document
+    pub warnings: Vec<String>,
+}
+
+/// Elements of a document found by their local names, whatever their namespace prefixes.
+/// The elements read are remembered to report the others.
+struct XmlReader<'a, 'input> {
+    root: Node<'a, 'input>,
+    read: RefCell<HashSet<NodeId>>,
+}
+
+impl<'a, 'input> XmlReader<'a, 'input> {
+    fn find(&self, node: Node<'a, 'input>, path: &str) -> Option<Node<'a, 'input>> {
+        path.split('/')
+            .try_fold(node, |node, name| node.children().find(|child| child.tag_name().name() == name))
+    }
+
+    /// Elements of the last name of the path under the first element of the rest of the path
+    fn find_all(&self, node: Node<'a, 'input>, path: &str) -> Vec<Node<'a, 'input>> {
+        let (parent, name) = match path.rsplit_once('/') {
+            Some((parent_path, name)) => (self.find(node, parent_path), name),
+            None => (Some(node), path),
+        };
+
+        parent
+            .map(|parent| parent.children().filter(|child| child.tag_name().name() == name).collect())
+            .unwrap_or_default()
+    }
+
+    fn text(&self, node: Node<'a, 'input>, path: &str) -> Option<String> {
+        let found = self.find(node, path)?;
+
+        self.read.borrow_mut().insert(found.id());
+
+        found.text().map(str::trim).filter(|text| !text.is_empty()).map(str::to_string)
+    }
+
+    fn required(&self, node: Node<'a, 'input>, path: &str) -> Result<String, String> {
+        self.text(node, path).ok_or_else(|| format!("{} is missing", path))
+    }
+
+    /// Elements of the path and all their content, for the values computed again on import
+    fn skip(&self, node: Node<'a, 'input>, path: &str) {
+        for found in self.find_all(node, path) {
+            self.read.borrow_mut().extend(found.descendants().map(|descendant| descendant.id()));
+        }
+    }
+
+    /// Path of the elements with a value not read, once per path with the number of occurrences
+    fn unmapped(&self) -> Vec<String> {
+        let read = self.read.borrow();
+        let mut unmapped: Vec<(String, usize)> = vec![];
+
+        for node in self.root.descendants().filter(|node| node.is_element() && !read.contains(&node.id())) {
+            if node.text().is_none_or(|text| text.trim().is_empty()) {
+                continue;
+            }
+
+            let mut names: Vec<&str> = node
+                .ancestors()
+                .filter(|ancestor| ancestor.is_element() && *ancestor != self.root)
+                .map(|ancestor| ancestor.tag_name().name())
+                .collect();
+            names.reverse();
+            let path = names.join("/");
+
+            match unmapped.iter_mut().find(|(unmapped_path, _)| *unmapped_path == path) {
+                Some((_, count)) => *count += 1,
+                None => unmapped.push((path, 1)),
+            }
+        }
+
+        unmapped
+            .into_iter()
+            .map(|(path, count)| if count > 1 { format!("{} ({} times)", path, count) } else { path })
+            .collect()
+    }
+}
+
+/// Values of a document before they are checked and turned into an invoice
+struct EInvoiceContent {
+    reference: String,
+    type_code: String,
+    date: NaiveDate,
+    due_date: Option<NaiveDate>,
+    title: Option<String>,
+    credited_invoice_ref: Option<String>,
+    currency: String,
+    /// SIREN or SIRET of the seller, the document must be an invoice of the enterprise
+    seller_identifier: Option<String>,
+    buyer: Customer,
+    lines: Vec<EInvoiceLine>,
+    exemption_reason: Option<String>,
+    grand_total: Option<String>,
+    warnings: Vec<String>,
+}
+
+struct EInvoiceLine {
+    description: String,
+    quantity: String,
+    price: String,
+    category: String,
+    percent: Option<String>,
+}
+
+/// Invoice and buyer of a CII or UBL document issued by the enterprise of SIREN `seller_siren`.
+/// The buyer is matched with the customers by SIREN, then by name.
+pub fn parse_e_invoice(
+    xml: &str,
+    customers: &HashMap<String, Customer>,
+    default_payment_terms: &PaymentTerms,
+    default_vat_rate: &VatRate,
+    seller_siren: &Siren,
+) -> Result<ImportedEInvoice, String> {
+    let document = Document::parse(xml).map_err(|err| format!("Invalid XML: {}", err))?;
+
+    let reader = XmlReader { root: document.root_element(), read: RefCell::new(HashSet::new()) };
+
+    let (syntax, content) = match reader.root.tag_name().name() {
+        "CrossIndustryInvoice" => (EInvoiceSyntax::Cii, read_cii(&reader)?),
+        "Invoice" | "CreditNote" => (EInvoiceSyntax::Ubl, read_ubl(&reader)?),
+        name => return Err(format!("{} is neither a CII nor a UBL document", name)),
+    };
+
+    let mut warnings = content.warnings;
+
+    // The reference is the file name of the invoice
+    validate_reference(&content.reference).map_err(|err| format!("Invalid ID: {}", err))?;
+
+    if let Some(credited_invoice_ref) = &content.credited_invoice_ref {
+        validate_reference(credited_invoice_ref).map_err(|err| format!("Invalid credited invoice ID: {}", err))?;
+    }
+
+    // An invoice of another seller would be booked as a revenue of the enterprise
+    match content.seller_identifier.as_deref().map(get_siren_part) {
+        Some(siren) if siren != seller_siren.to_string() => {
+            return Err(format!("Seller {} is not the enterprise of the settings ({})", siren, seller_siren));
+        }
+        Some(_) => {}
+        None => warnings.push(format!("No seller identifier, the enterprise {} is assumed to be the seller", seller_siren)),
+    }
+
+    if content.currency != "EUR" {
+        return Err(format!("Currency {} not supported, only EUR", content.currency));
+    }
+
+    let kind = [DocumentKind::Invoice, DocumentKind::CreditNote, DocumentKind::Deposit]
+        .into_iter()
+        .find(|kind| get_type_code(*kind) == content.type_code)
+        .ok_or_else(|| format!("Type code {} not supported (380, 381 or 386)", content.type_code))?;
+
+    if content.lines.is_empty() {
+        return Err("The document has no line".to_string());
+    }
+
+    let (customer_id, new_customer) = match customers.iter().find(|(_, customer)| {
+        content.buyer.siren.is_some() && customer.siren == content.buyer.siren
+    }) {
+        Some((customer_id, _)) => (customer_id.to_owned(), None),
+        None if customers.contains_key(&content.buyer.serialized_name()) => (content.buyer.serialized_name(), None),
+        None => (content.buyer.serialized_name(), Some(content.buyer.clone())),
+    };
+
+    let customer = customers.get(&customer_id).unwrap_or(&content.buyer);
+
+    let mut products = vec![];
+
+    for (index, line) in content.lines.iter().enumerate() {
+        let invalid_line = |err: String| format!("Line {}: {}", index + 1, err);
+
+        let price = Amount::new(&line.price).map_err(invalid_line)?;
+        let quantity = Quantity::new(&line.quantity).map_err(invalid_line)?;
+
+        // A discount has a negative quantity in the document (BR-27) and a negative price here
+        let (price, quantity) = if quantity < Quantity::ZERO { (-price, -quantity) } else { (price, quantity) };
+
+        let vat_rate = match line.category.as_str() {
+            "E" => VatRate::Exempt {
+                reason: content.exemption_reason.clone().unwrap_or_else(|| {
+                    warnings.push(format!("Line {}: no exemption reason, \"{}\" is used", index + 1, VatRate::FRANCHISE_REASON));
+                    VatRate::FRANCHISE_REASON.to_string()
+                }),
+            },
+            "Z" => VatRate::Zero,
+            "S" => {
+                let percent = line.percent.as_deref().unwrap_or_default();
+                let percent_amount = Amount::new(percent).map_err(invalid_line)?;
+
+                VatRate::all_rates()
+                    .into_iter()
+                    .find(|vat_rate| Amount::new(&format_vat_percent(vat_rate)) == Ok(percent_amount))
+                    .ok_or_else(|| invalid_line(format!("VAT rate {} % not supported", percent)))?
+            }
+            category => return Err(invalid_line(format!("VAT category {} not supported (S, Z or E)", category))),
+        };
+
+        products.push(Product {
+            description: line.description.to_owned(),
+            quantity,
+            price: if kind == DocumentKind::CreditNote { -price } else { price },
+            vat: Some(vat_rate).filter(|vat_rate| vat_rate != default_vat_rate),
+            activity: None,
+        });
+    }
+
+    let invoice_day_id = get_dated_day_id(kind, content.date, &content.reference);
+
+    let invoice = Invoice {
+        date: content.date,
+        due_date: Some(
+            content
+                .due_date
+                .unwrap_or_else(|| customer.get_payment_terms(default_payment_terms).get_due_date(content.date)),
+        ),
+        customer_id,
+        title: content.title.unwrap_or_else(|| products[0].description.to_owned()),
+        reference: Some(content.reference).filter(|_| invoice_day_id.is_none()),
+        invoice_day_id,
+        products,
+        kind,
+        credited_invoice_ref: content.credited_invoice_ref,
+        quote_ref: None,
+        sequence: None,
+    };
+
+    if let Some(grand_total) = content.grand_total {
+        let total_ttc = invoice.get_totals(default_vat_rate).total_ttc;
+        let total_ttc = if kind == DocumentKind::CreditNote { -total_ttc } else { total_ttc };
+
+        if Amount::new(&grand_total) != Ok(total_ttc) {
+            warnings.push(format!("Total of the document {} differs from the imported total {}", grand_total, total_ttc));
+        }
+    }
+
+    Ok(ImportedEInvoice { syntax, invoice, new_customer, unmapped: reader.unmapped(), warnings })
+}
+
+fn parse_date(date: &str, format: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, format).map_err(|_| format!("Invalid date: {}", date))
+}
+
+/// SIREN part of a SIREN or a SIRET, without spaces
+fn get_siren_part(identifier: &str) -> String {
+    let identifier: String = identifier.chars().filter(|character| !character.is_whitespace()).collect();
+
+    if identifier.len() == 14 { identifier[..9].to_string() } else { identifier }
+}
+
+fn parse_siren(identifier: &str, warnings: &mut Vec<String>) -> Option<Siren> {
+    Siren::new(&get_siren_part(identifier))
+        .map_err(|_| warnings.push(format!("Buyer identifier {} is not a SIREN, not kept", identifier.trim())))
+        .ok()
+}
+
+fn check_country(country: Option<String>, warnings: &mut Vec<String>) {
+    if let Some(country) = country.filter(|country| country != "FR") {
+        warnings.push(format!("Buyer country {} not kept, customers are in France", country));
+    }
+}
+
+fn read_cii(reader: &XmlReader) -> Result<EInvoiceContent, String> {
+    let root = reader.root;
+    let mut warnings = vec![];
+
+    reader.skip(root, "ExchangedDocumentContext");
+
+    let document = reader.find(root, "ExchangedDocument").ok_or("ExchangedDocument is missing")?;
+    let transaction = reader.find(root, "SupplyChainTradeTransaction").ok_or("SupplyChainTradeTransaction is missing")?;
+    let settlement = reader.find(transaction, "ApplicableHeaderTradeSettlement");
+
+    let seller_identifier = reader
+        .find(transaction, "ApplicableHeaderTradeAgreement/SellerTradeParty")
+        .and_then(|seller_node| reader.text(seller_node, "SpecifiedLegalOrganization/ID"));
+
+    let buyer_node = reader
+        .find(transaction, "ApplicableHeaderTradeAgreement/BuyerTradeParty")
+        .ok_or("BuyerTradeParty is missing")?;
+
+    let buyer_identifier = reader.text(buyer_node, "SpecifiedLegalOrganization/ID");
+    check_country(reader.text(buyer_node, "PostalTradeAddress/CountryID"), &mut warnings);
+
+    let buyer = Customer {
+        name: reader.required(buyer_node, "Name")?,
+        address: reader.text(buyer_node, "PostalTradeAddress/LineOne").unwrap_or_default(),
+        city: reader.text(buyer_node, "PostalTradeAddress/CityName").unwrap_or_default(),
+        postal: reader.text(buyer_node, "PostalTradeAddress/PostcodeCode").unwrap_or_default(),
+        payment_terms: None,
+        account: None,
+        siren: buyer_identifier.and_then(|identifier| parse_siren(&identifier, &mut warnings)),
+        tva: reader.text(buyer_node, "SpecifiedTaxRegistration/ID"),
+    };
+
+    let mut lines = vec![];
+
+    for line in reader.find_all(transaction, "IncludedSupplyChainTradeLineItem") {
+        reader.skip(line, "AssociatedDocumentLineDocument/LineID");
+        reader.skip(line, "SpecifiedLineTradeSettlement/ApplicableTradeTax/TypeCode");
+        reader.skip(line, "SpecifiedLineTradeSettlement/SpecifiedTradeSettlementLineMonetarySummation");
+
+        lines.push(EInvoiceLine {
+            description: reader.required(line, "SpecifiedTradeProduct/Name")?,
+            quantity: reader.required(line, "SpecifiedLineTradeDelivery/BilledQuantity")?,
+            price: reader.required(line, "SpecifiedLineTradeAgreement/NetPriceProductTradePrice/ChargeAmount")?,
+            category: reader.required(line, "SpecifiedLineTradeSettlement/ApplicableTradeTax/CategoryCode")?,
+            percent: reader.text(line, "SpecifiedLineTradeSettlement/ApplicableTradeTax/RateApplicablePercent"),
+        });
+    }
+
+    let mut exemption_reason = None;
+    let mut due_date = None;
+    let mut credited_invoice_ref = None;
+    let mut grand_total = None;
+    let mut currency = None;
+
+    if let Some(settlement) = settlement {
+        exemption_reason = reader
+            .find_all(settlement, "ApplicableTradeTax")
+            .into_iter()
+            .find_map(|tax| reader.text(tax, "ExemptionReason"));
+        due_date = reader
+            .text(settlement, "SpecifiedTradePaymentTerms/DueDateDateTime/DateTimeString")
+            .map(|date| parse_date(&date, "%Y%m%d"))
+            .transpose()?;
+        credited_invoice_ref = reader.text(settlement, "InvoiceReferencedDocument/IssuerAssignedID");
+        grand_total = reader.text(settlement, "SpecifiedTradeSettlementHeaderMonetarySummation/GrandTotalAmount");
+        currency = reader.text(settlement, "InvoiceCurrencyCode");
+
+        reader.skip(settlement, "ApplicableTradeTax");
+        reader.skip(settlement, "SpecifiedTradeSettlementHeaderMonetarySummation");
+    }
+
+    Ok(EInvoiceContent {
+        reference: reader.required(document, "ID")?,
+        type_code: reader.required(document, "TypeCode")?,
+        date: parse_date(&reader.required(document, "IssueDateTime/DateTimeString")?, "%Y%m%d")?,
+        due_date,
+        title: reader.text(document, "Name"),
+        credited_invoice_ref,
+        currency: currency.ok_or("InvoiceCurrencyCode is missing")?,
+        seller_identifier,
+        buyer,
+        lines,
+        exemption_reason,
+        grand_total,
+        warnings,
+    })
+}
+
+fn read_ubl(reader: &XmlReader) -> Result<EInvoiceContent, String> {
+    let root = reader.root;
+    let mut warnings = vec![];
+
+    reader.skip(root, "CustomizationID");
+    reader.skip(root, "ProfileID");
+
+    let (type_code_name, line_name, quantity_name) = match root.tag_name().name() {
+        "CreditNote" => ("CreditNoteTypeCode", "CreditNoteLine", "CreditedQuantity"),
+        _ => ("InvoiceTypeCode", "InvoiceLine", "InvoicedQuantity"),
+    };
+
+    let seller_identifier = reader.find(root, "AccountingSupplierParty/Party").and_then(|seller_node| {
+        reader
+            .text(seller_node, "PartyLegalEntity/CompanyID")
+            .or(reader.text(seller_node, "EndpointID").filter(|endpoint| endpoint.chars().all(|character| character.is_ascii_digit())))
+    });
+
+    let buyer_node = reader
+        .find(root, "AccountingCustomerParty/Party")
+        .ok_or("AccountingCustomerParty is missing")?;
+
+    let buyer_identifier = reader.text(buyer_node, "PartyLegalEntity/CompanyID");
+    let endpoint = reader.text(buyer_node, "EndpointID");
+    check_country(reader.text(buyer_node, "PostalAddress/Country/IdentificationCode"), &mut warnings);
+    reader.skip(buyer_node, "PartyTaxScheme/TaxScheme");
+
+    let buyer = Customer {
+        name: reader
+            .text(buyer_node, "PartyLegalEntity/RegistrationName")
+            .or_else(|| reader.text(buyer_node, "PartyName/Name"))
+            .ok_or("PartyLegalEntity/RegistrationName is missing")?,
+        address: reader.text(buyer_node, "PostalAddress/StreetName").unwrap_or_default(),
+        city: reader.text(buyer_node, "PostalAddress/CityName").unwrap_or_default(),
+        postal: reader.text(buyer_node, "PostalAddress/PostalZone").unwrap_or_default(),
+        payment_terms: None,
+        account: None,
+        siren: buyer_identifier
+            .or(endpoint.filter(|endpoint| endpoint.chars().all(|character| character.is_ascii_digit())))
+            .and_then(|identifier| parse_siren(&identifier, &mut warnings)),
+        tva: reader.text(buyer_node, "PartyTaxScheme/CompanyID"),
+    };
+    // The buyer name is often given twice
+    if reader.text(buyer_node, "PartyName/Name").as_ref() == Some(&buyer.name) {
+        reader.skip(buyer_node, "PartyName/Name");
+    }
+
+    let mut lines = vec![];
+
+    for line in reader.find_all(root, line_name) {
+        reader.skip(line, "ID");
+        reader.skip(line, "LineExtensionAmount");
+        reader.skip(line, "Item/ClassifiedTaxCategory/TaxScheme");
+
+        lines.push(EInvoiceLine {
+            description: reader.required(line, "Item/Name")?,
+            quantity: reader.required(line, quantity_name)?,
+            price: reader.required(line, "Price/PriceAmount")?,
+            category: reader.required(line, "Item/ClassifiedTaxCategory/ID")?,
+            percent: reader.text(line, "Item/ClassifiedTaxCategory/Percent"),
+        });
+    }
+
+    let exemption_reason = reader
+        .find_all(root, "TaxTotal/TaxSubtotal")
+        .into_iter()
+        .find_map(|subtotal| reader.text(subtotal, "TaxCategory/TaxExemptionReason"));
+    let due_date = reader
+        .text(root, "DueDate")
+        .or_else(|| reader.text(root, "PaymentMeans/PaymentDueDate"))
+        .map(|date| parse_date(&date, "%Y-%m-%d"))
+        .transpose()?;
+    let grand_total = reader.text(root, "LegalMonetaryTotal/TaxInclusiveAmount");
+
+    reader.skip(root, "TaxTotal");
+    reader.skip(root, "LegalMonetaryTotal");
+
+    Ok(EInvoiceContent {
+        reference: reader.required(root, "ID")?,
+        type_code: reader.required(root, type_code_name)?,
+        date: parse_date(&reader.required(root, "IssueDate")?, "%Y-%m-%d")?,
+        due_date,
+        title: None,
+        credited_invoice_ref: reader.text(root, "BillingReference/InvoiceDocumentReference/ID"),
+        currency: reader.required(root, "DocumentCurrencyCode")?,
+        seller_identifier,
+        buyer,
+        lines,
+        exemption_reason,
+        grand_total,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::factur_x::{FacturX, FacturXProfile};
+    use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::settings::Settings;
+    use crate::entities::ubl::Ubl;
+
+    use super::*;
+
+    fn generate_customer() -> Customer {
+        let mut customer = Customer::simple_customer();
+        customer.siren = Some(Siren::from(987654321));
+        customer.tva = Some("FR44987654321".to_string());
+        customer
+    }
+
+    #[test]
+    fn read_exported_cii() {
+        let settings = Settings::generate_simple_settings();
+        let mut invoice = Invoice::generate_simple_invoice_example();
+        invoice.products[0].vat = Some(VatRate::Normal);
+        invoice.products.push(Product {
+            description: "Remise".to_string(),
+            quantity: Quantity::from(1),
+            price: Amount::new("-50").unwrap(),
+            vat: Some(VatRate::Normal),
+            activity: None,
+        });
+        let xml = FacturX::new(&invoice, &generate_customer(), &settings, FacturXProfile::En16931).to_string();
+        let customers = HashMap::from([("king".to_string(), generate_customer())]);
+
+        let imported = parse_e_invoice(&xml, &customers, &settings.get_payment_terms(), &settings.get_default_vat_rate(), &settings.enterprise.siren).unwrap();
+
+        assert_eq!(imported.syntax, EInvoiceSyntax::Cii);
+        assert!(imported.new_customer.is_none());
+        assert_eq!(imported.invoice.get_ref(), Some("2015031401".to_string()));
+        assert_eq!(imported.invoice.customer_id, "king");
+        assert_eq!(imported.invoice.due_date, Some(invoice.get_due_date()));
+        assert_eq!(imported.invoice.title, "Product example");
+        assert_eq!(serde_yaml::to_string(&imported.invoice.products).unwrap(), serde_yaml::to_string(&invoice.products).unwrap());
+        assert!(imported.warnings.is_empty());
+        assert!(imported.unmapped.contains(&"SupplyChainTradeTransaction/ApplicableHeaderTradeAgreement/SellerTradeParty/Name".to_string()));
+        assert!(!imported.unmapped.iter().any(|path| path.contains("BuyerTradeParty") || path.contains("Summation")));
+    }
+
+    #[test]
+    fn read_exported_ubl_credit_note_of_a_new_customer() {
+        let settings = Settings::generate_simple_settings();
+        let invoice = Invoice::generate_simple_invoice_example();
+        let mut credit_note = invoice.create_credit_note(NaiveDate::from_ymd_opt(2015, 4, 1).unwrap(), invoice.products.clone());
+        credit_note.invoice_day_id = Some(InvoiceDayId::new("01").unwrap());
+        let xml = Ubl::new(&credit_note, &generate_customer(), &settings).to_string();
+
+        let imported = parse_e_invoice(&xml, &HashMap::new(), &settings.get_payment_terms(), &settings.get_default_vat_rate(), &settings.enterprise.siren).unwrap();
+
+        assert_eq!(imported.syntax, EInvoiceSyntax::Ubl);
+        assert_eq!(imported.invoice.kind, DocumentKind::CreditNote);
+        assert_eq!(imported.invoice.get_ref(), Some("AV2015040101".to_string()));
+        assert_eq!(imported.invoice.credited_invoice_ref, Some("2015031401".to_string()));
+        assert_eq!(imported.invoice.customer_id, "king_sarl");
+        assert_eq!(serde_yaml::to_string(&imported.invoice.products).unwrap(), serde_yaml::to_string(&credit_note.products).unwrap());
+        let new_customer = imported.new_customer.unwrap();
+        assert_eq!(new_customer.name, "King SARL");
+        assert_eq!(new_customer.postal, "75000");
+        assert_eq!(new_customer.siren, Some(Siren::from(987654321)));
+        assert_eq!(new_customer.tva, Some("FR44987654321".to_string()));
+        assert!(imported.warnings.is_empty());
+        assert!(imported.unmapped.contains(&"PaymentMeans/PaymentMeansCode".to_string()));
+    }
+
+    #[test]
+    fn report_unmapped_fields_and_differences() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Invoice xmlns="urn:oasis:names:specification:ubl:schema:xsd:Invoice-2" xmlns:cac="urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2" xmlns:cbc="urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2">
+  <cbc:ID>F-2023-12</cbc:ID>
+  <cbc:IssueDate>2023-12-05</cbc:IssueDate>
+  <cbc:InvoiceTypeCode>380</cbc:InvoiceTypeCode>
+  <cbc:DocumentCurrencyCode>EUR</cbc:DocumentCurrencyCode>
+  <cbc:OrderReference>PO-42</cbc:OrderReference>
+  <cac:AccountingCustomerParty>
+    <cac:Party>
+      <cbc:EndpointID schemeID="0009">98765432100017</cbc:EndpointID>
+      <cac:PostalAddress>
+        <cbc:StreetName>1 Main Street</cbc:StreetName>
+        <cac:Country><cbc:IdentificationCode>BE</cbc:IdentificationCode></cac:Country>
+      </cac:PostalAddress>
+      <cac:PartyLegalEntity><cbc:RegistrationName>Acme</cbc:RegistrationName></cac:PartyLegalEntity>
+    </cac:Party>
+  </cac:AccountingCustomerParty>
+  <cac:LegalMonetaryTotal>
+    <cbc:TaxInclusiveAmount currencyID="EUR">130.00</cbc:TaxInclusiveAmount>
+  </cac:LegalMonetaryTotal>
+  <cac:InvoiceLine>
+    <cbc:InvoicedQuantity unitCode="HUR">2</cbc:InvoicedQuantity>
+    <cac:Item>
+      <cbc:Description>Audit of the network</cbc:Description>
+      <cbc:Name>Audit</cbc:Name>
+      <cac:ClassifiedTaxCategory><cbc:ID>S</cbc:ID><cbc:Percent>20</cbc:Percent></cac:ClassifiedTaxCategory>
+    </cac:Item>
+    <cac:Price><cbc:PriceAmount currencyID="EUR">50.00</cbc:PriceAmount></cac:Price>
+  </cac:InvoiceLine>
+  <cac:InvoiceLine>
+    <cbc:InvoicedQuantity unitCode="C62">1</cbc:InvoicedQuantity>
+    <cac:Item>
+      <cbc:Description>Cable</cbc:Description>
+      <cbc:Name>Cable</cbc:Name>
+      <cac:ClassifiedTaxCategory><cbc:ID>S</cbc:ID><cbc:Percent>20</cbc:Percent></cac:ClassifiedTaxCategory>
+    </cac:Item>
+    <cac:Price><cbc:PriceAmount currencyID="EUR">10.00</cbc:PriceAmount></cac:Price>
+  </cac:InvoiceLine>
+</Invoice>"#;
+
+        let imported = parse_e_invoice(xml, &HashMap::new(), &PaymentTerms::default(), &VatRate::Normal, &Siren::from(123456789)).unwrap();
+
+        assert_eq!(imported.invoice.reference, Some("F-2023-12".to_string()));
+        assert_eq!(imported.invoice.due_date, Some(NaiveDate::from_ymd_opt(2024, 1, 4).unwrap()));
+        assert_eq!(imported.invoice.products[0].vat, None);
+        assert_eq!(imported.new_customer.unwrap().siren, Some(Siren::from(987654321)));
+        assert_eq!(imported.unmapped, vec!["OrderReference", "InvoiceLine/Item/Description (2 times)"]);
+        assert_eq!(
+            imported.warnings,
+            vec![
+                "Buyer country BE not kept, customers are in France",
+                "No seller identifier, the enterprise 123456789 is assumed to be the seller",
+                "Total of the document 130.00 differs from the imported total 132.00",
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_invoice_of_another_seller() {
+        let mut settings = Settings::generate_simple_settings();
+        let invoice = Invoice::generate_simple_invoice_example();
+        let xml = Ubl::new(&invoice, &generate_customer(), &settings).to_string();
+
+        settings.enterprise.siren = Siren::from(111222333);
+
+        assert_eq!(
+            parse_e_invoice(&xml, &HashMap::new(), &settings.get_payment_terms(), &settings.get_default_vat_rate(), &settings.enterprise.siren).err(),
+            Some("Seller 123456789 is not the enterprise of the settings (111222333)".to_string())
+        );
+    }
+
+    #[test]
+    fn reject_unsupported_documents() {
+        let parse = |xml: &str| parse_e_invoice(xml, &HashMap::new(), &PaymentTerms::default(), &VatRate::Normal, &Siren::from(123456789)).err();
+
+        assert_eq!(parse("<Order/>"), Some("Order is neither a CII nor a UBL document".to_string()));
+        assert_eq!(
+            parse("<Invoice><ID>1</ID><IssueDate>2024-01-01</IssueDate><InvoiceTypeCode>380</InvoiceTypeCode><DocumentCurrencyCode>USD</DocumentCurrencyCode><AccountingCustomerParty><Party><PartyName><Name>Acme</Name></PartyName></Party></AccountingCustomerParty></Invoice>"),
+            Some("Currency USD not supported, only EUR".to_string())
+        );
+        assert!(parse("<Invoice>").unwrap().starts_with("Invalid XML"));
+
+        let with_id = |id: &str| {
+            format!("<Invoice><ID>{}</ID><IssueDate>2024-01-01</IssueDate><InvoiceTypeCode>380</InvoiceTypeCode><DocumentCurrencyCode>EUR</DocumentCurrencyCode><AccountingCustomerParty><Party><PartyName><Name>Acme</Name></PartyName></Party></AccountingCustomerParty></Invoice>", id)
+        };
+        assert_eq!(
+            parse(&with_id("../../x")),
+            Some("Invalid ID: Reference ../../x is not a valid file name".to_string())
+        );
+        assert_eq!(
+            parse(&with_id("2024/001")),
+            Some("Invalid ID: Reference 2024/001 is not a valid file name".to_string())
+        );
+    }
+}
@@ -0,0 +1,473 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::amount::Amount;
+use crate::entities::customer::Customer;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::fixed_point::format_fixed_point;
+use crate::entities::invoice::Invoice;
+use crate::entities::quantity::Quantity;
+use crate::entities::settings::Settings;
+use crate::entities::vat_rate::VatRate;
+use crate::entities::xml_writer::XmlWriter;
+
+/// Profile of the Factur-X XML, each profile carries the data of the previous one
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FacturXProfile {
+    /// Header totals only, not an invoice for the tax administration
+    Minimum,
+    /// Product lines and VAT breakdown
+    #[default]
+    Basic,
+    /// Complete EN 16931 invoice with the seller contact
+    En16931,
+}
+
+impl FacturXProfile {
+    pub fn all_profiles() -> Vec<FacturXProfile> {
+        vec![FacturXProfile::Minimum, FacturXProfile::Basic, FacturXProfile::En16931]
+    }
+
+    /// Specification identifier (BT-24)
+    pub fn get_guideline(&self) -> &'static str {
+        match self {
+            FacturXProfile::Minimum => "urn:factur-x.eu:1p0:minimum",
+            FacturXProfile::Basic => "urn:cen.eu:en16931:2017#compliant#urn:factur-x.eu:1p0:basic",
+            FacturXProfile::En16931 => "urn:cen.eu:en16931:2017",
+        }
+    }
+
+    /// Conformance level declared in the XMP metadata of the PDF
+    pub fn get_conformance_level(&self) -> &'static str {
+        match self {
+            FacturXProfile::Minimum => "MINIMUM",
+            FacturXProfile::Basic => "BASIC",
+            FacturXProfile::En16931 => "EN 16931",
+        }
+    }
+
+    fn has_lines(&self) -> bool {
+        *self != FacturXProfile::Minimum
+    }
+}
+
+impl FromStr for FacturXProfile {
+    type Err = String;
+
+    fn from_str(profile: &str) -> Result<Self, Self::Err> {
+        FacturXProfile::all_profiles()
+            .into_iter()
+            .find(|candidate| candidate.to_string() == profile.to_lowercase().replace([' ', '_'], ""))
+            .ok_or_else(|| format!("Invalid Factur-X profile: {} (minimum, basic or en16931)", profile))
+    }
+}
+
+impl fmt::Display for FacturXProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FacturXProfile::Minimum => write!(f, "minimum"),
+            FacturXProfile::Basic => write!(f, "basic"),
+            FacturXProfile::En16931 => write!(f, "en16931"),
+        }
+    }
+}
+
+/// Code of the document type (UNTDID 1001)
+pub fn get_type_code(kind: DocumentKind) -> &'static str {
+    match kind {
+        DocumentKind::Invoice => "380",
+        DocumentKind::CreditNote => "381",
+        DocumentKind::Deposit => "386",
+    }
+}
+
+/// VAT category (UNTDID 5305) : standard, zero rated or exempt
+pub fn get_vat_category(vat_rate: &VatRate) -> &'static str {
+    match vat_rate {
+        VatRate::Zero => "Z",
+        VatRate::Exempt { .. } => "E",
+        _ => "S",
+    }
+}
+
+/// Price and quantity written for a line. The price can not be negative (BR-27), a discount
+/// or a deducted deposit gets a negative quantity instead and keeps its negative total
+pub fn get_line_price_and_quantity(price: Amount, quantity: Quantity) -> (Amount, Quantity) {
+    if price < Amount::ZERO {
+        (-price, -quantity)
+    } else {
+        (price, quantity)
+    }
+}
+
+pub fn format_vat_percent(vat_rate: &VatRate) -> String {
+    format_fixed_point(vat_rate.basis_points(), 2)
+}
+
+/// Cross Industry Invoice XML of an invoice, embedded in the PDF as `factur-x.xml`.
+/// Credit notes are written with positive amounts as their type already cancels the invoice.
+pub struct FacturX<'a> {
+    invoice: &'a Invoice,
+    customer: &'a Customer,
+    settings: &'a Settings,
+    profile: FacturXProfile,
+}
+
+impl<'a> FacturX<'a> {
+    pub fn new(invoice: &'a Invoice, customer: &'a Customer, settings: &'a Settings, profile: FacturXProfile) -> FacturX<'a> {
+        FacturX { invoice, customer, settings, profile }
+    }
+
+    pub fn get_profile(&self) -> FacturXProfile {
+        self.profile
+    }
+
+    fn sign(&self, amount: Amount) -> Amount {
+        match self.invoice.kind {
+            DocumentKind::CreditNote => -amount,
+            _ => amount,
+        }
+    }
+
+    fn signed(&self, amount: Amount) -> String {
+        self.sign(amount).to_string()
+    }
+
+    fn write_lines(&self, writer: &mut XmlWriter) {
+        let default_vat_rate = self.settings.get_default_vat_rate();
+
+        for (index, product) in self.invoice.products.iter().enumerate() {
+            let vat_rate = product.get_vat_rate(&default_vat_rate);
+            let (price, quantity) = get_line_price_and_quantity(self.sign(product.price), product.quantity);
+
+            writer
+                .open("ram:IncludedSupplyChainTradeLineItem", &[])
+                .open("ram:AssociatedDocumentLineDocument", &[])
+                .leaf("ram:LineID", &[], &(index + 1).to_string())
+                .close()
+                .open("ram:SpecifiedTradeProduct", &[])
+                .leaf("ram:Name", &[], &product.description)
+                .close()
+                .open("ram:SpecifiedLineTradeAgreement", &[])
+                .open("ram:NetPriceProductTradePrice", &[])
+                .leaf("ram:ChargeAmount", &[], &price.to_string())
+                .close()
+                .close()
+                .open("ram:SpecifiedLineTradeDelivery", &[])
+                .leaf("ram:BilledQuantity", &[("unitCode", "C62")], &quantity.to_string())
+                .close()
+                .open("ram:SpecifiedLineTradeSettlement", &[])
+                .open("ram:ApplicableTradeTax", &[])
+                .leaf("ram:TypeCode", &[], "VAT")
+                .leaf("ram:CategoryCode", &[], get_vat_category(vat_rate))
+                .leaf("ram:RateApplicablePercent", &[], &format_vat_percent(vat_rate));
+            writer
+                .close()
+                .open("ram:SpecifiedTradeSettlementLineMonetarySummation", &[])
+                .leaf("ram:LineTotalAmount", &[], &self.signed(product.get_total_price()))
+                .close()
+                .close()
+                .close();
+        }
+    }
+
+    fn write_seller(&self, writer: &mut XmlWriter) {
+        let enterprise = &self.settings.enterprise;
+
+        writer
+            .open("ram:SellerTradeParty", &[])
+            .leaf("ram:Name", &[], &enterprise.name)
+            .open("ram:SpecifiedLegalOrganization", &[])
+            .leaf("ram:ID", &[("schemeID", "0002")], &enterprise.siren.to_string())
+            .close();
+        if self.profile == FacturXProfile::En16931 {
+            writer
+                .open("ram:DefinedTradeContact", &[])
+                .leaf("ram:PersonName", &[], &enterprise.title)
+                .open("ram:TelephoneUniversalCommunication", &[])
+                .leaf("ram:CompleteNumber", &[], &enterprise.phone)
+                .close()
+                .open("ram:EmailURIUniversalCommunication", &[])
+                .leaf("ram:URIID", &[], &enterprise.email)
+                .close()
+                .close();
+        }
+        write_address(writer, self.profile, &enterprise.address, &enterprise.postal, &enterprise.city);
+        if self.profile == FacturXProfile::En16931 {
+            writer
+                .open("ram:URIUniversalCommunication", &[])
+                .leaf("ram:URIID", &[("schemeID", "EM")], &enterprise.email)
+                .close();
+        }
+        if !enterprise.tva.is_empty() {
+            writer
+                .open("ram:SpecifiedTaxRegistration", &[])
+                .leaf("ram:ID", &[("schemeID", "VA")], &enterprise.tva)
+                .close();
+        }
+        writer.close();
+    }
+
+    fn write_buyer(&self, writer: &mut XmlWriter) {
+        writer
+            .open("ram:BuyerTradeParty", &[])
+            .leaf("ram:Name", &[], &self.customer.name);
+        if let Some(siren) = &self.customer.siren {
+            writer
+                .open("ram:SpecifiedLegalOrganization", &[])
+                .leaf("ram:ID", &[("schemeID", "0002")], &siren.to_string())
+                .close();
+        }
+        write_address(writer, self.profile, &self.customer.address, &self.customer.postal, &self.customer.city);
+        if let Some(tva) = self.customer.tva.as_ref().filter(|_| self.profile.has_lines()) {
+            writer
+                .open("ram:SpecifiedTaxRegistration", &[])
+                .leaf("ram:ID", &[("schemeID", "VA")], tva)
+                .close();
+        }
+        writer.close();
+    }
+
+    fn write_settlement(&self, writer: &mut XmlWriter) {
+        let totals = self.invoice.get_totals(&self.settings.get_default_vat_rate());
+
+        writer
+            .open("ram:ApplicableHeaderTradeSettlement", &[])
+            .leaf("ram:InvoiceCurrencyCode", &[], "EUR");
+
+        if self.profile.has_lines() {
+            for vat_line in totals.vat_lines.iter() {
+                writer
+                    .open("ram:ApplicableTradeTax", &[])
+                    .leaf("ram:CalculatedAmount", &[], &self.signed(vat_line.vat))
+                    .leaf("ram:TypeCode", &[], "VAT");
+                if let Some(reason) = vat_line.rate.get_exemption_reason() {
+                    writer.leaf("ram:ExemptionReason", &[], reason);
+                }
+                writer
+                    .leaf("ram:BasisAmount", &[], &self.signed(vat_line.base))
+                    .leaf("ram:CategoryCode", &[], get_vat_category(&vat_line.rate))
+                    .leaf("ram:RateApplicablePercent", &[], &format_vat_percent(&vat_line.rate))
+                    .close();
+            }
+
+            writer
+                .open("ram:SpecifiedTradePaymentTerms", &[])
+                .open("ram:DueDateDateTime", &[]);
+            write_date(writer, self.invoice.get_due_date());
+            writer.close().close();
+        }
+
+        writer.open("ram:SpecifiedTradeSettlementHeaderMonetarySummation", &[]);
+        if self.profile.has_lines() {
+            writer.leaf("ram:LineTotalAmount", &[], &self.signed(totals.total_ht));
+        }
+        writer
+            .leaf("ram:TaxBasisTotalAmount", &[], &self.signed(totals.total_ht))
+            .leaf("ram:TaxTotalAmount", &[("currencyID", "EUR")], &self.signed(totals.total_vat))
+            .leaf("ram:GrandTotalAmount", &[], &self.signed(totals.total_ttc))
+            .leaf("ram:DuePayableAmount", &[], &self.signed(totals.total_ttc))
+            .close();
+
+        if let Some(credited_invoice_ref) = self.invoice.credited_invoice_ref.as_ref().filter(|_| self.profile.has_lines()) {
+            writer
+                .open("ram:InvoiceReferencedDocument", &[])
+                .leaf("ram:IssuerAssignedID", &[], credited_invoice_ref)
+                .close();
+        }
+
+        writer.close();
+    }
+}
+
+fn write_date(writer: &mut XmlWriter, date: NaiveDate) {
+    writer.leaf("udt:DateTimeString", &[("format", "102")], &date.format("%Y%m%d").to_string());
+}
+
+/// The MINIMUM profile only has the country of the parties
+fn write_address(writer: &mut XmlWriter, profile: FacturXProfile, address: &str, postal: &str, city: &str) {
+    writer.open("ram:PostalTradeAddress", &[]);
+    if profile.has_lines() {
+        writer
+            .leaf("ram:PostcodeCode", &[], postal)
+            .leaf("ram:LineOne", &[], address)
+            .leaf("ram:CityName", &[], city);
+    }
+    writer.leaf("ram:CountryID", &[], "FR").close();
+}
+
+impl fmt::Display for FacturX<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut writer = XmlWriter::new();
+
+        writer
+            .open(
+                "rsm:CrossIndustryInvoice",
+                &[
+                    ("xmlns:rsm", "urn:un:unece:uncefact:data:standard:CrossIndustryInvoice:100"),
+                    ("xmlns:qdt", "urn:un:unece:uncefact:data:standard:QualifiedDataType:100"),
+                    ("xmlns:ram", "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:100"),
+                    ("xmlns:udt", "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:100"),
+                ],
+            )
+            .open("rsm:ExchangedDocumentContext", &[])
+            .open("ram:GuidelineSpecifiedDocumentContextParameter", &[])
+            .leaf("ram:ID", &[], self.profile.get_guideline())
+            .close()
+            .close()
+            .open("rsm:ExchangedDocument", &[])
+            .leaf("ram:ID", &[], &self.invoice.get_ref().unwrap_or_default())
+            .leaf("ram:TypeCode", &[], get_type_code(self.invoice.kind))
+            .open("ram:IssueDateTime", &[]);
+        write_date(&mut writer, self.invoice.date);
+        writer.close();
+        if self.profile.has_lines() && !self.settings.law_rules.is_empty() {
+            writer
+                .open("ram:IncludedNote", &[])
+                .leaf("ram:Content", &[], &self.settings.law_rules)
+                .leaf("ram:SubjectCode", &[], "AAI")
+                .close();
+        }
+        writer.close().open("rsm:SupplyChainTradeTransaction", &[]);
+
+        if self.profile.has_lines() {
+            self.write_lines(&mut writer);
+        }
+
+        writer.open("ram:ApplicableHeaderTradeAgreement", &[]);
+        self.write_seller(&mut writer);
+        self.write_buyer(&mut writer);
+        writer
+            .close()
+            .open("ram:ApplicableHeaderTradeDelivery", &[])
+            .close();
+
+        self.write_settlement(&mut writer);
+
+        write!(f, "{}", writer.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::product::Product;
+    use crate::entities::quantity::Quantity;
+    use crate::entities::siren::Siren;
+
+    use super::*;
+
+    fn generate_invoice() -> Invoice {
+        let mut invoice = Invoice::generate_simple_invoice_example();
+        invoice.products.push(Product {
+            description: "Livres & revues".to_string(),
+            quantity: Quantity::from(2),
+            price: Amount::new("10").unwrap(),
+            vat: Some(VatRate::Reduced),
+            activity: None,
+        });
+        invoice
+    }
+
+    #[test]
+    fn basic_profile_with_lines_and_vat_breakdown() {
+        let mut settings = Settings::generate_simple_settings();
+        settings.default_vat_rate = Some(VatRate::Normal);
+        settings.enterprise.tva = "FR12123456789".to_string();
+        let invoice = generate_invoice();
+        let mut customer = Customer::simple_customer();
+        customer.siren = Some(Siren::from(987654321));
+        customer.tva = Some("FR45987654321".to_string());
+
+        let xml = FacturX::new(&invoice, &customer, &settings, FacturXProfile::Basic).to_string();
+
+        assert!(xml.contains("<ram:ID>urn:cen.eu:en16931:2017#compliant#urn:factur-x.eu:1p0:basic</ram:ID>"));
+        assert!(xml.contains("<ram:ID>2015031401</ram:ID>\n    <ram:TypeCode>380</ram:TypeCode>"));
+        assert!(xml.contains("<udt:DateTimeString format=\"102\">20150314</udt:DateTimeString>"));
+        assert!(xml.contains("<ram:Name>Livres &amp; revues</ram:Name>"));
+        assert!(xml.contains("<ram:BilledQuantity unitCode=\"C62\">2</ram:BilledQuantity>"));
+        assert!(xml.contains("<ram:ID schemeID=\"0002\">123456789</ram:ID>"));
+        assert!(xml.contains("<ram:ID schemeID=\"VA\">FR12123456789</ram:ID>"));
+        assert!(xml.contains("<ram:ID schemeID=\"0002\">987654321</ram:ID>"));
+        assert!(xml.contains("<ram:ID schemeID=\"VA\">FR45987654321</ram:ID>"));
+        assert!(xml.contains(&(
+            "<ram:CalculatedAmount>70.00</ram:CalculatedAmount>\n".to_owned()
+                + "        <ram:TypeCode>VAT</ram:TypeCode>\n"
+                + "        <ram:BasisAmount>350.00</ram:BasisAmount>\n"
+                + "        <ram:CategoryCode>S</ram:CategoryCode>\n"
+                + "        <ram:RateApplicablePercent>20.00</ram:RateApplicablePercent>")
+        ));
+        assert!(xml.contains("<ram:RateApplicablePercent>5.50</ram:RateApplicablePercent>"));
+        assert!(xml.contains("<ram:LineTotalAmount>370.00</ram:LineTotalAmount>"));
+        assert!(xml.contains("<ram:TaxTotalAmount currencyID=\"EUR\">71.10</ram:TaxTotalAmount>"));
+        assert!(xml.contains("<ram:GrandTotalAmount>441.10</ram:GrandTotalAmount>"));
+        assert!(!xml.contains("DefinedTradeContact"));
+        assert!(xml.ends_with("</rsm:CrossIndustryInvoice>\n"));
+    }
+
+    #[test]
+    fn minimum_profile_and_exempt_credit_note() {
+        let settings = Settings::generate_simple_settings();
+        let invoice = generate_invoice();
+        let customer = Customer::simple_customer();
+        let credit_note = invoice.create_credit_note(NaiveDate::from_ymd_opt(2015, 4, 1).unwrap(), invoice.products[..1].to_vec());
+
+        let minimum = FacturX::new(&invoice, &customer, &settings, FacturXProfile::Minimum).to_string();
+        assert!(!minimum.contains("IncludedSupplyChainTradeLineItem"));
+        assert!(!minimum.contains("ApplicableTradeTax"));
+        assert!(!minimum.contains("CityName"));
+        assert!(minimum.contains("<ram:CountryID>FR</ram:CountryID>"));
+        assert!(minimum.contains("<ram:TaxBasisTotalAmount>370.00</ram:TaxBasisTotalAmount>"));
+
+        let xml = FacturX::new(&credit_note, &customer, &settings, FacturXProfile::En16931).to_string();
+        assert!(xml.contains("<ram:TypeCode>381</ram:TypeCode>"));
+        assert!(xml.contains("<ram:ExemptionReason>TVA non applicable, art. 293 B du CGI</ram:ExemptionReason>"));
+        assert!(xml.contains("<ram:CategoryCode>E</ram:CategoryCode>"));
+        // BR-E-05: the rate of an exempt line is 0
+        assert!(xml.contains("<ram:CategoryCode>E</ram:CategoryCode>\n          <ram:RateApplicablePercent>0.00</ram:RateApplicablePercent>"));
+        assert!(xml.contains("<ram:CategoryCode>E</ram:CategoryCode>\n        <ram:RateApplicablePercent>0.00</ram:RateApplicablePercent>"));
+        assert!(xml.contains("<ram:ChargeAmount>350.00</ram:ChargeAmount>"));
+        assert!(xml.contains("<ram:GrandTotalAmount>350.00</ram:GrandTotalAmount>"));
+        assert!(xml.contains("<ram:IssuerAssignedID>2015031401</ram:IssuerAssignedID>"));
+        assert!(xml.contains("<ram:URIID schemeID=\"EM\">contact@example.com</ram:URIID>"));
+    }
+
+    #[test]
+    fn discount_line_has_a_negative_quantity() {
+        let settings = Settings::generate_simple_settings();
+        let mut invoice = Invoice::generate_simple_invoice_example();
+        invoice.products.push(Product {
+            description: "Remise".to_string(),
+            quantity: Quantity::from(2),
+            price: Amount::new("-25").unwrap(),
+            vat: None,
+            activity: None,
+        });
+        let customer = Customer::simple_customer();
+        let credit_note = invoice.create_credit_note(NaiveDate::from_ymd_opt(2015, 4, 1).unwrap(), invoice.products.clone());
+
+        // BR-27: the net price is never negative, the sign goes on the quantity
+        let xml = FacturX::new(&invoice, &customer, &settings, FacturXProfile::Basic).to_string();
+        assert!(!xml.contains("<ram:ChargeAmount>-"));
+        assert!(xml.contains("<ram:ChargeAmount>25.00</ram:ChargeAmount>"));
+        assert!(xml.contains("<ram:BilledQuantity unitCode=\"C62\">-2</ram:BilledQuantity>"));
+        assert!(xml.contains("<ram:LineTotalAmount>-50.00</ram:LineTotalAmount>"));
+        assert!(xml.contains("<ram:GrandTotalAmount>300.00</ram:GrandTotalAmount>"));
+
+        let xml = FacturX::new(&credit_note, &customer, &settings, FacturXProfile::Basic).to_string();
+        assert!(!xml.contains("<ram:ChargeAmount>-"));
+        assert!(xml.contains("<ram:BilledQuantity unitCode=\"C62\">-2</ram:BilledQuantity>"));
+        assert!(xml.contains("<ram:GrandTotalAmount>300.00</ram:GrandTotalAmount>"));
+    }
+
+    #[test]
+    fn profile_from_str() {
+        assert_eq!(FacturXProfile::from_str("EN 16931"), Ok(FacturXProfile::En16931));
+        assert_eq!(FacturXProfile::from_str("basic"), Ok(FacturXProfile::Basic));
+        assert!(FacturXProfile::from_str("extended").is_err());
+        assert_eq!(serde_yaml::to_string(&FacturXProfile::En16931).unwrap(), "en16931\n");
+    }
+}
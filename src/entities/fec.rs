@@ -0,0 +1,357 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::entities::accounting_settings::AccountingSettings;
+use crate::entities::activity::Activity;
+use crate::entities::amount::Amount;
+use crate::entities::customer::Customer;
+use crate::entities::invoice::Invoice;
+use crate::entities::payment::Payment;
+use crate::entities::vat_rate::VatRate;
+
+/// Line of the Fichier des Écritures Comptables
+#[derive(PartialEq, Clone, Debug)]
+pub struct FecEntry {
+    pub journal_code: String,
+    pub journal_label: String,
+    /// Same number for all the lines of a transaction
+    pub entry_number: usize,
+    pub date: NaiveDate,
+    pub account: String,
+    pub account_label: String,
+    pub aux_account: String,
+    pub aux_label: String,
+    pub piece_ref: String,
+    pub piece_date: NaiveDate,
+    pub label: String,
+    pub debit: Amount,
+    pub credit: Amount,
+}
+
+/// Sales and bank entries of a year: invoices and credit notes in the sales journal,
+/// payments in the bank journal. Every transaction is balanced.
+#[derive(PartialEq, Debug)]
+pub struct Fec {
+    pub entries: Vec<FecEntry>,
+}
+
+/// Where the lines of a transaction go, the account of the customer is auxiliary
+struct AccountLine<'a> {
+    account: &'a str,
+    account_label: &'a str,
+    aux: Option<(&'a str, &'a str)>,
+    /// Debit when positive, credit when negative
+    amount: Amount,
+}
+
+fn format_amount(amount: Amount) -> String {
+    amount.to_string().replace('.', ",")
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+impl Fec {
+    pub const HEADER: [&'static str; 18] = [
+        "JournalCode",
+        "JournalLib",
+        "EcritureNum",
+        "EcritureDate",
+        "CompteNum",
+        "CompteLib",
+        "CompAuxNum",
+        "CompAuxLib",
+        "PieceRef",
+        "PieceDate",
+        "EcritureLib",
+        "Debit",
+        "Credit",
+        "EcritureLet",
+        "DateLet",
+        "ValidDate",
+        "Montantdevise",
+        "Idevise",
+    ];
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        year: i32,
+        all_invoices: &[Invoice],
+        all_payments: &HashMap<String, Vec<Payment>>,
+        customers: &HashMap<String, Customer>,
+        default_vat_rate: &VatRate,
+        default_activity: Activity,
+        accounting: &AccountingSettings,
+    ) -> Self {
+        let mut transactions: Vec<Vec<FecEntry>> = vec![];
+
+        let get_customer = |customer_id: &str| -> (String, String) {
+            match customers.get(customer_id) {
+                Some(customer) => {
+                    (customer.get_account(customer_id, &accounting.customer_account), customer.name.to_owned())
+                }
+                None => (
+                    accounting.customer_account.to_owned()
+                        + &customer_id.chars().filter(char::is_ascii_alphanumeric).collect::<String>().to_uppercase(),
+                    customer_id.to_owned(),
+                ),
+            }
+        };
+
+        for invoice in all_invoices.iter() {
+            let invoice_ref = invoice.get_ref().unwrap_or_default();
+            let (customer_account, customer_name) = get_customer(&invoice.customer_id);
+            let totals = invoice.get_totals(default_vat_rate);
+
+            if invoice.date.year() == year {
+                let mut activity_totals: BTreeMap<Activity, Amount> = BTreeMap::new();
+                for product in invoice.products.iter() {
+                    *activity_totals.entry(product.get_activity(default_activity)).or_default() +=
+                        product.get_total_price();
+                }
+
+                let mut lines = vec![AccountLine {
+                    account: &accounting.customer_account,
+                    account_label: "Clients",
+                    aux: Some((&customer_account, &customer_name)),
+                    amount: totals.total_ttc,
+                }];
+                lines.extend(activity_totals.iter().map(|(activity, total)| AccountLine {
+                    account: accounting.get_revenue_account(activity),
+                    account_label: match activity {
+                        Activity::Sale => "Ventes de marchandises",
+                        Activity::ServiceBic | Activity::ServiceBnc => "Prestations de services",
+                    },
+                    aux: None,
+                    amount: -*total,
+                }));
+                if totals.total_vat != Amount::ZERO {
+                    lines.push(AccountLine {
+                        account: &accounting.vat_account,
+                        account_label: "TVA collectée",
+                        aux: None,
+                        amount: -totals.total_vat,
+                    });
+                }
+
+                transactions.push(Self::create_transaction(
+                    (&accounting.sales_journal, "Ventes"),
+                    invoice.date,
+                    (&invoice_ref, invoice.date),
+                    &format!("{} {} {}", invoice.kind.get_title(), invoice_ref, customer_name),
+                    lines,
+                ));
+            }
+
+            for payment in all_payments.get(&invoice_ref).into_iter().flatten() {
+                if payment.date.year() != year {
+                    continue;
+                }
+
+                transactions.push(Self::create_transaction(
+                    (&accounting.bank_journal, "Banque"),
+                    payment.date,
+                    (&invoice_ref, invoice.date),
+                    &format!("Règlement {} {}", invoice_ref, customer_name),
+                    vec![
+                        AccountLine {
+                            account: &accounting.bank_account,
+                            account_label: "Banque",
+                            aux: None,
+                            amount: payment.amount,
+                        },
+                        AccountLine {
+                            account: &accounting.customer_account,
+                            account_label: "Clients",
+                            aux: Some((&customer_account, &customer_name)),
+                            amount: -payment.amount,
+                        },
+                    ],
+                ));
+            }
+        }
+
+        transactions.sort_by(|a, b| {
+            (a[0].date, &a[0].journal_code, &a[0].piece_ref).cmp(&(b[0].date, &b[0].journal_code, &b[0].piece_ref))
+        });
+
+        Fec {
+            entries: transactions
+                .into_iter()
+                .enumerate()
+                .flat_map(|(index, transaction)| {
+                    transaction.into_iter().map(move |entry| FecEntry { entry_number: index + 1, ..entry })
+                })
+                .collect(),
+        }
+    }
+
+    fn create_transaction(
+        (journal_code, journal_label): (&str, &str),
+        date: NaiveDate,
+        (piece_ref, piece_date): (&str, NaiveDate),
+        label: &str,
+        lines: Vec<AccountLine>,
+    ) -> Vec<FecEntry> {
+        lines
+            .into_iter()
+            .map(|line| {
+                let (aux_account, aux_label) = line.aux.unwrap_or_default();
+
+                FecEntry {
+                    journal_code: journal_code.to_owned(),
+                    journal_label: journal_label.to_owned(),
+                    entry_number: 0,
+                    date,
+                    account: line.account.to_owned(),
+                    account_label: line.account_label.to_owned(),
+                    aux_account: aux_account.to_owned(),
+                    aux_label: aux_label.to_owned(),
+                    piece_ref: piece_ref.to_owned(),
+                    piece_date,
+                    label: label.to_owned(),
+                    debit: line.amount.max(Amount::ZERO),
+                    credit: (-line.amount).max(Amount::ZERO),
+                }
+            })
+            .collect()
+    }
+
+    /// Name required by the administration, `SirenFECYYYYMMDD.txt` with the closing date
+    pub fn get_filename(siren: &str, year: i32) -> String {
+        format!("{}FEC{}1231.txt", siren, year)
+    }
+}
+
+/// Tab separated lines with the header, as accepted by the administration
+impl fmt::Display for Fec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", Fec::HEADER.join("\t"))?;
+
+        for entry in self.entries.iter() {
+            let fields = [
+                entry.journal_code.to_owned(),
+                entry.journal_label.to_owned(),
+                entry.entry_number.to_string(),
+                format_date(entry.date),
+                entry.account.to_owned(),
+                entry.account_label.to_owned(),
+                entry.aux_account.to_owned(),
+                entry.aux_label.to_owned(),
+                entry.piece_ref.to_owned(),
+                format_date(entry.piece_date),
+                entry.label.replace('\t', " "),
+                format_amount(entry.debit),
+                format_amount(entry.credit),
+                String::new(),
+                String::new(),
+                format_date(entry.date),
+                String::new(),
+                String::new(),
+            ];
+
+            writeln!(f, "{}", fields.join("\t"))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::payment::PaymentMethod;
+    use crate::entities::product::Product;
+
+    use super::*;
+
+    fn generate_fec() -> Fec {
+        let mut invoice = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2024, 1, 20).unwrap(),
+        );
+        invoice.products = vec![
+            Product::from_str("Development;1;1000").unwrap(),
+            Product::from_str("Router;1;100;;sale").unwrap(),
+        ];
+        let mut credit_note = invoice.create_credit_note(
+            NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            vec![Product::from_str("Router;1;100;;sale").unwrap()],
+        );
+        credit_note.invoice_day_id = Some(InvoiceDayId::new("01").unwrap());
+        let mut last_year = invoice.clone();
+        last_year.date = NaiveDate::from_ymd_opt(2023, 12, 20).unwrap();
+
+        let all_payments = HashMap::from([(
+            "2024012001".to_string(),
+            vec![Payment {
+                date: NaiveDate::from_ymd_opt(2024, 2, 15).unwrap(),
+                amount: Amount::new("1200").unwrap(),
+                method: PaymentMethod::Transfer,
+                reference: None,
+            }],
+        )]);
+
+        Fec::new(
+            2024,
+            &[invoice, credit_note, last_year],
+            &all_payments,
+            &HashMap::from([("king".to_string(), Customer::simple_customer())]),
+            &VatRate::Normal,
+            Activity::ServiceBnc,
+            &AccountingSettings::default(),
+        )
+    }
+
+    #[test]
+    fn test_fec_entries_are_balanced() {
+        let fec = generate_fec();
+
+        assert_eq!(fec.entries.len(), 4 + 3 + 2);
+        for entry_number in 1..=3 {
+            let transaction: Vec<&FecEntry> =
+                fec.entries.iter().filter(|entry| entry.entry_number == entry_number).collect();
+            let debit: Amount = transaction.iter().map(|entry| entry.debit).sum();
+            let credit: Amount = transaction.iter().map(|entry| entry.credit).sum();
+
+            assert!(!transaction.is_empty());
+            assert_eq!(debit, credit);
+        }
+
+        assert_eq!(fec.entries[0].account, "411");
+        assert_eq!(fec.entries[0].aux_account, "411KING");
+        assert_eq!(fec.entries[0].debit, Amount::new("1320").unwrap());
+        assert_eq!(fec.entries[1].account, "707");
+        assert_eq!(fec.entries[2].account, "706");
+        assert_eq!(fec.entries[2].credit, Amount::new("1000").unwrap());
+        assert_eq!(fec.entries[3].account, "44571");
+        assert_eq!(fec.entries[3].credit, Amount::new("220").unwrap());
+
+        // The credit note reverses the sale of goods
+        assert_eq!(fec.entries[4].credit, Amount::new("120").unwrap());
+        assert_eq!(fec.entries[5].debit, Amount::new("100").unwrap());
+
+        assert_eq!(fec.entries[7].journal_code, "BQ");
+        assert_eq!(fec.entries[7].account, "512");
+    }
+
+    #[test]
+    fn test_fec_to_string() {
+        let fec = generate_fec().to_string();
+        let lines: Vec<&str> = fec.lines().collect();
+
+        assert_eq!(lines.len(), 10);
+        assert!(lines[0].starts_with("JournalCode\tJournalLib\tEcritureNum"));
+        assert_eq!(
+            lines[1],
+            "VT\tVentes\t1\t20240120\t411\tClients\t411KING\tKing SARL\t2024012001\t20240120\t\
+             Facture 2024012001 King SARL\t1320,00\t0,00\t\t\t20240120\t\t"
+        );
+        assert_eq!(Fec::get_filename("123456789", 2024), "123456789FEC20241231.txt");
+    }
+}
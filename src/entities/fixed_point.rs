@@ -0,0 +1,123 @@
+/// Parse a decimal string into an integer scaled by `10^scale`.
+///
+/// Extra decimals are rounded half-up (away from zero), so `"1.005"` with a scale of 2
+/// gives `101`.
+pub(crate) fn parse_fixed_point(input: &str, scale: u32) -> Result<i64, String> {
+    let input = input.trim();
+
+    let (negative, digits) = match input.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, input.strip_prefix('+').unwrap_or(input)),
+    };
+
+    let (integer_part, decimal_part) = match digits.split_once(['.', ',']) {
+        Some((integer_part, decimal_part)) => (integer_part, decimal_part),
+        None => (digits, ""),
+    };
+
+    if (integer_part.is_empty() && decimal_part.is_empty())
+        || !integer_part.chars().all(|c| c.is_ascii_digit())
+        || !decimal_part.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("Invalid number: {}", input));
+    }
+
+    let integer_value = if integer_part.is_empty() {
+        0
+    } else {
+        integer_part.parse::<i64>().map_err(|_| format!("Number too large: {}", input))?
+    };
+
+    let mut decimal_value: i64 = 0;
+    for position in 0..scale as usize {
+        let digit = decimal_part.as_bytes().get(position).map_or(0, |c| (c - b'0') as i64);
+        decimal_value = decimal_value * 10 + digit;
+    }
+
+    if decimal_part.as_bytes().get(scale as usize).is_some_and(|c| *c >= b'5') {
+        decimal_value += 1;
+    }
+
+    let value = integer_value
+        .checked_mul(10_i64.pow(scale))
+        .and_then(|value| value.checked_add(decimal_value))
+        .ok_or_else(|| format!("Number too large: {}", input))?;
+
+    Ok(if negative { -value } else { value })
+}
+
+/// Format an integer scaled by `10^scale` with exactly `scale` decimals.
+pub(crate) fn format_fixed_point(value: i64, scale: u32) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let divider = 10_u64.pow(scale);
+    let absolute_value = value.unsigned_abs();
+
+    if scale == 0 {
+        return format!("{}{}", sign, absolute_value);
+    }
+
+    format!(
+        "{}{}.{:0width$}",
+        sign,
+        absolute_value / divider,
+        absolute_value % divider,
+        width = scale as usize
+    )
+}
+
+/// Divide with half-up (away from zero) rounding.
+pub(crate) fn divide_half_up(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+
+    if remainder.abs() * 2 >= denominator.abs() {
+        if (numerator < 0) != (denominator < 0) {
+            quotient - 1
+        } else {
+            quotient + 1
+        }
+    } else {
+        quotient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fixed_point() {
+        assert_eq!(parse_fixed_point("350", 2), Ok(35_000));
+        assert_eq!(parse_fixed_point("350.5", 2), Ok(35_050));
+        assert_eq!(parse_fixed_point("1049,99", 2), Ok(104_999));
+        assert_eq!(parse_fixed_point("-12.5", 2), Ok(-1_250));
+        assert_eq!(parse_fixed_point(".5", 2), Ok(50));
+        assert_eq!(parse_fixed_point("1.005", 2), Ok(101));
+        assert_eq!(parse_fixed_point("1.004", 2), Ok(100));
+        assert_eq!(parse_fixed_point("-1.005", 2), Ok(-101));
+        assert_eq!(parse_fixed_point("1.5", 0), Ok(2));
+
+        assert!(parse_fixed_point("", 2).is_err());
+        assert!(parse_fixed_point(".", 2).is_err());
+        assert!(parse_fixed_point("12a", 2).is_err());
+        assert!(parse_fixed_point("1.2.3", 2).is_err());
+    }
+
+    #[test]
+    fn test_format_fixed_point() {
+        assert_eq!(format_fixed_point(35_000, 2), "350.00");
+        assert_eq!(format_fixed_point(5, 2), "0.05");
+        assert_eq!(format_fixed_point(-1_250, 2), "-12.50");
+        assert_eq!(format_fixed_point(1_500, 3), "1.500");
+        assert_eq!(format_fixed_point(12, 0), "12");
+    }
+
+    #[test]
+    fn test_divide_half_up() {
+        assert_eq!(divide_half_up(15, 10), 2);
+        assert_eq!(divide_half_up(14, 10), 1);
+        assert_eq!(divide_half_up(-15, 10), -2);
+        assert_eq!(divide_half_up(-14, 10), -1);
+        assert_eq!(divide_half_up(10, 10), 1);
+    }
+}
@@ -3,8 +3,18 @@ use std::fmt;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
-use crate::entities::invoice_date::{deser_invoice_date, ser_invoice_date};
+use crate::entities::amount::Amount;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice_date::{
+    deser_invoice_date, deser_option_invoice_date, ser_invoice_date, ser_option_invoice_date,
+};
+use crate::entities::invoice_totals::InvoiceTotals;
+use crate::entities::payment::Payment;
+use crate::entities::payment_status::PaymentStatus;
+use crate::entities::payment_terms::PaymentTerms;
 use crate::entities::product::Product;
+use crate::entities::quantity::Quantity;
+use crate::entities::vat_rate::VatRate;
 
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct InvoiceDayId(String);
@@ -13,7 +23,7 @@ impl InvoiceDayId {
     pub fn new(id: &str) -> Result<Self, String> {
         if id.len() <= 2 && id.chars().all(char::is_numeric) {
             let id_num = id.parse::<u8>().map_err(|_| "Invalid number".to_string())?;
-            if id_num >= 1 && id_num <= 99 {
+            if (1..=99).contains(&id_num) {
                 if id.len() == 1 {
                     return Ok(InvoiceDayId("0".to_string() + id));
                 } else {
@@ -44,31 +54,181 @@ pub struct Invoice {
     deserialize_with = "deser_invoice_date"
     )]
     pub date: NaiveDate,
+    /// Computed from the payment terms when the invoice is created
+    #[serde(
+    default,
+    skip_serializing_if = "Option::is_none",
+    serialize_with = "ser_option_invoice_date",
+    deserialize_with = "deser_option_invoice_date"
+    )]
+    pub due_date: Option<NaiveDate>,
     pub customer_id: String,
     pub title: String,
     pub invoice_day_id: Option<InvoiceDayId>,
     pub products: Vec<Product>,
+    #[serde(default, skip_serializing_if = "DocumentKind::is_invoice")]
+    pub kind: DocumentKind,
+    /// Reference of the invoice cancelled by a credit note
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credited_invoice_ref: Option<String>,
+    /// Reference of the quote billed by a deposit or a final invoice
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_ref: Option<String>,
+    /// Reference given by the numbering policy, other invoices use their date and day id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Position of the invoice in the series of the numbering policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u32>,
 }
 
 impl Invoice {
     pub fn get_ref(&self) -> Option<String> {
-        match &self.invoice_day_id {
-            Some(invoice_day_id) => Some(
-                self.date.format("%Y%m%d").to_string() + &(invoice_day_id.to_string()),
-            ),
-            None => None,
+        if self.reference.is_some() {
+            return self.reference.clone();
         }
+
+        self.invoice_day_id.as_ref().map(|invoice_day_id| {
+            self.kind.get_prefix().to_string()
+                + &self.date.format("%Y%m%d").to_string()
+                + &(invoice_day_id.to_string())
+        })
+    }
+    pub fn get_total_price(&self) -> Amount {
+        self.products.iter().map(Product::get_total_price).sum()
     }
-    pub fn get_total_price(&self) -> f32 {
+
+    /// HT, VAT by rate and TTC totals. Lines without rate use `default_vat_rate`.
+    pub fn get_totals(&self, default_vat_rate: &VatRate) -> InvoiceTotals {
+        InvoiceTotals::from_products(&self.products, default_vat_rate)
+    }
+
+    /// Invoice with the rate of every line set, the lines without rate take `default_vat_rate`.
+    /// Issued invoices keep their rates when the settings change.
+    pub fn resolve_vat_rates(self, default_vat_rate: &VatRate) -> Invoice {
+        Invoice {
+            products: self
+                .products
+                .into_iter()
+                .map(|product| Product {
+                    vat: Some(product.get_vat_rate(default_vat_rate).clone()),
+                    ..product
+                })
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Invoices created before payment terms existed use the default terms
+    pub fn get_due_date(&self) -> NaiveDate {
+        self.due_date
+            .unwrap_or_else(|| PaymentTerms::default().get_due_date(self.date))
+    }
+
+    /// Status of the invoice given the amount due (TTC) and the payments received until `today`
+    pub fn get_payment_status(
+        &self,
+        total_due: Amount,
+        payments: &[Payment],
+        today: NaiveDate,
+    ) -> PaymentStatus {
+        let paid: Amount = payments.iter().map(|payment| payment.amount).sum();
+
+        PaymentStatus::new(total_due, paid, self.get_due_date(), today)
+    }
+
+    /// Credit note cancelling the given products of this invoice, their prices are negated
+    pub fn create_credit_note(&self, date: NaiveDate, products: Vec<Product>) -> Invoice {
+        Invoice {
+            date,
+            due_date: None,
+            customer_id: self.customer_id.to_owned(),
+            title: format!("Avoir sur facture {} : {}", self.get_ref().unwrap(), self.title),
+            invoice_day_id: None,
+            products: products
+                .into_iter()
+                .map(|product| Product { price: -product.price, ..product })
+                .collect(),
+            kind: DocumentKind::CreditNote,
+            credited_invoice_ref: self.get_ref(),
+            quote_ref: None,
+            reference: None,
+            sequence: None,
+        }
+    }
+
+    /// Credit notes of `all_invoices` referencing this invoice
+    pub fn get_credit_notes<'a>(&self, all_invoices: &'a [Invoice]) -> Vec<&'a Invoice> {
+        let invoice_ref = self.get_ref();
+
+        all_invoices
+            .iter()
+            .filter(|invoice| {
+                invoice.kind == DocumentKind::CreditNote
+                    && invoice_ref.is_some()
+                    && invoice.credited_invoice_ref == invoice_ref
+            })
+            .collect()
+    }
+
+    /// Lines still billed once the credit notes are deduced, a line partly credited is left
+    /// for its remaining amount. A credited amount is deduced from the line of the same description,
+    /// VAT rate and activity, then from the other lines of the same VAT rate and activity.
+    pub fn get_remaining_lines(&self, all_invoices: &[Invoice], default_vat_rate: &VatRate) -> Vec<Product> {
+        let mut remaining: Vec<Amount> = self.products.iter().map(Product::get_total_price).collect();
+
+        for credited in self.get_credit_notes(all_invoices).into_iter().flat_map(|credit_note| credit_note.products.iter()) {
+            // Rates compared once resolved, the lines of older invoices have no rate
+            let same_rates = |product: &&Product| {
+                product.get_vat_rate(default_vat_rate) == credited.get_vat_rate(default_vat_rate) && product.activity == credited.activity
+            };
+            let same_lines = self.products.iter().enumerate().filter(|(_, product)| same_rates(product));
+            let candidates = same_lines
+                .clone()
+                .filter(|(_, product)| product.description == credited.description)
+                .chain(same_lines)
+                .map(|(index, _)| index);
+
+            let mut left = credited.get_total_price();
+
+            for index in candidates {
+                // A credit only cancels a line of the opposite sign, never beyond its total
+                if (remaining[index] > Amount::ZERO && left < Amount::ZERO) || (remaining[index] < Amount::ZERO && left > Amount::ZERO) {
+                    let deduced = if left.abs() < remaining[index].abs() { left } else { -remaining[index] };
+                    remaining[index] += deduced;
+                    left -= deduced;
+                }
+            }
+        }
+
         self.products
             .iter()
-            .fold(0.0, |total, product| total + product.get_total_price())
+            .zip(remaining)
+            .filter(|(_, total)| *total != Amount::ZERO)
+            .map(|(product, total)| {
+                if total == product.get_total_price() {
+                    product.clone()
+                } else {
+                    Product { quantity: Quantity::from(1), price: total, ..product.clone() }
+                }
+            })
+            .collect()
+    }
+
+    /// TTC amount still to collect for the invoice once its credit notes are deduced
+    pub fn get_amount_due(&self, all_invoices: &[Invoice], default_vat_rate: &VatRate) -> Amount {
+        self.get_totals(default_vat_rate).total_ttc
+            + self
+                .get_credit_notes(all_invoices)
+                .iter()
+                .map(|credit_note| credit_note.get_totals(default_vat_rate).total_ttc)
+                .sum()
     }
 }
 
 impl fmt::Display for Invoice {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} - {} - {} €", self.get_ref().unwrap(), self.customer_id, self.get_total_price().to_string())
+        write!(f, "{} - {} - {} €", self.get_ref().unwrap(), self.customer_id, self.get_total_price())
     }
 }
 
@@ -82,16 +242,24 @@ mod tests {
         pub fn generate_simple_invoice_example() -> Invoice {
             let simple_product = Product {
                 description: "Product example".to_string(),
-                quantity: 1.0,
-                price: 350.0,
+                quantity: Quantity::from(1),
+                price: Amount::new("350").unwrap(),
+                vat: None,
+                activity: None,
             };
 
             Invoice {
                 invoice_day_id: Some(InvoiceDayId::new("01").unwrap()),
                 date: NaiveDate::from_ymd_opt(2015, 3, 14).unwrap(),
+                due_date: None,
                 customer_id: "king".to_string(),
                 title: "Test invoice for simple customer".to_string(),
                 products: vec![simple_product],
+                kind: DocumentKind::Invoice,
+                credited_invoice_ref: None,
+                quote_ref: None,
+                reference: None,
+                sequence: None,
             }
         }
 
@@ -101,16 +269,24 @@ mod tests {
         ) -> Invoice {
             let simple_product = Product {
                 description: "Product example".to_string(),
-                quantity: 1.0,
-                price: 350.0,
+                quantity: Quantity::from(1),
+                price: Amount::new("350").unwrap(),
+                vat: None,
+                activity: None,
             };
 
             Invoice {
                 invoice_day_id,
                 date,
+                due_date: None,
                 customer_id: "king".to_string(),
                 title: "Test invoice for simple customer".to_string(),
                 products: vec![simple_product],
+                kind: DocumentKind::Invoice,
+                credited_invoice_ref: None,
+                quote_ref: None,
+                reference: None,
+                sequence: None,
             }
         }
     }
@@ -172,6 +348,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn invoice_with_due_date_to_string() {
+        let invoice_example = Invoice {
+            due_date: Some(NaiveDate::from_ymd_opt(2015, 4, 30).unwrap()),
+            ..Invoice::generate_simple_invoice_example()
+        };
+
+        let yaml = serde_yaml::to_string(&invoice_example).unwrap();
+
+        assert!(yaml.contains("due_date:
+  day: '30'
+  month: '04'
+  year: '2015'
+"));
+
+        let invoice_from_yaml: Invoice = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(invoice_from_yaml.due_date, invoice_example.due_date);
+        assert_eq!(invoice_from_yaml.get_due_date(), NaiveDate::from_ymd_opt(2015, 4, 30).unwrap());
+    }
+
     #[test]
     fn invoice_id_test() {
         assert_eq!(
@@ -201,50 +398,241 @@ mod tests {
         let invoice_example = Invoice::generate_simple_invoice_example();
 
         assert_eq!(invoice_example.get_ref(), Some("2015031401".to_string()));
+
+        let invoice_example = Invoice {
+            invoice_day_id: None,
+            reference: Some("FAC-2015-00012".to_string()),
+            sequence: Some(12),
+            ..Invoice::generate_simple_invoice_example()
+        };
+
+        assert_eq!(invoice_example.get_ref(), Some("FAC-2015-00012".to_string()));
+
+        let yaml = serde_yaml::to_string(&invoice_example).unwrap();
+        assert!(yaml.ends_with("reference: FAC-2015-00012\nsequence: 12\n"));
     }
 
     #[test]
     fn invoice_total_price() {
         let simple_product = Product {
             description: "Product example".to_string(),
-            quantity: 1.0,
-            price: 350.0,
+            quantity: Quantity::from(1),
+            price: Amount::new("350").unwrap(),
+            vat: None,
+            activity: None,
         };
         let second_simple_product = Product {
             description: "Product example".to_string(),
-            quantity: 7.0,
-            price: 75.0,
+            quantity: Quantity::from(7),
+            price: Amount::new("75").unwrap(),
+            vat: None,
+            activity: None,
         };
 
         let invoice_example = Invoice {
             invoice_day_id: Some(InvoiceDayId::new("01").unwrap()),
             date: NaiveDate::from_ymd_opt(2015, 3, 14).unwrap(),
+            due_date: None,
             customer_id: "king".to_string(),
             title: "Test invoice for simple customer".to_string(),
             products: vec![],
+            kind: DocumentKind::Invoice,
+            credited_invoice_ref: None,
+            quote_ref: None,
+            reference: None,
+            sequence: None,
         };
 
-        assert_eq!(invoice_example.get_total_price(), 0.0);
+        assert_eq!(invoice_example.get_total_price(), Amount::ZERO);
 
         let invoice_example = Invoice {
             invoice_day_id: Some(InvoiceDayId::new("01").unwrap()),
             date: NaiveDate::from_ymd_opt(2015, 3, 14).unwrap(),
+            due_date: None,
             customer_id: "king".to_string(),
             title: "Test invoice for simple customer".to_string(),
             products: vec![simple_product.clone()],
+            kind: DocumentKind::Invoice,
+            credited_invoice_ref: None,
+            quote_ref: None,
+            reference: None,
+            sequence: None,
         };
 
-        assert_eq!(invoice_example.get_total_price(), 350.0);
+        assert_eq!(invoice_example.get_total_price(), Amount::new("350").unwrap());
 
         let invoice_example = Invoice {
             invoice_day_id: Some(InvoiceDayId::new("01").unwrap()),
             date: NaiveDate::from_ymd_opt(2015, 3, 14).unwrap(),
+            due_date: None,
             customer_id: "king".to_string(),
             title: "Test invoice for simple customer".to_string(),
             products: vec![simple_product.clone(), second_simple_product.clone()],
+            kind: DocumentKind::Invoice,
+            credited_invoice_ref: None,
+            quote_ref: None,
+            reference: None,
+            sequence: None,
+        };
+
+        assert_eq!(invoice_example.get_total_price(), Amount::new("875").unwrap());
+    }
+
+    #[test]
+    fn invoice_total_price_is_exact() {
+        let products = ["349.99", "350.00", "349.99", "0.02"]
+            .iter()
+            .map(|price| Product {
+                description: "Product example".to_string(),
+                quantity: Quantity::new("1").unwrap(),
+                price: Amount::new(price).unwrap(),
+                vat: None,
+                activity: None,
+            })
+            .collect();
+
+        let invoice_example = Invoice {
+            products,
+            ..Invoice::generate_simple_invoice_example()
         };
 
-        assert_eq!(invoice_example.get_total_price(), 875.0);
+        assert_eq!(invoice_example.get_total_price(), Amount::new("1050").unwrap());
+        assert_eq!(invoice_example.get_total_price().to_string(), "1050.00");
+    }
+
+    #[test]
+    fn invoice_totals_with_vat() {
+        let mut invoice_example = Invoice::generate_simple_invoice_example();
+        invoice_example.products.push(Product {
+            description: "Book".to_string(),
+            quantity: Quantity::from(2),
+            price: Amount::new("15").unwrap(),
+            vat: Some(VatRate::Reduced),
+            activity: None,
+        });
+
+        let totals = invoice_example.get_totals(&VatRate::Normal);
+
+        assert_eq!(totals.total_ht, Amount::new("380").unwrap());
+        assert_eq!(totals.vat_lines.len(), 2);
+        assert_eq!(totals.vat_lines[0].vat, Amount::new("70").unwrap());
+        assert_eq!(totals.vat_lines[1].vat, Amount::new("1.65").unwrap());
+        assert_eq!(totals.total_ttc, Amount::new("451.65").unwrap());
+        assert_eq!(totals.total_ht, invoice_example.get_total_price());
+    }
+
+    #[test]
+    fn invoice_payment_status() {
+        let invoice = Invoice::generate_simple_invoice_example();
+        let total_due = invoice.get_total_price();
+        let before_due_date = NaiveDate::from_ymd_opt(2015, 3, 20).unwrap();
+        let after_due_date = NaiveDate::from_ymd_opt(2015, 4, 14).unwrap();
+
+        assert_eq!(invoice.get_due_date(), NaiveDate::from_ymd_opt(2015, 4, 13).unwrap());
+        assert_eq!(
+            invoice.get_payment_status(total_due, &[], before_due_date),
+            PaymentStatus::Unpaid
+        );
+
+        let payments = vec![Payment::generate_simple_payment_example("100")];
+        assert_eq!(
+            invoice.get_payment_status(total_due, &payments, before_due_date),
+            PaymentStatus::Partial
+        );
+        assert_eq!(
+            invoice.get_payment_status(total_due, &payments, after_due_date),
+            PaymentStatus::Overdue
+        );
+
+        let payments = vec![
+            Payment::generate_simple_payment_example("100"),
+            Payment::generate_simple_payment_example("250"),
+        ];
+        assert_eq!(
+            invoice.get_payment_status(total_due, &payments, after_due_date),
+            PaymentStatus::Paid
+        );
+    }
+
+    #[test]
+    fn invoice_credit_note() {
+        let invoice = Invoice::generate_simple_invoice_example();
+        let credit_date = NaiveDate::from_ymd_opt(2015, 3, 20).unwrap();
+
+        let credit_note = Invoice {
+            invoice_day_id: Some(InvoiceDayId::new("01").unwrap()),
+            ..invoice.create_credit_note(credit_date, invoice.products.clone())
+        };
+
+        assert_eq!(credit_note.get_ref(), Some("AV2015032001".to_string()));
+        assert_eq!(credit_note.credited_invoice_ref, Some("2015031401".to_string()));
+        assert_eq!(credit_note.get_total_price(), Amount::new("-350").unwrap());
+        assert_eq!(credit_note.due_date, None);
+
+        let yaml = serde_yaml::to_string(&credit_note).unwrap();
+        assert!(yaml.ends_with("kind: credit_note\ncredited_invoice_ref: '2015031401'\n"));
+
+        let all_invoices = vec![invoice.clone(), credit_note];
+        assert_eq!(invoice.get_credit_notes(&all_invoices).len(), 1);
+        assert!(all_invoices[1].get_credit_notes(&all_invoices).is_empty());
+    }
+
+    #[test]
+    fn invoice_remaining_lines() {
+        let mut invoice = Invoice::generate_simple_invoice_example();
+        invoice.products.push(Product {
+            description: "Option".to_string(),
+            quantity: Quantity::from(2),
+            price: Amount::new("25").unwrap(),
+            vat: None,
+            activity: None,
+        });
+        invoice.products.push(Product {
+            description: "Remise".to_string(),
+            quantity: Quantity::from(1),
+            price: Amount::new("-30").unwrap(),
+            vat: None,
+            activity: None,
+        });
+        let credit_date = NaiveDate::from_ymd_opt(2015, 3, 20).unwrap();
+        let credit_note = Invoice {
+            invoice_day_id: Some(InvoiceDayId::new("01").unwrap()),
+            ..invoice.create_credit_note(credit_date, vec![invoice.products[0].get_partial_credit(Amount::new("100").unwrap()).unwrap()])
+        };
+
+        assert_eq!(invoice.get_remaining_lines(&[], &VatRate::Normal).len(), 3);
+
+        let all_invoices = vec![invoice.clone(), credit_note];
+        let remaining_lines = invoice.get_remaining_lines(&all_invoices, &VatRate::Normal);
+        assert_eq!(
+            remaining_lines.iter().map(|product| (product.description.as_str(), product.quantity, product.get_total_price())).collect::<Vec<_>>(),
+            vec![
+                ("Product example", Quantity::from(1), Amount::new("250").unwrap()),
+                ("Option", Quantity::from(2), Amount::new("50").unwrap()),
+                ("Remise", Quantity::from(1), Amount::new("-30").unwrap()),
+            ]
+        );
+
+        // A line credited under another description is deduced from the lines of the same rates,
+        // the rate of the credit note is resolved when the one of the invoice is not
+        let credit_note = Invoice {
+            invoice_day_id: Some(InvoiceDayId::new("02").unwrap()),
+            ..invoice.create_credit_note(
+                credit_date,
+                vec![Product {
+                    description: "Geste commercial".to_string(),
+                    price: Amount::new("270").unwrap(),
+                    vat: Some(VatRate::Normal),
+                    ..invoice.products[0].clone()
+                }],
+            )
+        };
+        let all_invoices = [all_invoices, vec![credit_note]].concat();
+        let remaining_lines = invoice.get_remaining_lines(&all_invoices, &VatRate::Normal);
+        assert_eq!(
+            remaining_lines.iter().map(|product| (product.description.as_str(), product.get_total_price())).collect::<Vec<_>>(),
+            vec![("Option", Amount::new("30").unwrap()), ("Remise", Amount::new("-30").unwrap())]
+        );
     }
 
     #[test]
@@ -253,6 +641,6 @@ mod tests {
 
         let output = format!("{}", invoice);
 
-        assert_eq!(output, "2015031401 - king - 350 €");
+        assert_eq!(output, "2015031401 - king - 350.00 €");
     }
 }
@@ -10,7 +10,7 @@ impl DayString {
     pub fn new(day: &str) -> Result<Self, String> {
         if day.len() <= 2 && day.chars().all(char::is_numeric) {
             let day_num = day.parse::<u8>().map_err(|_| "Invalid number".to_string())?;
-            if day_num >= 1 && day_num <= 31 {
+            if (1..=31).contains(&day_num) {
                 if day.len() == 1 {
                     return Ok(DayString("0".to_string() + day));
                 } else {
@@ -42,7 +42,7 @@ impl MonthString {
         if month.len() <= 2 && month.chars().all(char::is_numeric) {
             let month_num =
                 month.parse::<u8>().map_err(|_| "Invalid number".to_string())?;
-            if month_num >= 1 && month_num <= 12 {
+            if (1..=12).contains(&month_num) {
                 if month.len() == 1 {
                     return Ok(MonthString("0".to_string() + month));
                 } else {
@@ -113,12 +113,12 @@ impl From<&NaiveDate> for InvoiceDate {
     }
 }
 
-impl Into<NaiveDate> for InvoiceDate {
-    fn into(self) -> NaiveDate {
+impl From<InvoiceDate> for NaiveDate {
+    fn from(invoice_date: InvoiceDate) -> NaiveDate {
         NaiveDate::from_ymd_opt(
-            self.year.to_string().parse::<i32>().unwrap(),
-            self.month.to_string().parse::<u32>().unwrap(),
-            self.day.to_string().parse::<u32>().unwrap(),
+            invoice_date.year.to_string().parse::<i32>().unwrap(),
+            invoice_date.month.to_string().parse::<u32>().unwrap(),
+            invoice_date.day.to_string().parse::<u32>().unwrap(),
         )
             .unwrap()
     }
@@ -142,6 +142,24 @@ pub(crate) fn deser_invoice_date<'de, D>(deserializer: D) -> Result<NaiveDate, D
     Ok(date)
 }
 
+pub(crate) fn ser_option_invoice_date<S>(
+    date: &Option<NaiveDate>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+{
+    date.as_ref().map(InvoiceDate::from).serialize(serializer)
+}
+
+pub(crate) fn deser_option_invoice_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+{
+    let date: Option<InvoiceDate> = Option::deserialize(deserializer)?;
+    Ok(date.map(NaiveDate::from))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
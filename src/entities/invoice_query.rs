@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+
+use crate::entities::amount::Amount;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice::Invoice;
+use crate::entities::payment::Payment;
+use crate::entities::payment_status::PaymentStatus;
+use crate::entities::vat_rate::VatRate;
+
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum InvoiceSort {
+    #[default]
+    Date,
+    Reference,
+    Customer,
+    /// TTC total
+    Amount,
+}
+
+impl InvoiceSort {
+    pub fn all_sorts() -> Vec<InvoiceSort> {
+        vec![InvoiceSort::Date, InvoiceSort::Reference, InvoiceSort::Customer, InvoiceSort::Amount]
+    }
+}
+
+impl FromStr for InvoiceSort {
+    type Err = String;
+
+    fn from_str(sort: &str) -> Result<Self, Self::Err> {
+        InvoiceSort::all_sorts()
+            .into_iter()
+            .find(|invoice_sort| invoice_sort.to_string() == sort.to_lowercase())
+            .ok_or_else(|| format!("Invalid sort: {} (date, reference, customer or amount)", sort))
+    }
+}
+
+impl fmt::Display for InvoiceSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvoiceSort::Date => write!(f, "date"),
+            InvoiceSort::Reference => write!(f, "reference"),
+            InvoiceSort::Customer => write!(f, "customer"),
+            InvoiceSort::Amount => write!(f, "amount"),
+        }
+    }
+}
+
+/// Search of invoices, missing bounds and empty lists do not filter
+#[derive(Clone, Debug, Default)]
+pub struct InvoiceQuery {
+    pub customer_id: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// Bounds of the TTC total, included
+    pub min_amount: Option<Amount>,
+    pub max_amount: Option<Amount>,
+    /// Searched without case in the title and the product descriptions
+    pub text: Option<String>,
+    pub statuses: Vec<PaymentStatus>,
+    pub kinds: Vec<DocumentKind>,
+    pub sort: InvoiceSort,
+    pub descending: bool,
+    pub limit: Option<usize>,
+}
+
+impl InvoiceQuery {
+    /// Invoices of `all_invoices` matching the query, sorted then limited.
+    /// The payment status is the one of `today`, credit notes deduced.
+    pub fn apply(
+        &self,
+        all_invoices: &[Invoice],
+        all_payments: &HashMap<String, Vec<Payment>>,
+        default_vat_rate: &VatRate,
+        today: NaiveDate,
+    ) -> Vec<Invoice> {
+        let mut invoices: Vec<(&Invoice, Amount)> = all_invoices
+            .iter()
+            .map(|invoice| (invoice, invoice.get_totals(default_vat_rate).total_ttc))
+            .filter(|(invoice, total_ttc)| self.matches(invoice, *total_ttc))
+            .filter(|(invoice, _)| {
+                if self.statuses.is_empty() {
+                    return true;
+                }
+
+                let payments = invoice
+                    .get_ref()
+                    .and_then(|invoice_ref| all_payments.get(&invoice_ref))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let total_due = invoice.get_amount_due(all_invoices, default_vat_rate);
+
+                self.statuses.contains(&invoice.get_payment_status(total_due, payments, today))
+            })
+            .collect();
+
+        match self.sort {
+            InvoiceSort::Date => invoices.sort_by_key(|(invoice, _)| (invoice.date, invoice.get_ref())),
+            InvoiceSort::Reference => invoices.sort_by_key(|(invoice, _)| invoice.get_ref()),
+            InvoiceSort::Customer => {
+                invoices.sort_by_key(|(invoice, _)| (invoice.customer_id.to_owned(), invoice.date))
+            }
+            InvoiceSort::Amount => invoices.sort_by_key(|(_, total_ttc)| *total_ttc),
+        }
+
+        if self.descending {
+            invoices.reverse();
+        }
+
+        invoices
+            .into_iter()
+            .take(self.limit.unwrap_or(usize::MAX))
+            .map(|(invoice, _)| invoice.clone())
+            .collect()
+    }
+
+    fn matches(&self, invoice: &Invoice, total_ttc: Amount) -> bool {
+        let text_matches = |text: &String| {
+            let text = text.to_lowercase();
+
+            invoice.title.to_lowercase().contains(&text)
+                || invoice
+                    .products
+                    .iter()
+                    .any(|product| product.description.to_lowercase().contains(&text))
+        };
+
+        self.customer_id.as_ref().is_none_or(|customer_id| invoice.customer_id == *customer_id)
+            && self.from.is_none_or(|from| invoice.date >= from)
+            && self.to.is_none_or(|to| invoice.date <= to)
+            && self.min_amount.is_none_or(|min_amount| total_ttc >= min_amount)
+            && self.max_amount.is_none_or(|max_amount| total_ttc <= max_amount)
+            && self.text.as_ref().is_none_or(text_matches)
+            && (self.kinds.is_empty() || self.kinds.contains(&invoice.kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::payment::PaymentMethod;
+    use crate::entities::product::Product;
+
+    use super::*;
+
+    fn generate_invoices() -> Vec<Invoice> {
+        let mut first = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2024, 1, 10).unwrap(),
+        );
+        first.title = "Website".to_string();
+
+        let mut second = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2024, 2, 10).unwrap(),
+        );
+        second.customer_id = "queen".to_string();
+        second.products.push(Product::from_str("Hosting;1;50").unwrap());
+
+        let mut credit_note = second.create_credit_note(
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            vec![Product::from_str("Hosting;1;50").unwrap()],
+        );
+        credit_note.invoice_day_id = Some(InvoiceDayId::new("01").unwrap());
+
+        vec![second, first, credit_note]
+    }
+
+    #[test]
+    fn query_without_filter_sorts_by_date() {
+        let invoices = generate_invoices();
+
+        let found = InvoiceQuery::default().apply(&invoices, &HashMap::new(), &VatRate::Zero, NaiveDate::MAX);
+
+        let found_refs: Vec<_> = found.iter().map(|invoice| invoice.get_ref().unwrap()).collect();
+        assert_eq!(found_refs, vec!["2024011001", "2024021001", "AV2024030101"]);
+    }
+
+    #[test]
+    fn query_with_filters() {
+        let invoices = generate_invoices();
+        let today = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let all_payments = HashMap::from([(
+            "2024011001".to_string(),
+            vec![Payment {
+                date: today,
+                amount: Amount::new("350").unwrap(),
+                method: PaymentMethod::Transfer,
+                reference: None,
+            }],
+        )]);
+
+        let search = |query: InvoiceQuery| -> Vec<String> {
+            query
+                .apply(&invoices, &all_payments, &VatRate::Zero, today)
+                .iter()
+                .map(|invoice| invoice.get_ref().unwrap())
+                .collect()
+        };
+
+        assert_eq!(
+            search(InvoiceQuery { customer_id: Some("queen".to_string()), ..Default::default() }),
+            vec!["2024021001", "AV2024030101"]
+        );
+        assert_eq!(
+            search(InvoiceQuery { text: Some("HOSTING".to_string()), kinds: vec![DocumentKind::Invoice], ..Default::default() }),
+            vec!["2024021001"]
+        );
+        assert_eq!(
+            search(InvoiceQuery {
+                from: NaiveDate::from_ymd_opt(2024, 2, 1),
+                max_amount: Some(Amount::new("350").unwrap()),
+                ..Default::default()
+            }),
+            vec!["AV2024030101"]
+        );
+        assert_eq!(
+            search(InvoiceQuery {
+                statuses: vec![PaymentStatus::Overdue],
+                kinds: vec![DocumentKind::Invoice],
+                ..Default::default()
+            }),
+            vec!["2024021001"]
+        );
+        assert_eq!(
+            search(InvoiceQuery { sort: InvoiceSort::Amount, descending: true, limit: Some(2), ..Default::default() }),
+            vec!["2024021001", "2024011001"]
+        );
+    }
+
+    #[test]
+    fn invoice_sort_from_str() {
+        assert_eq!(InvoiceSort::from_str("Amount"), Ok(InvoiceSort::Amount));
+        assert!(InvoiceSort::from_str("size").is_err());
+    }
+}
@@ -0,0 +1,151 @@
+use std::iter::Sum;
+use std::ops::Add;
+
+use serde::Serialize;
+
+use crate::entities::amount::Amount;
+use crate::entities::product::Product;
+use crate::entities::vat_rate::VatRate;
+
+/// VAT due for one rate, computed on the sum of the lines using that rate.
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct VatLine {
+    pub rate: VatRate,
+    pub base: Amount,
+    pub vat: Amount,
+}
+
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct InvoiceTotals {
+    pub total_ht: Amount,
+    pub vat_lines: Vec<VatLine>,
+    pub total_vat: Amount,
+    pub total_ttc: Amount,
+}
+
+impl InvoiceTotals {
+    pub fn new(lines: Vec<(VatRate, Amount)>) -> Self {
+        let mut vat_lines: Vec<VatLine> = vec![];
+
+        for (rate, total) in lines {
+            match vat_lines.iter_mut().find(|vat_line| vat_line.rate == rate) {
+                Some(vat_line) => vat_line.base += total,
+                None => vat_lines.push(VatLine { rate, base: total, vat: Amount::ZERO }),
+            }
+        }
+
+        vat_lines.sort_by_key(|vat_line| -vat_line.rate.basis_points());
+        vat_lines
+            .iter_mut()
+            .for_each(|vat_line| vat_line.vat = vat_line.base.get_percentage(vat_line.rate.basis_points()));
+
+        let total_ht: Amount = vat_lines.iter().map(|vat_line| vat_line.base).sum();
+        let total_vat: Amount = vat_lines.iter().map(|vat_line| vat_line.vat).sum();
+
+        InvoiceTotals { total_ht, vat_lines, total_vat, total_ttc: total_ht + total_vat }
+    }
+
+    /// Totals of product lines, lines without rate use `default_vat_rate`
+    pub fn from_products(products: &[Product], default_vat_rate: &VatRate) -> Self {
+        InvoiceTotals::new(
+            products
+                .iter()
+                .map(|product| {
+                    (product.get_vat_rate(default_vat_rate).clone(), product.get_total_price())
+                })
+                .collect(),
+        )
+    }
+
+    pub fn get_exemption_reasons(&self) -> Vec<&str> {
+        self.vat_lines
+            .iter()
+            .filter_map(|vat_line| vat_line.rate.get_exemption_reason())
+            .collect()
+    }
+}
+
+impl Add for InvoiceTotals {
+    type Output = InvoiceTotals;
+
+    fn add(self, other: InvoiceTotals) -> InvoiceTotals {
+        let mut vat_lines = self.vat_lines;
+
+        for other_line in other.vat_lines {
+            match vat_lines.iter_mut().find(|vat_line| vat_line.rate == other_line.rate) {
+                Some(vat_line) => {
+                    vat_line.base += other_line.base;
+                    vat_line.vat += other_line.vat;
+                }
+                None => vat_lines.push(other_line),
+            }
+        }
+
+        vat_lines.sort_by_key(|vat_line| -vat_line.rate.basis_points());
+
+        InvoiceTotals {
+            total_ht: self.total_ht + other.total_ht,
+            vat_lines,
+            total_vat: self.total_vat + other.total_vat,
+            total_ttc: self.total_ttc + other.total_ttc,
+        }
+    }
+}
+
+impl Sum for InvoiceTotals {
+    fn sum<I: Iterator<Item = InvoiceTotals>>(iter: I) -> InvoiceTotals {
+        iter.fold(InvoiceTotals::new(vec![]), |total, totals| total + totals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invoice_totals_by_rate() {
+        let totals = InvoiceTotals::new(vec![
+            (VatRate::Reduced, Amount::new("10.01").unwrap()),
+            (VatRate::Normal, Amount::new("100").unwrap()),
+            (VatRate::Reduced, Amount::new("10.01").unwrap()),
+        ]);
+
+        assert_eq!(totals.total_ht, Amount::new("120.02").unwrap());
+        assert_eq!(totals.vat_lines.len(), 2);
+        assert_eq!(totals.vat_lines[0].rate, VatRate::Normal);
+        assert_eq!(totals.vat_lines[0].vat, Amount::new("20").unwrap());
+        assert_eq!(totals.vat_lines[1].rate, VatRate::Reduced);
+        assert_eq!(totals.vat_lines[1].base, Amount::new("20.02").unwrap());
+        // 5.5 % of 20.02 is 1.1011, computed once on the base of the rate
+        assert_eq!(totals.vat_lines[1].vat, Amount::new("1.10").unwrap());
+        assert_eq!(totals.total_vat, Amount::new("21.10").unwrap());
+        assert_eq!(totals.total_ttc, Amount::new("141.12").unwrap());
+    }
+
+    #[test]
+    fn test_invoice_totals_exempt() {
+        let totals = InvoiceTotals::new(vec![(
+            VatRate::Exempt { reason: VatRate::FRANCHISE_REASON.into() },
+            Amount::new("350").unwrap(),
+        )]);
+
+        assert_eq!(totals.total_vat, Amount::ZERO);
+        assert_eq!(totals.total_ttc, Amount::new("350").unwrap());
+        assert_eq!(totals.get_exemption_reasons(), vec![VatRate::FRANCHISE_REASON]);
+    }
+
+    #[test]
+    fn test_invoice_totals_sum() {
+        let totals: InvoiceTotals = vec![
+            InvoiceTotals::new(vec![(VatRate::Normal, Amount::new("100").unwrap())]),
+            InvoiceTotals::new(vec![(VatRate::Normal, Amount::new("50").unwrap())]),
+            InvoiceTotals::new(vec![(VatRate::Zero, Amount::new("5").unwrap())]),
+        ]
+        .into_iter()
+        .sum();
+
+        assert_eq!(totals.total_ht, Amount::new("155").unwrap());
+        assert_eq!(totals.total_vat, Amount::new("30").unwrap());
+        assert_eq!(totals.vat_lines.len(), 2);
+    }
+}
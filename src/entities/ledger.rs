@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::entities::invoice::Invoice;
+
+/// Link of the hash chain sealing an issued invoice. Its digest covers the
+/// canonical serialization of the invoice and the digest of the previous link,
+/// so changing, removing or inserting an invoice breaks every following link.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct LedgerEntry {
+    pub reference: String,
+    pub previous_digest: String,
+    pub digest: String,
+}
+
+impl LedgerEntry {
+    /// Previous digest of the first link
+    pub const GENESIS_DIGEST: &'static str = "";
+
+    pub fn new(invoice: &Invoice, previous_digest: &str) -> Self {
+        LedgerEntry {
+            reference: invoice.get_ref().unwrap_or_default(),
+            previous_digest: previous_digest.to_string(),
+            digest: Self::compute_digest(invoice, previous_digest),
+        }
+    }
+
+    pub fn compute_digest(invoice: &Invoice, previous_digest: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(previous_digest.as_bytes());
+        hasher.update(serde_yaml::to_string(invoice).unwrap().as_bytes());
+
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum LedgerIssue {
+    /// Invoice changed since it was sealed
+    Modified(String),
+    /// Sealed invoice not in the store anymore
+    Removed(String),
+    /// Invoice in the store never sealed
+    Inserted(String),
+    /// Link not following the previous one, the ledger itself was changed
+    BrokenChain(String),
+}
+
+impl fmt::Display for LedgerIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerIssue::Modified(reference) => write!(f, "{} was modified after issuing", reference),
+            LedgerIssue::Removed(reference) => write!(f, "{} was removed", reference),
+            LedgerIssue::Inserted(reference) => write!(f, "{} was inserted without being sealed", reference),
+            LedgerIssue::BrokenChain(reference) => write!(f, "Ledger chain is broken at {}", reference),
+        }
+    }
+}
+
+/// Check every link of `ledger` against the invoices of the store
+pub fn verify_ledger(ledger: &[LedgerEntry], invoices: &[Invoice]) -> Vec<LedgerIssue> {
+    let mut invoices_by_ref: HashMap<String, &Invoice> = invoices
+        .iter()
+        .filter_map(|invoice| invoice.get_ref().map(|reference| (reference, invoice)))
+        .collect();
+
+    let mut issues = vec![];
+    let mut previous_digest = LedgerEntry::GENESIS_DIGEST;
+
+    for entry in ledger {
+        if entry.previous_digest != previous_digest {
+            issues.push(LedgerIssue::BrokenChain(entry.reference.to_owned()));
+        }
+
+        match invoices_by_ref.remove(&entry.reference) {
+            Some(invoice) if LedgerEntry::compute_digest(invoice, &entry.previous_digest) != entry.digest => {
+                issues.push(LedgerIssue::Modified(entry.reference.to_owned()))
+            }
+            Some(_) => {}
+            None => issues.push(LedgerIssue::Removed(entry.reference.to_owned())),
+        }
+
+        previous_digest = &entry.digest;
+    }
+
+    let mut inserted_refs: Vec<String> = invoices_by_ref.into_keys().collect();
+    inserted_refs.sort();
+    issues.extend(inserted_refs.into_iter().map(LedgerIssue::Inserted));
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::entities::amount::Amount;
+    use crate::entities::invoice::InvoiceDayId;
+
+    use super::*;
+
+    fn generate_sealed_invoices() -> (Vec<Invoice>, Vec<LedgerEntry>) {
+        let invoices: Vec<Invoice> = (1..=3)
+            .map(|day| {
+                Invoice::generate_simple_invoice_with_id_and_date_example(
+                    Some(InvoiceDayId::new("01").unwrap()),
+                    NaiveDate::from_ymd_opt(2020, 3, day).unwrap(),
+                )
+            })
+            .collect();
+
+        let mut ledger: Vec<LedgerEntry> = vec![];
+        invoices.iter().for_each(|invoice| {
+            let previous_digest = ledger
+                .last()
+                .map(|entry| entry.digest.to_owned())
+                .unwrap_or_default();
+            ledger.push(LedgerEntry::new(invoice, &previous_digest));
+        });
+
+        (invoices, ledger)
+    }
+
+    #[test]
+    fn ledger_entry_digest() {
+        let invoice = Invoice::generate_simple_invoice_example();
+
+        let entry = LedgerEntry::new(&invoice, LedgerEntry::GENESIS_DIGEST);
+
+        assert_eq!(entry.reference, "2015031401");
+        assert_eq!(entry.digest.len(), 64);
+        assert_eq!(entry, LedgerEntry::new(&invoice, LedgerEntry::GENESIS_DIGEST));
+        assert_ne!(entry.digest, LedgerEntry::new(&invoice, &entry.digest).digest);
+    }
+
+    #[test]
+    fn verify_untouched_ledger() {
+        let (invoices, ledger) = generate_sealed_invoices();
+
+        assert!(verify_ledger(&ledger, &invoices).is_empty());
+    }
+
+    #[test]
+    fn verify_tampered_invoices() {
+        let (mut invoices, ledger) = generate_sealed_invoices();
+
+        invoices[0].products[0].price = Amount::new("35").unwrap();
+        invoices.remove(1);
+        invoices.push(Invoice::generate_simple_invoice_example());
+
+        assert_eq!(
+            verify_ledger(&ledger, &invoices),
+            vec![
+                LedgerIssue::Modified("2020030101".to_string()),
+                LedgerIssue::Removed("2020030201".to_string()),
+                LedgerIssue::Inserted("2015031401".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn verify_tampered_ledger() {
+        let (mut invoices, mut ledger) = generate_sealed_invoices();
+
+        // Removing an invoice with its link breaks the chain
+        invoices.remove(1);
+        ledger.remove(1);
+
+        assert_eq!(
+            verify_ledger(&ledger, &invoices),
+            vec![LedgerIssue::BrokenChain("2020030301".to_string())]
+        );
+    }
+}
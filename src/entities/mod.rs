@@ -1,7 +1,37 @@
+pub mod accounting_settings;
+pub mod activity;
+pub mod amount;
+pub mod csv_export;
+pub mod csv_import;
 pub mod customer;
+pub mod customer_statement;
+pub mod customer_stats;
+pub mod document_kind;
+pub mod e_invoice_import;
+pub mod factur_x;
+pub mod fec;
+mod fixed_point;
 pub mod invoice;
 pub mod invoice_date;
+pub mod invoice_query;
+pub mod invoice_totals;
+pub mod ledger;
+pub mod numbering_audit;
+pub mod numbering_policy;
+pub mod payment;
+pub mod payment_status;
+pub mod payment_terms;
+pub mod percentage;
 pub mod product;
+pub mod quantity;
+pub mod quote;
+pub mod range_stats;
+pub mod recurring_invoice;
+pub mod revenue_thresholds;
 mod serializer;
 pub mod settings;
 pub mod siren;
+pub mod ubl;
+pub mod urssaf_declaration;
+pub mod vat_rate;
+mod xml_writer;
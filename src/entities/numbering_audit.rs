@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::entities::invoice::Invoice;
+use crate::entities::numbering_policy::{CounterReset, NumberingPolicy};
+
+/// Break in the chronological and unbroken numbering required for invoices
+#[derive(PartialEq, Clone, Debug)]
+pub enum NumberingIssue {
+    /// Numbers `from` to `to` of the series are missing
+    Gap { series: String, from: u32, to: u32 },
+    /// Number used by several invoices
+    Duplicate { series: String, reference: String },
+    /// Invoice dated before the previous number of its series
+    OutOfOrder { reference: String, date: NaiveDate, previous_reference: String, previous_date: NaiveDate },
+}
+
+impl fmt::Display for NumberingIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberingIssue::Gap { series, from, to } if from == to => {
+                write!(f, "Gap in {} series: number {} is missing", series, from)
+            }
+            NumberingIssue::Gap { series, from, to } => {
+                write!(f, "Gap in {} series: numbers {} to {} are missing", series, from, to)
+            }
+            NumberingIssue::Duplicate { series, reference } => {
+                write!(f, "Duplicate in {} series: {} is used twice", series, reference)
+            }
+            NumberingIssue::OutOfOrder { reference, date, previous_reference, previous_date } => write!(
+                f,
+                "Out of order: {} dated {} comes after {} dated {}",
+                reference, date, previous_reference, previous_date
+            ),
+        }
+    }
+}
+
+/// Number of the invoice in its series, with the series name. Dated references
+/// have a series by day, references of a policy a series by year or a single one.
+fn get_series_number(invoice: &Invoice, numbering_policy: Option<&NumberingPolicy>) -> Option<(String, u32)> {
+    let counter = numbering_policy
+        .map(|numbering_policy| numbering_policy.counter)
+        .unwrap_or_default();
+    let sequence = match numbering_policy {
+        Some(numbering_policy) => numbering_policy.get_sequence(invoice),
+        None => invoice.sequence,
+    };
+
+    match (sequence, &invoice.invoice_day_id) {
+        (Some(sequence), _) => match counter {
+            CounterReset::Yearly => Some((format!("{} {}", invoice.kind, invoice.date.year()), sequence)),
+            CounterReset::Continuous => Some((invoice.kind.to_string(), sequence)),
+        },
+        (None, Some(invoice_day_id)) => Some((
+            format!("{} {}", invoice.kind, invoice.date),
+            invoice_day_id.to_string().parse().ok()?,
+        )),
+        (None, None) => None,
+    }
+}
+
+/// Gaps, duplicates and out of order dates of all series of `invoices`.
+/// A number removed at the end of a series can not be detected.
+pub fn audit_numbering(invoices: &[Invoice], numbering_policy: Option<&NumberingPolicy>) -> Vec<NumberingIssue> {
+    let mut issues = vec![];
+
+    let mut all_series: BTreeMap<String, Vec<(u32, &Invoice)>> = BTreeMap::new();
+    invoices.iter().for_each(|invoice| {
+        if let Some((series, number)) = get_series_number(invoice, numbering_policy) {
+            all_series.entry(series).or_default().push((number, invoice));
+        }
+    });
+
+    for (series, mut numbers) in all_series {
+        numbers.sort_by_key(|(number, invoice)| (*number, invoice.get_ref()));
+
+        let mut expected_number = 1;
+        let mut previous_invoice: Option<&Invoice> = None;
+
+        for (number, invoice) in numbers {
+            let reference = invoice.get_ref().unwrap_or_default();
+
+            if number < expected_number {
+                issues.push(NumberingIssue::Duplicate { series: series.to_owned(), reference: reference.to_owned() });
+            } else if number > expected_number {
+                issues.push(NumberingIssue::Gap { series: series.to_owned(), from: expected_number, to: number - 1 });
+            }
+
+            if let Some(previous_invoice) = previous_invoice.filter(|previous_invoice| previous_invoice.date > invoice.date) {
+                issues.push(NumberingIssue::OutOfOrder {
+                    reference,
+                    date: invoice.date,
+                    previous_reference: previous_invoice.get_ref().unwrap_or_default(),
+                    previous_date: previous_invoice.date,
+                });
+            }
+
+            expected_number = expected_number.max(number + 1);
+            previous_invoice = Some(invoice);
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::document_kind::DocumentKind;
+    use crate::entities::invoice::InvoiceDayId;
+
+    use super::*;
+
+    fn generate_numbered_invoice(sequence: u32, year: i32, month: u32, day: u32) -> Invoice {
+        Invoice {
+            invoice_day_id: None,
+            reference: Some(format!("FAC-{}-{:05}", year, sequence)),
+            sequence: Some(sequence),
+            ..Invoice::generate_simple_invoice_with_id_and_date_example(
+                None,
+                NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+            )
+        }
+    }
+
+    fn generate_dated_invoice(invoice_day_id: u32, day: u32) -> Invoice {
+        Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::from(invoice_day_id)),
+            NaiveDate::from_ymd_opt(2020, 3, day).unwrap(),
+        )
+    }
+
+    #[test]
+    fn audit_unbroken_numbering() {
+        let invoices = vec![
+            generate_dated_invoice(1, 1),
+            generate_dated_invoice(2, 1),
+            generate_dated_invoice(1, 2),
+            Invoice {
+                kind: DocumentKind::CreditNote,
+                ..generate_dated_invoice(1, 2)
+            },
+            generate_numbered_invoice(1, 2020, 4, 1),
+            generate_numbered_invoice(2, 2020, 4, 1),
+            generate_numbered_invoice(1, 2021, 1, 1),
+        ];
+
+        assert!(audit_numbering(&invoices, None).is_empty());
+    }
+
+    #[test]
+    fn audit_broken_numbering() {
+        let invoices = vec![
+            generate_dated_invoice(1, 1),
+            generate_dated_invoice(3, 1),
+            generate_numbered_invoice(1, 2020, 4, 1),
+            generate_numbered_invoice(2, 2020, 4, 10),
+            generate_numbered_invoice(2, 2020, 4, 10),
+            generate_numbered_invoice(3, 2020, 4, 5),
+            generate_numbered_invoice(6, 2020, 5, 1),
+        ];
+
+        let issues = audit_numbering(&invoices, None);
+
+        assert_eq!(
+            issues,
+            vec![
+                NumberingIssue::Duplicate {
+                    series: "invoice 2020".to_string(),
+                    reference: "FAC-2020-00002".to_string()
+                },
+                NumberingIssue::OutOfOrder {
+                    reference: "FAC-2020-00003".to_string(),
+                    date: NaiveDate::from_ymd_opt(2020, 4, 5).unwrap(),
+                    previous_reference: "FAC-2020-00002".to_string(),
+                    previous_date: NaiveDate::from_ymd_opt(2020, 4, 10).unwrap(),
+                },
+                NumberingIssue::Gap { series: "invoice 2020".to_string(), from: 4, to: 5 },
+                NumberingIssue::Gap { series: "invoice 2020-03-01".to_string(), from: 2, to: 2 },
+            ]
+        );
+        assert_eq!(issues[3].to_string(), "Gap in invoice 2020-03-01 series: number 2 is missing");
+    }
+
+    #[test]
+    fn audit_continuous_numbering() {
+        let invoices = vec![
+            generate_numbered_invoice(1, 2020, 4, 1),
+            generate_numbered_invoice(2, 2021, 1, 1),
+        ];
+
+        let continuous_policy = NumberingPolicy::new("FAC-{YYYY}-{SEQ:5}", CounterReset::Continuous).unwrap();
+
+        assert!(audit_numbering(&invoices, Some(&continuous_policy)).is_empty());
+        assert_eq!(
+            audit_numbering(&invoices, None),
+            vec![NumberingIssue::Gap { series: "invoice 2021".to_string(), from: 1, to: 1 }]
+        );
+    }
+
+    #[test]
+    fn audit_imported_numbering() {
+        let imported_invoice = Invoice { sequence: None, ..generate_numbered_invoice(1, 2020, 4, 1) };
+        let invoices = vec![imported_invoice, generate_numbered_invoice(2, 2020, 5, 1)];
+
+        // The imported reference follows the pattern, it is the first number of the series
+        let numbering_policy = NumberingPolicy::new("FAC-{YYYY}-{SEQ:5}", CounterReset::Yearly).unwrap();
+
+        assert!(audit_numbering(&invoices, Some(&numbering_policy)).is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice::Invoice;
+
+/// References are file names in the invoices folder
+const INVALID_REFERENCE_CHARACTERS: [char; 3] = ['/', '\\', '.'];
+
+/// When the sequence of a numbering series starts again at 1
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CounterReset {
+    /// A new series each calendar year, the pattern must contain the year
+    #[default]
+    Yearly,
+    /// One series for all years
+    Continuous,
+}
+
+impl CounterReset {
+    pub fn all_counter_resets() -> Vec<CounterReset> {
+        vec![CounterReset::Yearly, CounterReset::Continuous]
+    }
+}
+
+impl fmt::Display for CounterReset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CounterReset::Yearly => write!(f, "yearly"),
+            CounterReset::Continuous => write!(f, "continuous"),
+        }
+    }
+}
+
+/// Reference pattern of the invoices, like `FAC-{YYYY}-{SEQ:5}`.
+///
+/// Placeholders are `{PREFIX}`, `{YYYY}`, `{YY}`, `{MM}`, `{DD}` and `{SEQ}`,
+/// or `{SEQ:n}` for a sequence padded to n digits. Each document kind has its
+/// own series: without `{PREFIX}` in the pattern, the prefix of credit notes and
+/// deposits is put before the reference. Invoices numbered before a policy was
+/// set keep their historical `YYYYMMDDNN` reference.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct NumberingPolicy {
+    pub pattern: String,
+    #[serde(default)]
+    pub counter: CounterReset,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invoice_prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credit_note_prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_prefix: Option<String>,
+}
+
+impl NumberingPolicy {
+    pub fn new(pattern: &str, counter: CounterReset) -> Result<Self, String> {
+        let numbering_policy = NumberingPolicy {
+            pattern: pattern.to_string(),
+            counter,
+            invoice_prefix: None,
+            credit_note_prefix: None,
+            deposit_prefix: None,
+        };
+
+        numbering_policy.validate()?;
+
+        Ok(numbering_policy)
+    }
+
+    /// References are file names, they must be unique in their series
+    pub fn validate(&self) -> Result<(), String> {
+        if self.get_sequence_width().is_none() {
+            return Err(format!("Numbering pattern {} needs a {{SEQ}} or {{SEQ:n}} placeholder", self.pattern));
+        }
+
+        if self.counter == CounterReset::Yearly
+            && !self.pattern.contains("{YYYY}")
+            && !self.pattern.contains("{YY}")
+        {
+            return Err(format!("Numbering pattern {} needs the year with a yearly counter", self.pattern));
+        }
+
+        if self.pattern.contains(INVALID_REFERENCE_CHARACTERS) {
+            return Err(format!("Numbering pattern {} is not a valid file name", self.pattern));
+        }
+
+        Ok(())
+    }
+
+    pub fn get_prefix(&self, kind: DocumentKind) -> &str {
+        let prefix = match kind {
+            DocumentKind::Invoice => &self.invoice_prefix,
+            DocumentKind::CreditNote => &self.credit_note_prefix,
+            DocumentKind::Deposit => &self.deposit_prefix,
+        };
+
+        match (prefix, kind) {
+            (Some(prefix), _) => prefix,
+            (None, DocumentKind::Invoice) => "FAC",
+            (None, _) => kind.get_prefix(),
+        }
+    }
+
+    /// Two documents dated `date` and `other_date` share the same sequence
+    pub fn is_same_series(&self, date: NaiveDate, other_date: NaiveDate) -> bool {
+        match self.counter {
+            CounterReset::Yearly => date.year() == other_date.year(),
+            CounterReset::Continuous => true,
+        }
+    }
+
+    pub fn format_reference(&self, kind: DocumentKind, date: NaiveDate, sequence: u32) -> String {
+        let width = self.get_sequence_width().unwrap_or(1);
+
+        self.format_reference_with(kind, date, &format!("{:0width$}", sequence, width = width))
+    }
+
+    /// Sequence of a reference following the pattern, for the documents numbered
+    /// outside of the policy like the imported ones
+    pub fn parse_sequence(&self, kind: DocumentKind, date: NaiveDate, reference: &str) -> Option<u32> {
+        let width = self.get_sequence_width()?;
+        let marker = "{SEQ}";
+        let template = self.format_reference_with(kind, date, marker);
+        let (before, after) = template.split_once(marker)?;
+
+        let sequence = reference.strip_prefix(before)?.strip_suffix(after)?;
+        if sequence.len() < width || !sequence.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        sequence.parse().ok()
+    }
+
+    /// Number of the invoice in the series of the policy, its sequence or the one of its reference
+    pub fn get_sequence(&self, invoice: &Invoice) -> Option<u32> {
+        invoice
+            .sequence
+            .or_else(|| self.parse_sequence(invoice.kind, invoice.date, &invoice.get_ref()?))
+    }
+
+    fn format_reference_with(&self, kind: DocumentKind, date: NaiveDate, sequence: &str) -> String {
+        let width = self.get_sequence_width().unwrap_or(1);
+        let sequence_placeholder = match width {
+            1 => "{SEQ}".to_string(),
+            width => format!("{{SEQ:{}}}", width),
+        };
+
+        let reference = self
+            .pattern
+            .replace("{PREFIX}", self.get_prefix(kind))
+            .replace("{YYYY}", &date.format("%Y").to_string())
+            .replace("{YY}", &date.format("%y").to_string())
+            .replace("{MM}", &date.format("%m").to_string())
+            .replace("{DD}", &date.format("%d").to_string())
+            .replace(&sequence_placeholder, sequence);
+
+        if self.pattern.contains("{PREFIX}") || kind.is_invoice() {
+            reference
+        } else {
+            self.get_prefix(kind).to_string() + &reference
+        }
+    }
+
+    fn get_sequence_width(&self) -> Option<usize> {
+        if self.pattern.contains("{SEQ}") {
+            return Some(1);
+        }
+
+        let start = self.pattern.find("{SEQ:")? + "{SEQ:".len();
+        let end = start + self.pattern[start..].find('}')?;
+
+        self.pattern[start..end].parse().ok().filter(|width| (1..=10).contains(width))
+    }
+}
+
+impl fmt::Display for NumberingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} counter)", self.pattern, self.counter)
+    }
+}
+
+/// Reference given outside of a numbering policy, by an import for example
+pub fn validate_reference(reference: &str) -> Result<(), String> {
+    if reference.is_empty() || reference.contains(INVALID_REFERENCE_CHARACTERS) {
+        return Err(format!("Reference {} is not a valid file name", reference));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbering_policy_from_string() {
+        let numbering_policy: NumberingPolicy =
+            serde_yaml::from_str("pattern: FAC-{YYYY}-{SEQ:5}\n").unwrap();
+
+        assert_eq!(numbering_policy.counter, CounterReset::Yearly);
+        assert_eq!(
+            serde_yaml::to_string(&numbering_policy).unwrap(),
+            "pattern: FAC-{YYYY}-{SEQ:5}\ncounter: yearly\n"
+        );
+    }
+
+    #[test]
+    fn numbering_policy_format_reference() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 7).unwrap();
+
+        let numbering_policy = NumberingPolicy::new("FAC-{YYYY}-{SEQ:5}", CounterReset::Yearly).unwrap();
+        assert_eq!(
+            numbering_policy.format_reference(DocumentKind::Invoice, date, 42),
+            "FAC-2024-00042"
+        );
+        assert_eq!(
+            numbering_policy.format_reference(DocumentKind::CreditNote, date, 1),
+            "AVFAC-2024-00001"
+        );
+
+        let mut numbering_policy = NumberingPolicy::new("{PREFIX}{YY}{MM}-{SEQ}", CounterReset::Continuous).unwrap();
+        numbering_policy.deposit_prefix = Some("ACP".to_string());
+        assert_eq!(
+            numbering_policy.format_reference(DocumentKind::Invoice, date, 123),
+            "FAC2403-123"
+        );
+        assert_eq!(
+            numbering_policy.format_reference(DocumentKind::Deposit, date, 7),
+            "ACP2403-7"
+        );
+    }
+
+    #[test]
+    fn numbering_policy_parse_sequence() {
+        let date = NaiveDate::from_ymd_opt(2023, 3, 7).unwrap();
+        let numbering_policy = NumberingPolicy::new("FAC-{YYYY}-{SEQ:5}", CounterReset::Yearly).unwrap();
+
+        assert_eq!(numbering_policy.parse_sequence(DocumentKind::Invoice, date, "FAC-2023-00012"), Some(12));
+        assert_eq!(numbering_policy.parse_sequence(DocumentKind::Invoice, date, "FAC-2023-123456"), Some(123456));
+        assert_eq!(numbering_policy.parse_sequence(DocumentKind::CreditNote, date, "AVFAC-2023-00002"), Some(2));
+        assert_eq!(numbering_policy.parse_sequence(DocumentKind::Invoice, date, "FAC-2022-00012"), None);
+        assert_eq!(numbering_policy.parse_sequence(DocumentKind::Invoice, date, "FAC-2023-12"), None);
+        assert_eq!(numbering_policy.parse_sequence(DocumentKind::Invoice, date, "FAC-2023-0001a"), None);
+        assert_eq!(numbering_policy.parse_sequence(DocumentKind::Invoice, date, "2023030701"), None);
+
+        let numbering_policy = NumberingPolicy::new("{PREFIX}{YY}{MM}-{SEQ}", CounterReset::Continuous).unwrap();
+        assert_eq!(numbering_policy.parse_sequence(DocumentKind::Invoice, date, "FAC2303-7"), Some(7));
+    }
+
+    #[test]
+    fn numbering_policy_validation() {
+        assert!(NumberingPolicy::new("FAC-{YYYY}", CounterReset::Yearly).is_err());
+        assert!(NumberingPolicy::new("FAC-{SEQ:5}", CounterReset::Yearly).is_err());
+        assert!(NumberingPolicy::new("FAC-{SEQ:5}", CounterReset::Continuous).is_ok());
+        assert!(NumberingPolicy::new("FAC/{YYYY}/{SEQ}", CounterReset::Yearly).is_err());
+        assert!(NumberingPolicy::new("FAC-{YYYY}-{SEQ:x}", CounterReset::Yearly).is_err());
+
+        assert!(validate_reference("F-2023-12").is_ok());
+        assert!(validate_reference("").is_err());
+        assert!(validate_reference("..\\x").is_err());
+    }
+
+    #[test]
+    fn numbering_policy_series() {
+        let yearly = NumberingPolicy::new("{YYYY}-{SEQ}", CounterReset::Yearly).unwrap();
+        let continuous = NumberingPolicy::new("{YYYY}-{SEQ}", CounterReset::Continuous).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let next_year = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+        assert!(!yearly.is_same_series(date, next_year));
+        assert!(continuous.is_same_series(date, next_year));
+    }
+}
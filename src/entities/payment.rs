@@ -0,0 +1,141 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::amount::Amount;
+use crate::entities::invoice_date::{deser_invoice_date, ser_invoice_date};
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentMethod {
+    Transfer,
+    Check,
+    Card,
+    Cash,
+    Other,
+}
+
+impl PaymentMethod {
+    pub fn all_methods() -> Vec<PaymentMethod> {
+        vec![
+            PaymentMethod::Transfer,
+            PaymentMethod::Check,
+            PaymentMethod::Card,
+            PaymentMethod::Cash,
+            PaymentMethod::Other,
+        ]
+    }
+}
+
+impl FromStr for PaymentMethod {
+    type Err = String;
+
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
+        PaymentMethod::all_methods()
+            .into_iter()
+            .find(|payment_method| payment_method.to_string() == method.to_lowercase())
+            .ok_or_else(|| format!("Invalid payment method: {} (transfer, check, card, cash or other)", method))
+    }
+}
+
+impl fmt::Display for PaymentMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaymentMethod::Transfer => write!(f, "transfer"),
+            PaymentMethod::Check => write!(f, "check"),
+            PaymentMethod::Card => write!(f, "card"),
+            PaymentMethod::Cash => write!(f, "cash"),
+            PaymentMethod::Other => write!(f, "other"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Payment {
+    #[serde(
+    serialize_with = "ser_invoice_date",
+    deserialize_with = "deser_invoice_date"
+    )]
+    pub date: NaiveDate,
+    pub amount: Amount,
+    pub method: PaymentMethod,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+}
+
+impl fmt::Display for Payment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {} € by {}", self.date, self.amount, self.method)?;
+
+        if let Some(reference) = &self.reference {
+            write!(f, " ({})", reference)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Payment {
+        pub fn generate_simple_payment_example(amount: &str) -> Payment {
+            Payment {
+                date: NaiveDate::from_ymd_opt(2015, 3, 20).unwrap(),
+                amount: Amount::new(amount).unwrap(),
+                method: PaymentMethod::Transfer,
+                reference: Some("VIR-001".to_string()),
+            }
+        }
+    }
+
+    #[test]
+    fn payment_to_string() {
+        let payment = Payment::generate_simple_payment_example("350");
+
+        let yaml = serde_yaml::to_string(&payment).unwrap();
+
+        assert_eq!(
+            yaml,
+            "date:\n".to_owned()
+                + "  day: '20'\n"
+                + "  month: '03'\n"
+                + "  year: '2015'\n"
+                + "amount: 350.0\n"
+                + "method: transfer\n"
+                + "reference: VIR-001\n"
+        );
+    }
+
+    #[test]
+    fn payment_from_string_without_reference() {
+        let yaml = "date:\n".to_owned()
+            + "  day: '20'\n"
+            + "  month: '03'\n"
+            + "  year: '2015'\n"
+            + "amount: 120.5\n"
+            + "method: check\n";
+
+        let payment: Payment = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(payment.amount, Amount::new("120.50").unwrap());
+        assert_eq!(payment.method, PaymentMethod::Check);
+        assert_eq!(payment.reference, None);
+    }
+
+    #[test]
+    fn payment_method_from_str() {
+        assert_eq!(PaymentMethod::from_str("Card"), Ok(PaymentMethod::Card));
+        assert!(PaymentMethod::from_str("bitcoin").is_err());
+    }
+
+    #[test]
+    fn payment_display() {
+        let payment = Payment::generate_simple_payment_example("350");
+
+        assert_eq!(payment.to_string(), "2015-03-20 - 350.00 € by transfer (VIR-001)");
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::entities::amount::Amount;
+
+#[derive(Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+    Unpaid,
+    Partial,
+    Paid,
+    Overdue,
+}
+
+impl PaymentStatus {
+    pub fn new(total_due: Amount, paid: Amount, due_date: NaiveDate, today: NaiveDate) -> Self {
+        if paid >= total_due {
+            PaymentStatus::Paid
+        } else if today > due_date {
+            PaymentStatus::Overdue
+        } else if paid > Amount::ZERO {
+            PaymentStatus::Partial
+        } else {
+            PaymentStatus::Unpaid
+        }
+    }
+
+    pub fn all_statuses() -> Vec<PaymentStatus> {
+        vec![PaymentStatus::Unpaid, PaymentStatus::Partial, PaymentStatus::Paid, PaymentStatus::Overdue]
+    }
+
+    pub fn is_paid(&self) -> bool {
+        *self == PaymentStatus::Paid
+    }
+}
+
+impl FromStr for PaymentStatus {
+    type Err = String;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        PaymentStatus::all_statuses()
+            .into_iter()
+            .find(|payment_status| payment_status.to_string() == status.to_lowercase())
+            .ok_or_else(|| format!("Invalid payment status: {} (unpaid, partial, paid or overdue)", status))
+    }
+}
+
+impl fmt::Display for PaymentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaymentStatus::Unpaid => write!(f, "unpaid"),
+            PaymentStatus::Partial => write!(f, "partial"),
+            PaymentStatus::Paid => write!(f, "paid"),
+            PaymentStatus::Overdue => write!(f, "overdue"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payment_status() {
+        let total_due = Amount::new("100").unwrap();
+        let due_date = NaiveDate::from_ymd_opt(2020, 1, 31).unwrap();
+        let before_due_date = NaiveDate::from_ymd_opt(2020, 1, 15).unwrap();
+        let after_due_date = NaiveDate::from_ymd_opt(2020, 2, 1).unwrap();
+
+        assert_eq!(
+            PaymentStatus::new(total_due, Amount::ZERO, due_date, before_due_date),
+            PaymentStatus::Unpaid
+        );
+        assert_eq!(
+            PaymentStatus::new(total_due, Amount::new("40").unwrap(), due_date, due_date),
+            PaymentStatus::Partial
+        );
+        assert_eq!(
+            PaymentStatus::new(total_due, Amount::new("40").unwrap(), due_date, after_due_date),
+            PaymentStatus::Overdue
+        );
+        assert_eq!(
+            PaymentStatus::new(total_due, Amount::new("100").unwrap(), due_date, after_due_date),
+            PaymentStatus::Paid
+        );
+        assert_eq!(
+            PaymentStatus::new(-total_due, Amount::ZERO, due_date, after_due_date),
+            PaymentStatus::Paid
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Delay granted to the customer to pay an invoice.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[serde(tag = "terms", rename_all = "snake_case")]
+pub enum PaymentTerms {
+    /// Paiement à réception : due on the invoice date
+    OnReceipt,
+    /// Due `days` days after the invoice date
+    Net { days: u64 },
+    /// Due at the end of the month reached `days` days after the invoice date
+    EndOfMonth { days: u64 },
+}
+
+impl PaymentTerms {
+    pub const DEFAULT_DAYS: u64 = 30;
+
+    pub fn get_due_date(&self, invoice_date: NaiveDate) -> NaiveDate {
+        match self {
+            PaymentTerms::OnReceipt => invoice_date,
+            PaymentTerms::Net { days } => invoice_date + Days::new(*days),
+            PaymentTerms::EndOfMonth { days } => {
+                let date = invoice_date + Days::new(*days);
+
+                (date.with_day(1).unwrap() + Months::new(1)).pred_opt().unwrap()
+            }
+        }
+    }
+
+    /// Terms written as parsed by `from_str`
+    pub fn get_code(&self) -> String {
+        match self {
+            PaymentTerms::OnReceipt => "on_receipt".to_string(),
+            PaymentTerms::Net { days } => format!("net:{}", days),
+            PaymentTerms::EndOfMonth { days } => format!("end_of_month:{}", days),
+        }
+    }
+}
+
+impl Default for PaymentTerms {
+    fn default() -> Self {
+        PaymentTerms::Net { days: Self::DEFAULT_DAYS }
+    }
+}
+
+/// Terms written `on_receipt`, `net:DAYS` or `end_of_month:DAYS`
+impl FromStr for PaymentTerms {
+    type Err = String;
+
+    fn from_str(terms: &str) -> Result<Self, Self::Err> {
+        let parse_days = |days: &str| {
+            days.trim().parse::<u64>().map_err(|_| format!("Invalid number of days: {}", days))
+        };
+
+        match terms.trim().split_once(':') {
+            None if terms.trim() == "on_receipt" => Ok(PaymentTerms::OnReceipt),
+            Some(("net", days)) => Ok(PaymentTerms::Net { days: parse_days(days)? }),
+            Some(("end_of_month", days)) => Ok(PaymentTerms::EndOfMonth { days: parse_days(days)? }),
+            _ => Err(format!(
+                "Invalid payment terms: {} (on_receipt, net:DAYS or end_of_month:DAYS)",
+                terms
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PaymentTerms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaymentTerms::OnReceipt => write!(f, "À réception"),
+            PaymentTerms::Net { days } => write!(f, "{} jours", days),
+            PaymentTerms::EndOfMonth { days } => write!(f, "{} jours fin de mois", days),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payment_terms_to_string() {
+        assert_eq!(
+            serde_yaml::to_string(&PaymentTerms::OnReceipt).unwrap(),
+            "terms: on_receipt\n"
+        );
+        assert_eq!(
+            serde_yaml::to_string(&PaymentTerms::EndOfMonth { days: 45 }).unwrap(),
+            "terms: end_of_month\ndays: 45\n"
+        );
+
+        let payment_terms: PaymentTerms = serde_yaml::from_str("terms: net\ndays: 60\n").unwrap();
+        assert_eq!(payment_terms, PaymentTerms::Net { days: 60 });
+    }
+
+    #[test]
+    fn test_payment_terms_due_date() {
+        let invoice_date = NaiveDate::from_ymd_opt(2024, 1, 20).unwrap();
+
+        assert_eq!(PaymentTerms::OnReceipt.get_due_date(invoice_date), invoice_date);
+        assert_eq!(
+            PaymentTerms::default().get_due_date(invoice_date),
+            NaiveDate::from_ymd_opt(2024, 2, 19).unwrap()
+        );
+        assert_eq!(
+            PaymentTerms::EndOfMonth { days: 30 }.get_due_date(invoice_date),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        assert_eq!(
+            PaymentTerms::EndOfMonth { days: 45 }.get_due_date(invoice_date),
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_payment_terms_from_str() {
+        assert_eq!(PaymentTerms::from_str("on_receipt"), Ok(PaymentTerms::OnReceipt));
+        assert_eq!(PaymentTerms::from_str("net:60"), Ok(PaymentTerms::Net { days: 60 }));
+        assert_eq!(
+            PaymentTerms::from_str("end_of_month:45"),
+            Ok(PaymentTerms::EndOfMonth { days: 45 })
+        );
+        assert!(PaymentTerms::from_str("net").is_err());
+        assert!(PaymentTerms::from_str("net:soon").is_err());
+
+        for payment_terms in [PaymentTerms::OnReceipt, PaymentTerms::default(), PaymentTerms::EndOfMonth { days: 45 }] {
+            assert_eq!(PaymentTerms::from_str(&payment_terms.get_code()), Ok(payment_terms));
+        }
+    }
+
+    #[test]
+    fn test_payment_terms_display() {
+        assert_eq!(PaymentTerms::Net { days: 30 }.to_string(), "30 jours");
+        assert_eq!(PaymentTerms::EndOfMonth { days: 45 }.to_string(), "45 jours fin de mois");
+    }
+}
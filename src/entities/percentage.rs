@@ -0,0 +1,109 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::entities::fixed_point::{format_fixed_point, parse_fixed_point};
+
+/// Rate stored as an exact number of basis points (1 % = 100).
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+pub struct Percentage(i64);
+
+impl Percentage {
+    pub const SCALE: u32 = 2;
+
+    pub fn new(percentage: &str) -> Result<Self, String> {
+        parse_fixed_point(percentage.trim().trim_end_matches('%'), Self::SCALE)
+            .map(Percentage)
+            .map_err(|_| format!("Invalid percentage: {}", percentage))
+    }
+
+    pub fn basis_points(&self) -> i64 {
+        self.0
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / 10_f64.powi(Self::SCALE as i32)
+    }
+}
+
+impl FromStr for Percentage {
+    type Err = String;
+
+    fn from_str(percentage: &str) -> Result<Self, Self::Err> {
+        Percentage::new(percentage)
+    }
+}
+
+impl fmt::Display for Percentage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percentage = format_fixed_point(self.0, Self::SCALE);
+        write!(f, "{} %", percentage.trim_end_matches('0').trim_end_matches('.'))
+    }
+}
+
+impl Serialize for Percentage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(self.to_f64())
+    }
+}
+
+struct PercentageVisitor;
+
+impl Visitor<'_> for PercentageVisitor {
+    type Value = Percentage;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal percentage")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Percentage, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Percentage, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Percentage, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Percentage, E> {
+        Percentage::new(value).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Percentage {
+    fn deserialize<D>(deserializer: D) -> Result<Percentage, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(PercentageVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_percentage() {
+        assert_eq!(Percentage::new("21.2").unwrap().basis_points(), 2_120);
+        assert_eq!(Percentage::new("12,3 %").unwrap().basis_points(), 1_230);
+        assert_eq!(Percentage::new("x"), Err("Invalid percentage: x".to_string()));
+        assert_eq!(Percentage::new("21.2").unwrap().to_string(), "21.2 %");
+    }
+
+    #[test]
+    fn test_percentage_yaml() {
+        assert_eq!(serde_yaml::to_string(&Percentage::new("26.1").unwrap()).unwrap(), "26.1\n");
+
+        let percentage: Percentage = serde_yaml::from_str("12").unwrap();
+        assert_eq!(percentage.basis_points(), 1_200);
+    }
+}
@@ -1,14 +1,80 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
+use crate::entities::activity::Activity;
+use crate::entities::amount::Amount;
+use crate::entities::quantity::Quantity;
+use crate::entities::vat_rate::VatRate;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Product {
     pub description: String,
-    pub quantity: f32,
-    pub price: f32,
+    pub quantity: Quantity,
+    pub price: Amount,
+    /// Use the default rate of the settings when not set, always set once the invoice is issued
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vat: Option<VatRate>,
+    /// Use the default activity of the settings when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activity: Option<Activity>,
 }
 impl Product {
-    pub fn get_total_price(&self) -> f32 {
-        self.quantity * self.price
+    pub fn get_total_price(&self) -> Amount {
+        self.price * self.quantity
+    }
+
+    pub fn get_vat_rate<'a>(&'a self, default_vat_rate: &'a VatRate) -> &'a VatRate {
+        self.vat.as_ref().unwrap_or(default_vat_rate)
+    }
+
+    pub fn get_activity(&self, default_activity: Activity) -> Activity {
+        self.activity.unwrap_or(default_activity)
+    }
+
+    /// Line to credit for `amount` of this line, a discount line stays negative.
+    /// The amount is between 0 and the total of the line without its sign.
+    pub fn get_partial_credit(&self, amount: Amount) -> Result<Product, String> {
+        let total_price = self.get_total_price();
+
+        if amount <= Amount::ZERO || amount > total_price.abs() {
+            return Err(format!("The amount must be between 0 and {}", total_price.abs()));
+        }
+
+        if amount == total_price.abs() {
+            return Ok(self.clone());
+        }
+
+        Ok(Product {
+            description: self.description.to_owned(),
+            quantity: Quantity::from(1),
+            price: if total_price < Amount::ZERO { -amount } else { amount },
+            vat: self.vat.clone(),
+            activity: self.activity,
+        })
+    }
+}
+
+/// Product line written `description;quantity;price`, with an optional `;vat rate`
+/// and an optional `;activity`. An empty field keeps the default of the settings.
+impl FromStr for Product {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = line.split(';').map(str::trim).collect();
+
+        let optional_field = |index: usize| fields.get(index).filter(|field| !field.is_empty());
+
+        match fields.as_slice() {
+            [description, quantity, price, ..] if fields.len() <= 5 => Ok(Product {
+                description: description.to_string(),
+                quantity: Quantity::new(quantity)?,
+                price: Amount::new(price)?,
+                vat: optional_field(3).map(|vat| VatRate::from_str(vat)).transpose()?,
+                activity: optional_field(4).map(|activity| Activity::from_str(activity)).transpose()?,
+            }),
+            _ => Err(format!("Invalid product line: {} (description;quantity;price[;vat rate][;activity])", line)),
+        }
     }
 }
 
@@ -20,8 +86,10 @@ mod tests {
     fn product_to_string() {
         let product_example = Product {
             description: "Product example".to_string(),
-            quantity: 1.0,
-            price: 350.0,
+            quantity: Quantity::new("1").unwrap(),
+            price: Amount::new("350").unwrap(),
+            vat: None,
+            activity: None,
         };
 
         let yaml = serde_yaml::to_string(&product_example).unwrap();
@@ -43,52 +111,136 @@ mod tests {
 
         assert_eq!("Product example".to_string(), product_example.description);
 
-        assert_eq!(350.0, product_example.price);
+        assert_eq!(Amount::new("350").unwrap(), product_example.price);
 
-        assert_eq!(1.0, product_example.quantity);
+        assert_eq!(Quantity::from(1), product_example.quantity);
     }
 
     #[test]
     fn product_total_price_simple() {
         let product_example = Product {
             description: "Product example".to_string(),
-            quantity: 1.0,
-            price: 350.0,
+            quantity: Quantity::new("1").unwrap(),
+            price: Amount::new("350").unwrap(),
+            vat: None,
+            activity: None,
         };
 
-        assert_eq!(product_example.get_total_price(), 350.0);
+        assert_eq!(product_example.get_total_price(), Amount::new("350").unwrap());
     }
 
     #[test]
     fn product_total_price_zero() {
         let product_example = Product {
             description: "Product example".to_string(),
-            quantity: 0.0,
-            price: 350.0,
+            quantity: Quantity::new("0").unwrap(),
+            price: Amount::new("350").unwrap(),
+            vat: None,
+            activity: None,
         };
 
-        assert_eq!(product_example.get_total_price(), 0.0);
+        assert_eq!(product_example.get_total_price(), Amount::ZERO);
     }
 
     #[test]
     fn product_total_price_multiple() {
         let product_example = Product {
             description: "Product example".to_string(),
-            quantity: 14.0,
-            price: 100_000.0,
+            quantity: Quantity::new("14").unwrap(),
+            price: Amount::new("100000").unwrap(),
+            vat: None,
+            activity: None,
         };
 
-        assert_eq!(product_example.get_total_price(), 1_400_000.0);
+        assert_eq!(product_example.get_total_price(), Amount::new("1400000").unwrap());
     }
 
     #[test]
     fn product_total_price_half() {
         let product_example = Product {
             description: "Product example".to_string(),
-            quantity: 1.5,
-            price: 350.0,
+            quantity: Quantity::new("1.5").unwrap(),
+            price: Amount::new("350").unwrap(),
+            vat: None,
+            activity: None,
         };
 
-        assert_eq!(product_example.get_total_price(), 525.0);
+        assert_eq!(product_example.get_total_price(), Amount::new("525").unwrap());
+    }
+
+    #[test]
+    fn product_total_price_rounding() {
+        let product_example = Product {
+            description: "Product example".to_string(),
+            quantity: Quantity::new("0.333").unwrap(),
+            price: Amount::new("10.05").unwrap(),
+            vat: None,
+            activity: None,
+        };
+
+        assert_eq!(product_example.get_total_price(), Amount::new("3.35").unwrap());
+    }
+
+    #[test]
+    fn product_from_legacy_float_string() {
+        let yaml_product_example = "description: Product example\n".to_owned()
+            + "quantity: 3.0\n"
+            + "price: 349.99\n";
+
+        let product_example: Product =
+            serde_yaml::from_str(&yaml_product_example).unwrap();
+
+        assert_eq!(product_example.get_total_price(), Amount::new("1049.97").unwrap());
+    }
+
+    #[test]
+    fn product_partial_credit() {
+        let product = Product::from_str("Consulting day;2;450").unwrap();
+
+        let credit = product.get_partial_credit(Amount::new("100").unwrap()).unwrap();
+        assert_eq!(credit.quantity, Quantity::from(1));
+        assert_eq!(credit.get_total_price(), Amount::new("100").unwrap());
+        assert_eq!(product.get_partial_credit(Amount::new("900").unwrap()).unwrap().quantity, Quantity::from(2));
+
+        assert!(product.get_partial_credit(Amount::ZERO).is_err());
+        assert!(product.get_partial_credit(Amount::new("900.01").unwrap()).is_err());
+        assert!(product.get_partial_credit(Amount::new("-100").unwrap()).is_err());
+
+        let discount = Product::from_str("Discount;1;-50").unwrap();
+
+        let credit = discount.get_partial_credit(Amount::new("20").unwrap()).unwrap();
+        assert_eq!(credit.get_total_price(), Amount::new("-20").unwrap());
+        assert_eq!(
+            discount.get_partial_credit(Amount::new("50").unwrap()).unwrap().get_total_price(),
+            Amount::new("-50").unwrap()
+        );
+        assert_eq!(
+            discount.get_partial_credit(Amount::new("60").unwrap()).err(),
+            Some("The amount must be between 0 and 50.00".to_string())
+        );
+
+        let free_line = Product::from_str("Setup;1;0").unwrap();
+        assert!(free_line.get_partial_credit(Amount::ZERO).is_err());
+    }
+
+    #[test]
+    fn product_from_line() {
+        let product = Product::from_str("Consulting day; 2 ;450").unwrap();
+
+        assert_eq!(product.description, "Consulting day");
+        assert_eq!(product.quantity, Quantity::from(2));
+        assert_eq!(product.price, Amount::new("450").unwrap());
+        assert_eq!(product.vat, None);
+
+        let product = Product::from_str("Book;1;10;5.5").unwrap();
+        assert_eq!(product.vat, Some(VatRate::Reduced));
+
+        let product = Product::from_str("Book;1;10;;sale").unwrap();
+        assert_eq!(product.vat, None);
+        assert_eq!(product.activity, Some(Activity::Sale));
+
+        assert!(Product::from_str("Book;1").is_err());
+        assert!(Product::from_str("Book;one;10").is_err());
+        assert!(Product::from_str("Book;1;10;5.5;sale;extra").is_err());
     }
 }
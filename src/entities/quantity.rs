@@ -0,0 +1,137 @@
+use std::fmt;
+use std::ops::Neg;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::entities::fixed_point::{format_fixed_point, parse_fixed_point};
+
+/// Product quantity stored as an exact number of thousandths.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+pub struct Quantity(i64);
+
+impl Quantity {
+    pub const ZERO: Quantity = Quantity(0);
+    pub const SCALE: u32 = 3;
+    pub const UNIT: i64 = 1_000;
+
+    pub fn new(quantity: &str) -> Result<Self, String> {
+        parse_fixed_point(quantity, Self::SCALE)
+            .map(Quantity)
+            .map_err(|_| format!("Invalid quantity: {}", quantity))
+    }
+
+    pub fn thousandths(&self) -> i64 {
+        self.0
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::UNIT as f64
+    }
+}
+
+impl From<u32> for Quantity {
+    fn from(quantity: u32) -> Quantity {
+        Quantity(quantity as i64 * Self::UNIT)
+    }
+}
+
+impl Neg for Quantity {
+    type Output = Quantity;
+
+    fn neg(self) -> Quantity {
+        Quantity(-self.0)
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = String;
+
+    fn from_str(quantity: &str) -> Result<Self, Self::Err> {
+        Quantity::new(quantity)
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let quantity = format_fixed_point(self.0, Self::SCALE);
+        write!(f, "{}", quantity.trim_end_matches('0').trim_end_matches('.'))
+    }
+}
+
+impl Serialize for Quantity {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(self.to_f64())
+    }
+}
+
+struct QuantityVisitor;
+
+impl Visitor<'_> for QuantityVisitor {
+    type Value = Quantity;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal quantity")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Quantity, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Quantity, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Quantity, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Quantity, E> {
+        Quantity::new(value).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Quantity {
+    fn deserialize<D>(deserializer: D) -> Result<Quantity, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(QuantityVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_quantity() {
+        assert_eq!(Quantity::new("1").unwrap(), Quantity::from(1));
+        assert_eq!(Quantity::new("1.5").unwrap().thousandths(), 1_500);
+        assert_eq!(Quantity::new("0.0005").unwrap().thousandths(), 1);
+        assert_eq!(Quantity::new("x"), Err("Invalid quantity: x".to_string()));
+    }
+
+    #[test]
+    fn test_quantity_display() {
+        assert_eq!(Quantity::from(7).to_string(), "7");
+        assert_eq!(Quantity::new("1.5").unwrap().to_string(), "1.5");
+        assert_eq!(Quantity::new("0.125").unwrap().to_string(), "0.125");
+        assert_eq!(Quantity::new("0").unwrap().to_string(), "0");
+    }
+
+    #[test]
+    fn test_quantity_yaml() {
+        assert_eq!(serde_yaml::to_string(&Quantity::from(1)).unwrap(), "1.0\n");
+
+        let quantity: Quantity = serde_yaml::from_str("1.5").unwrap();
+        assert_eq!(quantity.thousandths(), 1_500);
+
+        let quantity: Quantity = serde_yaml::from_str("3").unwrap();
+        assert_eq!(quantity, Quantity::from(3));
+    }
+}
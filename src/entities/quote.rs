@@ -0,0 +1,421 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::amount::Amount;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice::{Invoice, InvoiceDayId};
+use crate::entities::invoice_date::{deser_invoice_date, ser_invoice_date};
+use crate::entities::invoice_totals::InvoiceTotals;
+use crate::entities::product::Product;
+use crate::entities::quantity::Quantity;
+use crate::entities::vat_rate::VatRate;
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteStatus {
+    #[default]
+    Pending,
+    Accepted,
+    Refused,
+}
+
+impl fmt::Display for QuoteStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuoteStatus::Pending => write!(f, "pending"),
+            QuoteStatus::Accepted => write!(f, "accepted"),
+            QuoteStatus::Refused => write!(f, "refused"),
+        }
+    }
+}
+
+/// Devis : proposal sent to a customer, converted to an invoice once accepted
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Quote {
+    #[serde(
+    serialize_with = "ser_invoice_date",
+    deserialize_with = "deser_invoice_date"
+    )]
+    pub date: NaiveDate,
+    #[serde(
+    serialize_with = "ser_invoice_date",
+    deserialize_with = "deser_invoice_date"
+    )]
+    pub validity_date: NaiveDate,
+    pub customer_id: String,
+    pub title: String,
+    pub quote_day_id: Option<InvoiceDayId>,
+    pub products: Vec<Product>,
+    #[serde(default)]
+    pub status: QuoteStatus,
+    /// Reference of the invoice created when the quote was accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invoice_ref: Option<String>,
+}
+
+impl Quote {
+    pub const PREFIX: &'static str = "DEV";
+    pub const DEFAULT_VALIDITY_DAYS: u64 = 30;
+
+    pub fn get_ref(&self) -> Option<String> {
+        self.quote_day_id.as_ref().map(|quote_day_id| {
+            Self::PREFIX.to_string() + &self.date.format("%Y%m%d").to_string() + &(quote_day_id.to_string())
+        })
+    }
+
+    pub fn get_total_price(&self) -> Amount {
+        self.products.iter().map(Product::get_total_price).sum()
+    }
+
+    pub fn get_totals(&self, default_vat_rate: &VatRate) -> InvoiceTotals {
+        InvoiceTotals::from_products(&self.products, default_vat_rate)
+    }
+
+    /// A pending quote can no longer be accepted after its validity date
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.status == QuoteStatus::Pending && today > self.validity_date
+    }
+
+    /// Final invoice billing all the products of the quote, minus the deposits of `all_invoices`
+    /// net of their credit notes
+    pub fn create_invoice(&self, date: NaiveDate, due_date: NaiveDate, all_invoices: &[Invoice]) -> Invoice {
+        let deductions = self.get_deposits(all_invoices).into_iter().flat_map(|deposit| {
+            get_net_deposit_lines(deposit, all_invoices).into_iter().map(|line| Product {
+                description: format!("Acompte {} déjà facturé", deposit.get_ref().unwrap()),
+                price: -line.price,
+                ..line
+            })
+        });
+
+        Invoice {
+            date,
+            due_date: Some(due_date),
+            customer_id: self.customer_id.to_owned(),
+            title: self.title.to_owned(),
+            invoice_day_id: None,
+            products: self.products.iter().cloned().chain(deductions).collect(),
+            kind: DocumentKind::Invoice,
+            credited_invoice_ref: None,
+            quote_ref: self.get_ref(),
+            reference: None,
+            sequence: None,
+        }
+    }
+
+    /// Deposit invoice of `percent` % of the quote, with one line by VAT rate
+    pub fn create_deposit(
+        &self,
+        date: NaiveDate,
+        due_date: NaiveDate,
+        percent: u32,
+        default_vat_rate: &VatRate,
+    ) -> Invoice {
+        let products = self
+            .get_totals(default_vat_rate)
+            .vat_lines
+            .into_iter()
+            .map(|vat_line| Product {
+                description: format!("Acompte de {} % sur devis {}", percent, self.get_ref().unwrap()),
+                quantity: Quantity::from(1),
+                price: vat_line.base.get_percentage(i64::from(percent) * 100),
+                vat: Some(vat_line.rate),
+                activity: None,
+            })
+            .collect();
+
+        Invoice {
+            date,
+            due_date: Some(due_date),
+            customer_id: self.customer_id.to_owned(),
+            title: self.title.to_owned(),
+            invoice_day_id: None,
+            products,
+            kind: DocumentKind::Deposit,
+            credited_invoice_ref: None,
+            quote_ref: self.get_ref(),
+            reference: None,
+            sequence: None,
+        }
+    }
+
+    /// Deposit invoices of `all_invoices` billed for this quote, the ones fully cancelled by credit notes are left out
+    pub fn get_deposits<'a>(&self, all_invoices: &'a [Invoice]) -> Vec<&'a Invoice> {
+        let quote_ref = self.get_ref();
+
+        all_invoices
+            .iter()
+            .filter(|invoice| {
+                invoice.kind == DocumentKind::Deposit
+                    && quote_ref.is_some()
+                    && invoice.quote_ref == quote_ref
+                    && !get_net_deposit_lines(invoice, all_invoices).is_empty()
+            })
+            .collect()
+    }
+
+    /// HT amount of the quote not yet billed by its deposits, net of their credit notes
+    pub fn get_remaining_for_deposits(&self, all_invoices: &[Invoice]) -> Amount {
+        self.get_total_price()
+            - self
+                .get_deposits(all_invoices)
+                .into_iter()
+                .flat_map(|deposit| get_net_deposit_lines(deposit, all_invoices))
+                .map(|line| line.price)
+                .sum()
+    }
+}
+
+/// HT amount of the deposit still billed for each VAT rate once its credit notes are deduced
+fn get_net_deposit_lines(deposit: &Invoice, all_invoices: &[Invoice]) -> Vec<Product> {
+    let mut lines: Vec<Product> = vec![];
+
+    deposit
+        .products
+        .iter()
+        .chain(deposit.get_credit_notes(all_invoices).into_iter().flat_map(|credit_note| credit_note.products.iter()))
+        .for_each(|product| match lines.iter_mut().find(|line| line.vat == product.vat) {
+            Some(line) => line.price += product.get_total_price(),
+            None => lines.push(Product {
+                description: product.description.to_owned(),
+                quantity: Quantity::from(1),
+                price: product.get_total_price(),
+                vat: product.vat.clone(),
+                activity: product.activity,
+            }),
+        });
+
+    lines.retain(|line| line.price > Amount::ZERO);
+
+    lines
+}
+
+impl fmt::Display for Quote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} - {} - {} € - {}",
+            self.get_ref().unwrap(),
+            self.customer_id,
+            self.get_total_price(),
+            self.status
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Quote {
+        pub fn generate_simple_quote_example() -> Quote {
+            Quote {
+                date: NaiveDate::from_ymd_opt(2015, 3, 14).unwrap(),
+                validity_date: NaiveDate::from_ymd_opt(2015, 4, 13).unwrap(),
+                customer_id: "king".to_string(),
+                title: "Test quote for simple customer".to_string(),
+                quote_day_id: Some(InvoiceDayId::new("01").unwrap()),
+                products: vec![Product {
+                    description: "Product example".to_string(),
+                    quantity: Quantity::from(2),
+                    price: Amount::new("350").unwrap(),
+                    vat: None,
+                    activity: None,
+                }],
+                status: QuoteStatus::Pending,
+                invoice_ref: None,
+            }
+        }
+    }
+
+    #[test]
+    fn quote_to_string() {
+        let quote = Quote::generate_simple_quote_example();
+
+        let yaml = serde_yaml::to_string(&quote).unwrap();
+
+        assert_eq!(
+            yaml,
+            "date:\n".to_owned()
+                + "  day: '14'\n"
+                + "  month: '03'\n"
+                + "  year: '2015'\n"
+                + "validity_date:\n"
+                + "  day: '13'\n"
+                + "  month: '04'\n"
+                + "  year: '2015'\n"
+                + "customer_id: king\n"
+                + "title: Test quote for simple customer\n"
+                + "quote_day_id: '01'\n"
+                + "products:\n"
+                + "- description: Product example\n"
+                + "  quantity: 2.0\n"
+                + "  price: 350.0\n"
+                + "status: pending\n"
+        );
+
+        let quote_from_yaml: Quote = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(quote_from_yaml.get_ref(), quote.get_ref());
+        assert_eq!(quote_from_yaml.status, QuoteStatus::Pending);
+    }
+
+    #[test]
+    fn quote_reference_and_display() {
+        let quote = Quote::generate_simple_quote_example();
+
+        assert_eq!(quote.get_ref(), Some("DEV2015031401".to_string()));
+        assert_eq!(quote.to_string(), "DEV2015031401 - king - 700.00 € - pending");
+    }
+
+    #[test]
+    fn quote_expiration() {
+        let mut quote = Quote::generate_simple_quote_example();
+
+        assert!(!quote.is_expired(NaiveDate::from_ymd_opt(2015, 4, 13).unwrap()));
+        assert!(quote.is_expired(NaiveDate::from_ymd_opt(2015, 4, 14).unwrap()));
+
+        quote.status = QuoteStatus::Accepted;
+        assert!(!quote.is_expired(NaiveDate::from_ymd_opt(2015, 4, 14).unwrap()));
+    }
+
+    #[test]
+    fn quote_to_invoice() {
+        let quote = Quote::generate_simple_quote_example();
+        let date = NaiveDate::from_ymd_opt(2015, 3, 20).unwrap();
+        let due_date = NaiveDate::from_ymd_opt(2015, 4, 19).unwrap();
+
+        let invoice = quote.create_invoice(date, due_date, &[]);
+
+        assert_eq!(invoice.date, date);
+        assert_eq!(invoice.due_date, Some(due_date));
+        assert_eq!(invoice.title, quote.title);
+        assert_eq!(invoice.kind, DocumentKind::Invoice);
+        assert_eq!(invoice.quote_ref, Some("DEV2015031401".to_string()));
+        assert_eq!(invoice.get_total_price(), quote.get_total_price());
+        assert_eq!(invoice.invoice_day_id, None);
+    }
+
+    #[test]
+    fn quote_deposit_and_final_invoice() {
+        let mut quote = Quote::generate_simple_quote_example();
+        quote.products.push(Product {
+            description: "Book".to_string(),
+            quantity: Quantity::from(1),
+            price: Amount::new("100").unwrap(),
+            vat: Some(VatRate::Reduced),
+            activity: None,
+        });
+        let date = NaiveDate::from_ymd_opt(2015, 3, 20).unwrap();
+
+        let deposit = Invoice {
+            invoice_day_id: Some(InvoiceDayId::new("01").unwrap()),
+            ..quote.create_deposit(date, date, 30, &VatRate::Normal)
+        };
+
+        assert_eq!(deposit.kind, DocumentKind::Deposit);
+        assert_eq!(deposit.get_ref(), Some("AC2015032001".to_string()));
+        assert_eq!(deposit.products.len(), 2);
+        assert_eq!(deposit.products[0].price, Amount::new("210").unwrap());
+        assert_eq!(deposit.products[0].vat, Some(VatRate::Normal));
+        assert_eq!(deposit.products[1].price, Amount::new("30").unwrap());
+        assert_eq!(deposit.products[1].vat, Some(VatRate::Reduced));
+        assert_eq!(deposit.get_total_price(), Amount::new("240").unwrap());
+
+        let all_invoices = vec![deposit, Invoice::generate_simple_invoice_example()];
+        assert_eq!(quote.get_deposits(&all_invoices).len(), 1);
+
+        let final_invoice = quote.create_invoice(date, date, &all_invoices);
+
+        assert_eq!(final_invoice.products.len(), 4);
+        assert_eq!(final_invoice.products[2].description, "Acompte AC2015032001 déjà facturé");
+        assert_eq!(final_invoice.get_total_price(), Amount::new("560").unwrap());
+
+        let totals = final_invoice.get_totals(&VatRate::Normal);
+        assert_eq!(totals.vat_lines[0].base, Amount::new("490").unwrap());
+        assert_eq!(totals.vat_lines[1].base, Amount::new("70").unwrap());
+    }
+
+    fn generate_deposit(quote: &Quote, day: u32, percent: u32) -> Invoice {
+        let date = NaiveDate::from_ymd_opt(2015, 3, day).unwrap();
+
+        Invoice {
+            invoice_day_id: Some(InvoiceDayId::new("01").unwrap()),
+            ..quote.create_deposit(date, date, percent, &VatRate::Normal)
+        }
+    }
+
+    #[test]
+    fn quote_final_invoice_deducts_all_deposits() {
+        let quote = Quote::generate_simple_quote_example();
+        let all_invoices = vec![generate_deposit(&quote, 20, 30), generate_deposit(&quote, 25, 20)];
+        let date = NaiveDate::from_ymd_opt(2015, 4, 1).unwrap();
+
+        let final_invoice = quote.create_invoice(date, date, &all_invoices);
+
+        assert_eq!(final_invoice.products.len(), 3);
+        assert_eq!(final_invoice.products[1].price, Amount::new("-210").unwrap());
+        assert_eq!(final_invoice.products[2].price, Amount::new("-140").unwrap());
+        assert_eq!(final_invoice.get_total_price(), Amount::new("350").unwrap());
+    }
+
+    #[test]
+    fn quote_deposits_capped_at_total() {
+        let quote = Quote::generate_simple_quote_example();
+        let mut all_invoices = vec![];
+
+        assert_eq!(quote.get_remaining_for_deposits(&all_invoices), Amount::new("700").unwrap());
+
+        all_invoices.push(generate_deposit(&quote, 20, 30));
+        assert_eq!(quote.get_remaining_for_deposits(&all_invoices), Amount::new("490").unwrap());
+
+        all_invoices.push(generate_deposit(&quote, 25, 70));
+        assert_eq!(quote.get_remaining_for_deposits(&all_invoices), Amount::ZERO);
+
+        // Deposits of another quote are not counted
+        let other_quote = Quote { quote_day_id: Some(InvoiceDayId::new("02").unwrap()), ..Quote::generate_simple_quote_example() };
+        assert_eq!(other_quote.get_remaining_for_deposits(&all_invoices), Amount::new("700").unwrap());
+    }
+
+    #[test]
+    fn quote_credited_deposit_left_out() {
+        let quote = Quote::generate_simple_quote_example();
+        let deposit = generate_deposit(&quote, 20, 30);
+        let credit_note = Invoice {
+            invoice_day_id: Some(InvoiceDayId::new("01").unwrap()),
+            ..deposit.create_credit_note(NaiveDate::from_ymd_opt(2015, 3, 22).unwrap(), deposit.products.clone())
+        };
+        let all_invoices = vec![deposit, credit_note, generate_deposit(&quote, 25, 10)];
+
+        let deposits = quote.get_deposits(&all_invoices);
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].get_ref(), Some("AC2015032501".to_string()));
+        assert_eq!(quote.get_remaining_for_deposits(&all_invoices), Amount::new("630").unwrap());
+
+        let date = NaiveDate::from_ymd_opt(2015, 4, 1).unwrap();
+        let final_invoice = quote.create_invoice(date, date, &all_invoices);
+        assert_eq!(final_invoice.get_total_price(), Amount::new("630").unwrap());
+    }
+
+    #[test]
+    fn quote_partly_credited_deposit_deducted_net() {
+        let quote = Quote::generate_simple_quote_example();
+        let deposit = generate_deposit(&quote, 20, 50);
+        let partial_credit = deposit.products[0].get_partial_credit(Amount::new("100").unwrap()).unwrap();
+        let credit_note = Invoice {
+            invoice_day_id: Some(InvoiceDayId::new("01").unwrap()),
+            ..deposit.create_credit_note(NaiveDate::from_ymd_opt(2015, 3, 22).unwrap(), vec![partial_credit])
+        };
+        let all_invoices = vec![deposit, credit_note];
+
+        assert_eq!(quote.get_deposits(&all_invoices).len(), 1);
+        assert_eq!(quote.get_remaining_for_deposits(&all_invoices), Amount::new("450").unwrap());
+
+        // 350 billed by the deposit, 100 of them credited
+        let date = NaiveDate::from_ymd_opt(2015, 4, 1).unwrap();
+        let final_invoice = quote.create_invoice(date, date, &all_invoices);
+        assert_eq!(final_invoice.products.len(), 2);
+        assert_eq!(final_invoice.products[1].price, Amount::new("-250").unwrap());
+        assert_eq!(final_invoice.get_total_price(), Amount::new("450").unwrap());
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, Months, NaiveDate};
+use serde::Serialize;
+
+use crate::entities::amount::Amount;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice::Invoice;
+use crate::entities::invoice_totals::InvoiceTotals;
+use crate::entities::vat_rate::VatRate;
+
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum Breakdown {
+    #[default]
+    Month,
+    Customer,
+}
+
+impl Breakdown {
+    pub fn all_breakdowns() -> Vec<Breakdown> {
+        vec![Breakdown::Month, Breakdown::Customer]
+    }
+
+    /// Month key of `date`, given to compare invoices of last year with this year
+    fn get_key(&self, invoice: &Invoice, date: NaiveDate) -> String {
+        match self {
+            Breakdown::Month => get_month_key(date),
+            Breakdown::Customer => invoice.customer_id.to_owned(),
+        }
+    }
+}
+
+impl FromStr for Breakdown {
+    type Err = String;
+
+    fn from_str(breakdown: &str) -> Result<Self, Self::Err> {
+        Breakdown::all_breakdowns()
+            .into_iter()
+            .find(|candidate| candidate.to_string() == breakdown.to_lowercase())
+            .ok_or_else(|| format!("Invalid breakdown: {} (month or customer)", breakdown))
+    }
+}
+
+impl fmt::Display for Breakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Breakdown::Month => write!(f, "month"),
+            Breakdown::Customer => write!(f, "customer"),
+        }
+    }
+}
+
+/// Revenue HT of a month or a customer, compared to the same period last year
+#[derive(Serialize, PartialEq, Debug)]
+pub struct BreakdownLine {
+    pub key: String,
+    pub invoices: usize,
+    pub total_ht: Amount,
+    pub previous_total_ht: Amount,
+    /// Percentage, none without revenue last year
+    pub evolution: Option<f64>,
+}
+
+/// Invoices between two dates, their breakdown and the same period last year.
+/// Invoice counts exclude credit notes, totals deduce them.
+#[derive(Serialize, Debug)]
+pub struct RangeStats {
+    pub invoices: usize,
+    pub totals: InvoiceTotals,
+    pub previous_invoices: usize,
+    pub previous_totals: InvoiceTotals,
+    pub evolution: Option<f64>,
+    pub breakdown: Vec<BreakdownLine>,
+}
+
+/// Same day one year earlier, February 29th becomes February 28th
+pub fn get_previous_year_date(date: NaiveDate) -> NaiveDate {
+    date.checked_sub_months(Months::new(12)).unwrap()
+}
+
+fn get_month_key(date: NaiveDate) -> String {
+    date.format("%Y-%m").to_string()
+}
+
+fn get_evolution(total: Amount, previous_total: Amount) -> Option<f64> {
+    if previous_total == Amount::ZERO {
+        return None;
+    }
+
+    let evolution = (total - previous_total).to_f64() / previous_total.to_f64().abs() * 100.0;
+
+    Some((evolution * 10.0).round() / 10.0)
+}
+
+impl BreakdownLine {
+    fn new(key: String) -> Self {
+        BreakdownLine {
+            key,
+            invoices: 0,
+            total_ht: Amount::ZERO,
+            previous_total_ht: Amount::ZERO,
+            evolution: None,
+        }
+    }
+}
+
+impl RangeStats {
+    /// `all_invoices` must cover the range and the same range last year
+    pub fn new(
+        from: NaiveDate,
+        to: NaiveDate,
+        breakdown: Breakdown,
+        all_invoices: &[Invoice],
+        default_vat_rate: &VatRate,
+    ) -> Self {
+        let previous_from = get_previous_year_date(from);
+        let previous_to = get_previous_year_date(to);
+
+        let invoices: Vec<&Invoice> = all_invoices
+            .iter()
+            .filter(|invoice| invoice.date >= from && invoice.date <= to)
+            .collect();
+        let previous_invoices: Vec<&Invoice> = all_invoices
+            .iter()
+            .filter(|invoice| invoice.date >= previous_from && invoice.date <= previous_to)
+            .collect();
+
+        let mut lines: BTreeMap<String, BreakdownLine> = BTreeMap::new();
+
+        // Months without invoices are shown too
+        if breakdown == Breakdown::Month {
+            let mut month = from.with_day(1).unwrap();
+            while month <= to {
+                let key = get_month_key(month);
+                lines.insert(key.clone(), BreakdownLine::new(key));
+                month = month + Months::new(1);
+            }
+        }
+
+        for invoice in invoices.iter() {
+            let key = breakdown.get_key(invoice, invoice.date);
+            let line = lines.entry(key.clone()).or_insert_with(|| BreakdownLine::new(key));
+
+            if invoice.kind != DocumentKind::CreditNote {
+                line.invoices += 1;
+            }
+            line.total_ht += invoice.get_total_price();
+        }
+
+        for invoice in previous_invoices.iter() {
+            let key = breakdown.get_key(invoice, invoice.date + Months::new(12));
+            let line = lines.entry(key.clone()).or_insert_with(|| BreakdownLine::new(key));
+
+            line.previous_total_ht += invoice.get_total_price();
+        }
+
+        let mut breakdown_lines: Vec<BreakdownLine> = lines
+            .into_values()
+            .map(|line| BreakdownLine {
+                evolution: get_evolution(line.total_ht, line.previous_total_ht),
+                ..line
+            })
+            .collect();
+
+        if breakdown == Breakdown::Customer {
+            breakdown_lines.sort_by(|a, b| b.total_ht.cmp(&a.total_ht).then_with(|| a.key.cmp(&b.key)));
+        }
+
+        let count = |invoices: &[&Invoice]| {
+            invoices.iter().filter(|invoice| invoice.kind != DocumentKind::CreditNote).count()
+        };
+        let totals: InvoiceTotals = invoices.iter().map(|invoice| invoice.get_totals(default_vat_rate)).sum();
+        let previous_totals: InvoiceTotals = previous_invoices
+            .iter()
+            .map(|invoice| invoice.get_totals(default_vat_rate))
+            .sum();
+
+        RangeStats {
+            invoices: count(&invoices),
+            evolution: get_evolution(totals.total_ht, previous_totals.total_ht),
+            totals,
+            previous_invoices: count(&previous_invoices),
+            previous_totals,
+            breakdown: breakdown_lines,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::product::Product;
+
+    use super::*;
+
+    fn generate_invoices() -> Vec<Invoice> {
+        let invoice = |year: i32, month: u32, customer_id: &str, price: &str| {
+            let mut invoice = Invoice::generate_simple_invoice_with_id_and_date_example(
+                Some(InvoiceDayId::new("01").unwrap()),
+                NaiveDate::from_ymd_opt(year, month, 10).unwrap(),
+            );
+            invoice.customer_id = customer_id.to_string();
+            invoice.products = vec![Product::from_str(&format!("Product example;1;{}", price)).unwrap()];
+            invoice
+        };
+
+        let last_year = invoice(2023, 1, "king", "100");
+        let mut credit_note = last_year.create_credit_note(
+            NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            vec![Product::from_str("Product example;1;20").unwrap()],
+        );
+        credit_note.invoice_day_id = Some(InvoiceDayId::new("01").unwrap());
+
+        vec![
+            last_year,
+            invoice(2023, 3, "queen", "50"),
+            invoice(2024, 1, "king", "150"),
+            invoice(2024, 1, "queen", "200"),
+            credit_note,
+            invoice(2024, 4, "king", "500"),
+        ]
+    }
+
+    #[test]
+    fn test_range_stats_by_month() {
+        let stats = RangeStats::new(
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+            Breakdown::Month,
+            &generate_invoices(),
+            &VatRate::Zero,
+        );
+
+        assert_eq!(stats.invoices, 2);
+        assert_eq!(stats.totals.total_ht, Amount::new("330").unwrap());
+        assert_eq!(stats.previous_invoices, 2);
+        assert_eq!(stats.previous_totals.total_ht, Amount::new("150").unwrap());
+        assert_eq!(stats.evolution, Some(120.0));
+        assert_eq!(
+            stats.breakdown,
+            vec![
+                BreakdownLine {
+                    key: "2024-01".to_string(),
+                    invoices: 2,
+                    total_ht: Amount::new("350").unwrap(),
+                    previous_total_ht: Amount::new("100").unwrap(),
+                    evolution: Some(250.0),
+                },
+                BreakdownLine {
+                    key: "2024-02".to_string(),
+                    invoices: 0,
+                    total_ht: Amount::new("-20").unwrap(),
+                    previous_total_ht: Amount::ZERO,
+                    evolution: None,
+                },
+                BreakdownLine {
+                    key: "2024-03".to_string(),
+                    invoices: 0,
+                    total_ht: Amount::ZERO,
+                    previous_total_ht: Amount::new("50").unwrap(),
+                    evolution: Some(-100.0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_range_stats_by_customer() {
+        let stats = RangeStats::new(
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            Breakdown::Customer,
+            &generate_invoices(),
+            &VatRate::Zero,
+        );
+
+        let keys: Vec<&str> = stats.breakdown.iter().map(|line| line.key.as_str()).collect();
+        assert_eq!(keys, vec!["king", "queen"]);
+        assert_eq!(stats.breakdown[0].total_ht, Amount::new("630").unwrap());
+        assert_eq!(stats.breakdown[0].previous_total_ht, Amount::new("100").unwrap());
+        assert_eq!(stats.breakdown[1].evolution, Some(300.0));
+    }
+
+    #[test]
+    fn test_previous_year_date() {
+        assert_eq!(
+            get_previous_year_date(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()),
+            NaiveDate::from_ymd_opt(2023, 2, 28).unwrap()
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::fmt;
+
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice::Invoice;
+use crate::entities::invoice_date::{
+    deser_invoice_date, deser_option_invoice_date, ser_invoice_date, ser_option_invoice_date,
+};
+use crate::entities::product::Product;
+use crate::entities::serializer::serializer;
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Cadence {
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl Cadence {
+    pub fn all_cadences() -> Vec<Cadence> {
+        vec![Cadence::Monthly, Cadence::Quarterly, Cadence::Yearly]
+    }
+
+    pub fn get_months(&self) -> u32 {
+        match self {
+            Cadence::Monthly => 1,
+            Cadence::Quarterly => 3,
+            Cadence::Yearly => 12,
+        }
+    }
+}
+
+impl fmt::Display for Cadence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cadence::Monthly => write!(f, "monthly"),
+            Cadence::Quarterly => write!(f, "quarterly"),
+            Cadence::Yearly => write!(f, "yearly"),
+        }
+    }
+}
+
+/// Schedule of an invoice billed again at each period.
+///
+/// The title accepts the placeholders `{month}` (month name in French),
+/// `{MM}` and `{YYYY}`, replaced by the period billed by each invoice.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecurringInvoice {
+    pub name: String,
+    pub customer_id: String,
+    pub title: String,
+    pub products: Vec<Product>,
+    pub cadence: Cadence,
+    #[serde(
+    serialize_with = "ser_invoice_date",
+    deserialize_with = "deser_invoice_date"
+    )]
+    pub start_date: NaiveDate,
+    #[serde(
+    default,
+    skip_serializing_if = "Option::is_none",
+    serialize_with = "ser_option_invoice_date",
+    deserialize_with = "deser_option_invoice_date"
+    )]
+    pub end_date: Option<NaiveDate>,
+    /// Last period billed, later runs only create the invoices of the next ones
+    #[serde(
+    default,
+    skip_serializing_if = "Option::is_none",
+    serialize_with = "ser_option_invoice_date",
+    deserialize_with = "deser_option_invoice_date"
+    )]
+    pub last_generated: Option<NaiveDate>,
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre",
+    "octobre", "novembre", "décembre",
+];
+
+impl RecurringInvoice {
+    /// Key of a schedule named `name` in the recurring file
+    pub fn serialize_name(name: &str) -> String {
+        serializer(name)
+    }
+
+    pub fn serialized_name(&self) -> String {
+        Self::serialize_name(&self.name)
+    }
+
+    /// Periods to bill until `today` included
+    pub fn get_pending_dates(&self, today: NaiveDate) -> Vec<NaiveDate> {
+        let last_date = match self.end_date {
+            Some(end_date) if end_date < today => end_date,
+            _ => today,
+        };
+
+        (0..)
+            .map_while(|period: u32| {
+                self.start_date
+                    .checked_add_months(Months::new(period * self.cadence.get_months()))
+            })
+            .take_while(|date| *date <= last_date)
+            .filter(|date| self.last_generated.is_none_or(|last_generated| *date > last_generated))
+            .collect()
+    }
+
+    pub fn get_title(&self, date: NaiveDate) -> String {
+        self.title
+            .replace("{month}", MONTH_NAMES[date.month0() as usize])
+            .replace("{MM}", &date.format("%m").to_string())
+            .replace("{YYYY}", &date.format("%Y").to_string())
+    }
+
+    /// Invoice of `period` issued on `date`. An invoice issued after its period,
+    /// when catching up missed runs, states the period in its title.
+    pub fn create_invoice(&self, period: NaiveDate, date: NaiveDate, due_date: NaiveDate) -> Invoice {
+        let title = if period == date {
+            self.get_title(period)
+        } else {
+            format!("{} (période du {})", self.get_title(period), period.format("%d/%m/%Y"))
+        };
+
+        Invoice {
+            date,
+            due_date: Some(due_date),
+            customer_id: self.customer_id.to_owned(),
+            title,
+            invoice_day_id: None,
+            products: self.products.clone(),
+            kind: DocumentKind::Invoice,
+            credited_invoice_ref: None,
+            quote_ref: None,
+            reference: None,
+            sequence: None,
+        }
+    }
+}
+
+impl fmt::Display for RecurringInvoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {} - {} since {}", self.name, self.customer_id, self.cadence, self.start_date)?;
+
+        if let Some(end_date) = self.end_date {
+            write!(f, " until {}", end_date)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::amount::Amount;
+    use crate::entities::quantity::Quantity;
+
+    use super::*;
+
+    impl RecurringInvoice {
+        pub fn generate_simple_recurring_invoice_example() -> RecurringInvoice {
+            RecurringInvoice {
+                name: "King retainer".to_string(),
+                customer_id: "king".to_string(),
+                title: "Maintenance {month} {YYYY}".to_string(),
+                products: vec![Product {
+                    description: "Maintenance".to_string(),
+                    quantity: Quantity::from(1),
+                    price: Amount::new("500").unwrap(),
+                    vat: None,
+                    activity: None,
+                }],
+                cadence: Cadence::Monthly,
+                start_date: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+                end_date: None,
+                last_generated: None,
+            }
+        }
+    }
+
+    #[test]
+    fn recurring_invoice_to_string() {
+        let recurring_invoice = RecurringInvoice::generate_simple_recurring_invoice_example();
+
+        let yaml = serde_yaml::to_string(&recurring_invoice).unwrap();
+
+        assert_eq!(
+            yaml,
+            "name: King retainer\n".to_owned()
+                + "customer_id: king\n"
+                + "title: Maintenance {month} {YYYY}\n"
+                + "products:\n"
+                + "- description: Maintenance\n"
+                + "  quantity: 1.0\n"
+                + "  price: 500.0\n"
+                + "cadence: monthly\n"
+                + "start_date:\n"
+                + "  day: '31'\n"
+                + "  month: '01'\n"
+                + "  year: '2024'\n"
+        );
+
+        let recurring_invoice_from_yaml: RecurringInvoice = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(recurring_invoice_from_yaml.serialized_name(), "king_retainer");
+    }
+
+    #[test]
+    fn recurring_invoice_pending_dates() {
+        let mut recurring_invoice = RecurringInvoice::generate_simple_recurring_invoice_example();
+        let today = NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+
+        assert_eq!(
+            recurring_invoice.get_pending_dates(today),
+            vec![
+                NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+            ]
+        );
+
+        recurring_invoice.last_generated = Some(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+        assert_eq!(
+            recurring_invoice.get_pending_dates(today),
+            vec![NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()]
+        );
+
+        recurring_invoice.end_date = Some(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+        assert!(recurring_invoice.get_pending_dates(today).is_empty());
+
+        recurring_invoice.cadence = Cadence::Quarterly;
+        recurring_invoice.last_generated = None;
+        recurring_invoice.end_date = None;
+        assert_eq!(
+            recurring_invoice.get_pending_dates(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()).len(),
+            4
+        );
+    }
+
+    #[test]
+    fn recurring_invoice_title() {
+        let recurring_invoice = RecurringInvoice::generate_simple_recurring_invoice_example();
+        let date = NaiveDate::from_ymd_opt(2024, 8, 31).unwrap();
+
+        assert_eq!(recurring_invoice.get_title(date), "Maintenance août 2024");
+
+        let invoice = recurring_invoice.create_invoice(date, date, date);
+        assert_eq!(invoice.title, "Maintenance août 2024");
+        assert_eq!(invoice.get_total_price(), Amount::new("500").unwrap());
+    }
+
+    #[test]
+    fn recurring_invoice_caught_up() {
+        let recurring_invoice = RecurringInvoice::generate_simple_recurring_invoice_example();
+        let today = NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+
+        let invoices: Vec<Invoice> = recurring_invoice
+            .get_pending_dates(today)
+            .into_iter()
+            .map(|period| recurring_invoice.create_invoice(period, today, today))
+            .collect();
+
+        // Issued today so their numbers follow the invoices already issued
+        assert!(invoices.iter().all(|invoice| invoice.date == today));
+        assert_eq!(
+            invoices.iter().map(|invoice| invoice.title.as_str()).collect::<Vec<_>>(),
+            vec![
+                "Maintenance janvier 2024 (période du 31/01/2024)",
+                "Maintenance février 2024 (période du 29/02/2024)",
+                "Maintenance mars 2024 (période du 31/03/2024)",
+            ]
+        );
+    }
+}
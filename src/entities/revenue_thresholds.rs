@@ -0,0 +1,189 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::amount::Amount;
+use crate::entities::invoice::Invoice;
+use crate::entities::quantity::Quantity;
+
+/// Annual revenue ceilings HT. Defaults are the 2024 values for services.
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[serde(default)]
+pub struct RevenueThresholds {
+    /// Franchise en base de TVA
+    pub vat_franchise: Amount,
+    /// Franchise lost from the day it is crossed
+    pub vat_franchise_increased: Amount,
+    /// Micro-entreprise regime
+    pub micro_enterprise: Amount,
+}
+
+impl Default for RevenueThresholds {
+    fn default() -> Self {
+        RevenueThresholds {
+            vat_franchise: Amount::new("36800").unwrap(),
+            vat_franchise_increased: Amount::new("39100").unwrap(),
+            micro_enterprise: Amount::new("77700").unwrap(),
+        }
+    }
+}
+
+impl RevenueThresholds {
+    /// Share of a threshold from which it is reported as close
+    pub const WARNING_PERCENTAGE: f64 = 90.0;
+
+    pub fn get_limits(&self) -> Vec<(&'static str, Amount)> {
+        vec![
+            ("VAT franchise", self.vat_franchise),
+            ("Increased VAT franchise", self.vat_franchise_increased),
+            ("Micro-enterprise ceiling", self.micro_enterprise),
+        ]
+    }
+
+    /// Thresholds crossed when the revenue of the year goes from `revenue` to `new_revenue`
+    pub fn get_crossed(&self, revenue: Amount, new_revenue: Amount) -> Vec<(&'static str, Amount)> {
+        self.get_limits()
+            .into_iter()
+            .filter(|(_, limit)| revenue <= *limit && new_revenue > *limit)
+            .collect()
+    }
+}
+
+#[derive(Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdLevel {
+    Under,
+    /// Over the warning percentage, or over the threshold at the end of the year at this pace
+    Close,
+    Exceeded,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct ThresholdLine {
+    pub name: String,
+    pub limit: Amount,
+    pub remaining: Amount,
+    /// Percentage of the threshold reached
+    pub usage: f64,
+    pub level: ThresholdLevel,
+}
+
+/// Revenue HT invoiced during the year, credit notes deduced, against the thresholds
+#[derive(Serialize, PartialEq, Debug)]
+pub struct ThresholdReport {
+    pub year: i32,
+    pub revenue: Amount,
+    /// Revenue at the end of the year at the pace of the days elapsed
+    pub projected_revenue: Amount,
+    pub thresholds: Vec<ThresholdLine>,
+}
+
+fn get_usage(revenue: Amount, limit: Amount) -> f64 {
+    if limit <= Amount::ZERO {
+        return 100.0;
+    }
+
+    (revenue.to_f64() / limit.to_f64() * 1000.0).round() / 10.0
+}
+
+impl ThresholdReport {
+    /// `date` is the last day taken into account, the end of the year for a past year
+    pub fn new(thresholds: &RevenueThresholds, year_invoices: &[Invoice], date: NaiveDate) -> Self {
+        let year = date.year();
+        let revenue: Amount = year_invoices
+            .iter()
+            .filter(|invoice| invoice.date.year() == year)
+            .map(|invoice| invoice.get_total_price())
+            .sum();
+
+        let days_in_year = NaiveDate::from_ymd_opt(year, 12, 31).unwrap().ordinal();
+        let projected_revenue = revenue * Quantity::from(days_in_year) / date.ordinal() as usize;
+
+        ThresholdReport {
+            year,
+            revenue,
+            projected_revenue,
+            thresholds: thresholds
+                .get_limits()
+                .into_iter()
+                .map(|(name, limit)| {
+                    let usage = get_usage(revenue, limit);
+                    let level = if revenue > limit {
+                        ThresholdLevel::Exceeded
+                    } else if usage >= RevenueThresholds::WARNING_PERCENTAGE || projected_revenue > limit {
+                        ThresholdLevel::Close
+                    } else {
+                        ThresholdLevel::Under
+                    };
+
+                    ThresholdLine {
+                        name: name.to_string(),
+                        limit,
+                        remaining: (limit - revenue).max(Amount::ZERO),
+                        usage,
+                        level,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::product::Product;
+
+    use super::*;
+
+    #[test]
+    fn test_crossed_thresholds() {
+        let thresholds = RevenueThresholds::default();
+
+        assert!(thresholds
+            .get_crossed(Amount::new("30000").unwrap(), Amount::new("36800").unwrap())
+            .is_empty());
+        assert_eq!(
+            thresholds.get_crossed(Amount::new("36800").unwrap(), Amount::new("40000").unwrap()),
+            vec![
+                ("VAT franchise", Amount::new("36800").unwrap()),
+                ("Increased VAT franchise", Amount::new("39100").unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_threshold_report() {
+        let mut invoice = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2023, 1, 10).unwrap(),
+        );
+        invoice.products = vec![Product::from_str("Consulting;1;20000").unwrap()];
+        let mut last_year = invoice.clone();
+        last_year.date = NaiveDate::from_ymd_opt(2022, 12, 10).unwrap();
+
+        // Day 73 of 365
+        let report = ThresholdReport::new(
+            &RevenueThresholds::default(),
+            &[invoice, last_year],
+            NaiveDate::from_ymd_opt(2023, 3, 14).unwrap(),
+        );
+
+        assert_eq!(report.revenue, Amount::new("20000").unwrap());
+        assert_eq!(report.projected_revenue, Amount::new("100000").unwrap());
+        assert_eq!(report.thresholds[0].remaining, Amount::new("16800").unwrap());
+        assert_eq!(report.thresholds[0].usage, 54.3);
+        assert_eq!(report.thresholds[0].level, ThresholdLevel::Close);
+        assert_eq!(report.thresholds[2].level, ThresholdLevel::Close);
+    }
+
+    #[test]
+    fn test_thresholds_from_partial_yaml() {
+        let thresholds: RevenueThresholds = serde_yaml::from_str("vat_franchise: 91900\n").unwrap();
+
+        assert_eq!(thresholds.vat_franchise, Amount::new("91900").unwrap());
+        assert_eq!(thresholds.micro_enterprise, Amount::new("77700").unwrap());
+    }
+}
@@ -1,6 +1,13 @@
 use serde::{Deserialize, Serialize};
 
+use crate::entities::accounting_settings::AccountingSettings;
+use crate::entities::activity::{Activity, ContributionRates};
+use crate::entities::factur_x::FacturXProfile;
+use crate::entities::numbering_policy::NumberingPolicy;
+use crate::entities::payment_terms::PaymentTerms;
+use crate::entities::revenue_thresholds::RevenueThresholds;
 use crate::entities::siren::Siren;
+use crate::entities::vat_rate::VatRate;
 
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(test, derive(Debug, PartialEq))]
@@ -14,6 +21,9 @@ pub struct Enterprise {
     pub phone: String,
     pub title: String,
     pub tva: String,
+    /// Account of the credit transfers in the electronic invoices
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iban: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -22,6 +32,64 @@ pub struct Settings {
     pub enterprise: Enterprise,
     pub law_rules: String,
     pub politeness: String,
+    /// Rate used by product lines without their own rate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_vat_rate: Option<VatRate>,
+    /// Terms used for customers without their own terms
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_terms: Option<PaymentTerms>,
+    /// Reference pattern of new invoices, dated references `YYYYMMDDNN` without policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numbering: Option<NumberingPolicy>,
+    /// Annual revenue ceilings watched by `stats thresholds` and `invoice create`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thresholds: Option<RevenueThresholds>,
+    /// Activity of the product lines without their own activity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activity: Option<Activity>,
+    /// Rates of the social contributions declared to the URSSAF
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contribution_rates: Option<ContributionRates>,
+    /// Accounts and journals of the FEC export
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accounting: Option<AccountingSettings>,
+    /// Profile of the Factur-X XML embedded in the invoice PDFs, plain PDFs when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factur_x: Option<FacturXProfile>,
+}
+
+impl Settings {
+    /// Without explicit setting, an enterprise without TVA number is under "franchise en base"
+    pub fn get_default_vat_rate(&self) -> VatRate {
+        match &self.default_vat_rate {
+            Some(default_vat_rate) => default_vat_rate.clone(),
+            None if self.enterprise.tva.is_empty() => {
+                VatRate::Exempt { reason: VatRate::FRANCHISE_REASON.to_string() }
+            }
+            None => VatRate::Normal,
+        }
+    }
+
+    pub fn get_payment_terms(&self) -> PaymentTerms {
+        self.payment_terms.clone().unwrap_or_default()
+    }
+
+    pub fn get_thresholds(&self) -> RevenueThresholds {
+        self.thresholds.clone().unwrap_or_default()
+    }
+
+    /// Services in BNC without explicit setting, the usual activity of freelancers
+    pub fn get_activity(&self) -> Activity {
+        self.activity.unwrap_or(Activity::ServiceBnc)
+    }
+
+    pub fn get_contribution_rates(&self) -> ContributionRates {
+        self.contribution_rates.clone().unwrap_or_default()
+    }
+
+    pub fn get_accounting(&self) -> AccountingSettings {
+        self.accounting.clone().unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -41,6 +109,7 @@ mod tests {
                 phone: "123-456-7890".into(),
                 title: "CEO".into(),
                 tva: "".into(),
+                iban: None,
             }
         }
     }
@@ -51,6 +120,14 @@ mod tests {
                 enterprise: Enterprise::generate_simple_enterprise(),
                 law_rules: "Example Law".into(),
                 politeness: "Kind Regards".into(),
+                default_vat_rate: None,
+                payment_terms: None,
+                numbering: None,
+                thresholds: None,
+                activity: None,
+                contribution_rates: None,
+                accounting: None,
+                factur_x: None,
             }
         }
     }
@@ -191,4 +268,30 @@ mod tests {
             settings_example.enterprise.tva
         );
     }
+
+    #[test]
+    fn test_default_vat_rate() {
+        let mut settings = Settings::generate_simple_settings();
+
+        assert_eq!(
+            settings.get_default_vat_rate(),
+            VatRate::Exempt { reason: VatRate::FRANCHISE_REASON.to_string() }
+        );
+
+        settings.enterprise.tva = "FR12123456789".into();
+        assert_eq!(settings.get_default_vat_rate(), VatRate::Normal);
+
+        settings.default_vat_rate = Some(VatRate::Intermediate);
+        assert_eq!(settings.get_default_vat_rate(), VatRate::Intermediate);
+    }
+
+    #[test]
+    fn test_payment_terms() {
+        let mut settings = Settings::generate_simple_settings();
+
+        assert_eq!(settings.get_payment_terms(), PaymentTerms::Net { days: 30 });
+
+        settings.payment_terms = Some(PaymentTerms::OnReceipt);
+        assert_eq!(settings.get_payment_terms(), PaymentTerms::OnReceipt);
+    }
 }
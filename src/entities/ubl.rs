@@ -0,0 +1,427 @@
+use std::fmt;
+
+use crate::entities::amount::Amount;
+use crate::entities::customer::Customer;
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::factur_x::{format_vat_percent, get_line_price_and_quantity, get_type_code, get_vat_category};
+use crate::entities::invoice::Invoice;
+use crate::entities::settings::Settings;
+use crate::entities::vat_rate::VatRate;
+use crate::entities::xml_writer::XmlWriter;
+
+const CUSTOMIZATION_ID: &str = "urn:cen.eu:en16931:2017#compliant#urn:fdc:peppol.eu:2017:poacc:billing:3.0";
+
+const PROFILE_ID: &str = "urn:fdc:peppol.eu:2017:poacc:billing:01:1.0";
+
+/// Peppol BIS Billing 3.0 UBL document of an invoice. Credit notes are `CreditNote` documents
+/// with positive amounts, the other kinds are `Invoice` documents.
+pub struct Ubl<'a> {
+    invoice: &'a Invoice,
+    customer: &'a Customer,
+    settings: &'a Settings,
+}
+
+impl<'a> Ubl<'a> {
+    pub fn new(invoice: &'a Invoice, customer: &'a Customer, settings: &'a Settings) -> Ubl<'a> {
+        Ubl { invoice, customer, settings }
+    }
+
+    fn is_credit_note(&self) -> bool {
+        self.invoice.kind == DocumentKind::CreditNote
+    }
+
+    fn sign(&self, amount: Amount) -> Amount {
+        if self.is_credit_note() { -amount } else { amount }
+    }
+
+    fn signed(&self, amount: Amount) -> String {
+        self.sign(amount).to_string()
+    }
+
+    /// Rules of EN 16931 and Peppol the document would break, empty when it can be sent
+    pub fn validate(&self) -> Vec<String> {
+        let enterprise = &self.settings.enterprise;
+        let default_vat_rate = self.settings.get_default_vat_rate();
+        let mut issues = vec![];
+
+        let mut check = |valid: bool, issue: &str| {
+            if !valid {
+                issues.push(issue.to_string());
+            }
+        };
+
+        check(self.invoice.get_ref().is_some(), "BR-02: the invoice has no reference");
+        check(!self.invoice.products.is_empty(), "BR-16: the invoice has no line");
+        check(!enterprise.name.is_empty(), "BR-06: the seller name is empty");
+        check(!self.customer.name.is_empty(), "BR-07: the buyer name is empty");
+        check(
+            !self.customer.address.is_empty() && !self.customer.city.is_empty() && !self.customer.postal.is_empty(),
+            "BR-10: the buyer postal address is incomplete",
+        );
+        check(
+            self.customer.siren.is_some(),
+            "PEPPOL-EN16931-R010: the buyer electronic address is missing, set the SIREN of the customer",
+        );
+        check(
+            self.invoice.products.iter().all(|product| !product.description.is_empty()),
+            "BR-25: a line has no description",
+        );
+        check(
+            enterprise.tva.is_empty() || has_country_prefix(&enterprise.tva),
+            "BR-CO-09: the seller TVA number does not start with its country code",
+        );
+        check(
+            self.customer.tva.as_deref().is_none_or(has_country_prefix),
+            "BR-CO-09: the buyer TVA number does not start with its country code",
+        );
+        check(
+            !enterprise.tva.is_empty()
+                || self
+                    .invoice
+                    .products
+                    .iter()
+                    .all(|product| get_vat_category(product.get_vat_rate(&default_vat_rate)) != "S"),
+            "BR-S-02: a line has a standard VAT rate but the seller has no TVA number",
+        );
+        check(
+            enterprise.iban.as_deref().is_none_or(is_iban),
+            "BR-61: the IBAN of the settings is invalid",
+        );
+
+        issues
+    }
+
+    fn write_party(&self, writer: &mut XmlWriter, siren: Option<String>, name: &str, address: (&str, &str, &str), tva: Option<&str>) {
+        let (street, postal, city) = address;
+
+        writer.open("cac:Party", &[]);
+        if let Some(siren) = &siren {
+            writer.leaf("cbc:EndpointID", &[("schemeID", "0002")], siren);
+        }
+        writer
+            .open("cac:PartyName", &[])
+            .leaf("cbc:Name", &[], name)
+            .close()
+            .open("cac:PostalAddress", &[])
+            .leaf("cbc:StreetName", &[], street)
+            .leaf("cbc:CityName", &[], city)
+            .leaf("cbc:PostalZone", &[], postal)
+            .open("cac:Country", &[])
+            .leaf("cbc:IdentificationCode", &[], "FR")
+            .close()
+            .close();
+        if let Some(tva) = tva.filter(|tva| !tva.is_empty()) {
+            writer
+                .open("cac:PartyTaxScheme", &[])
+                .leaf("cbc:CompanyID", &[], tva)
+                .open("cac:TaxScheme", &[])
+                .leaf("cbc:ID", &[], "VAT")
+                .close()
+                .close();
+        }
+        writer.open("cac:PartyLegalEntity", &[]).leaf("cbc:RegistrationName", &[], name);
+        if let Some(siren) = &siren {
+            writer.leaf("cbc:CompanyID", &[("schemeID", "0002")], siren);
+        }
+        writer.close();
+    }
+
+    fn write_parties(&self, writer: &mut XmlWriter) {
+        let enterprise = &self.settings.enterprise;
+
+        writer.open("cac:AccountingSupplierParty", &[]);
+        self.write_party(
+            writer,
+            Some(enterprise.siren.to_string()),
+            &enterprise.name,
+            (&enterprise.address, &enterprise.postal, &enterprise.city),
+            Some(&enterprise.tva),
+        );
+        writer
+            .open("cac:Contact", &[])
+            .leaf("cbc:Name", &[], &enterprise.title)
+            .leaf("cbc:Telephone", &[], &enterprise.phone)
+            .leaf("cbc:ElectronicMail", &[], &enterprise.email)
+            .close()
+            .close()
+            .close();
+
+        writer.open("cac:AccountingCustomerParty", &[]);
+        self.write_party(
+            writer,
+            self.customer.siren.as_ref().map(|siren| siren.to_string()),
+            &self.customer.name,
+            (&self.customer.address, &self.customer.postal, &self.customer.city),
+            self.customer.tva.as_deref(),
+        );
+        writer.close().close();
+    }
+
+    /// Credit transfer to the IBAN of the settings, else an undefined payment instrument
+    fn write_payment_means(&self, writer: &mut XmlWriter) {
+        let reference = self.invoice.get_ref().unwrap_or_default();
+
+        writer.open("cac:PaymentMeans", &[]);
+        match &self.settings.enterprise.iban {
+            Some(iban) => {
+                writer
+                    .leaf("cbc:PaymentMeansCode", &[], "30")
+                    .leaf("cbc:PaymentID", &[], &reference)
+                    .open("cac:PayeeFinancialAccount", &[])
+                    .leaf("cbc:ID", &[], &iban.replace(' ', ""))
+                    .close();
+            }
+            None => {
+                writer.leaf("cbc:PaymentMeansCode", &[], "1").leaf("cbc:PaymentID", &[], &reference);
+            }
+        }
+        writer.close();
+    }
+
+    fn write_totals(&self, writer: &mut XmlWriter) {
+        let totals = self.invoice.get_totals(&self.settings.get_default_vat_rate());
+        let euro = [("currencyID", "EUR")];
+
+        writer
+            .open("cac:TaxTotal", &[])
+            .leaf("cbc:TaxAmount", &euro, &self.signed(totals.total_vat));
+        for vat_line in totals.vat_lines.iter() {
+            writer
+                .open("cac:TaxSubtotal", &[])
+                .leaf("cbc:TaxableAmount", &euro, &self.signed(vat_line.base))
+                .leaf("cbc:TaxAmount", &euro, &self.signed(vat_line.vat));
+            write_tax_category(writer, "cac:TaxCategory", &vat_line.rate, true);
+            writer.close();
+        }
+        writer
+            .close()
+            .open("cac:LegalMonetaryTotal", &[])
+            .leaf("cbc:LineExtensionAmount", &euro, &self.signed(totals.total_ht))
+            .leaf("cbc:TaxExclusiveAmount", &euro, &self.signed(totals.total_ht))
+            .leaf("cbc:TaxInclusiveAmount", &euro, &self.signed(totals.total_ttc))
+            .leaf("cbc:PayableAmount", &euro, &self.signed(totals.total_ttc))
+            .close();
+    }
+
+    fn write_lines(&self, writer: &mut XmlWriter) {
+        let default_vat_rate = self.settings.get_default_vat_rate();
+        let euro = [("currencyID", "EUR")];
+        let (line, quantity) = if self.is_credit_note() {
+            ("cac:CreditNoteLine", "cbc:CreditedQuantity")
+        } else {
+            ("cac:InvoiceLine", "cbc:InvoicedQuantity")
+        };
+
+        for (index, product) in self.invoice.products.iter().enumerate() {
+            let (price, line_quantity) = get_line_price_and_quantity(self.sign(product.price), product.quantity);
+
+            writer
+                .open(line, &[])
+                .leaf("cbc:ID", &[], &(index + 1).to_string())
+                .leaf(quantity, &[("unitCode", "C62")], &line_quantity.to_string())
+                .leaf("cbc:LineExtensionAmount", &euro, &self.signed(product.get_total_price()))
+                .open("cac:Item", &[])
+                .leaf("cbc:Name", &[], &product.description);
+            write_tax_category(writer, "cac:ClassifiedTaxCategory", product.get_vat_rate(&default_vat_rate), false);
+            writer
+                .close()
+                .open("cac:Price", &[])
+                .leaf("cbc:PriceAmount", &euro, &price.to_string())
+                .close()
+                .close();
+        }
+    }
+}
+
+/// The exemption reason is only given in the VAT breakdown
+fn write_tax_category(writer: &mut XmlWriter, name: &str, vat_rate: &VatRate, with_reason: bool) {
+    writer
+        .open(name, &[])
+        .leaf("cbc:ID", &[], get_vat_category(vat_rate))
+        .leaf("cbc:Percent", &[], &format_vat_percent(vat_rate));
+    if let Some(reason) = vat_rate.get_exemption_reason().filter(|_| with_reason) {
+        writer.leaf("cbc:TaxExemptionReason", &[], reason);
+    }
+    writer
+        .open("cac:TaxScheme", &[])
+        .leaf("cbc:ID", &[], "VAT")
+        .close()
+        .close();
+}
+
+fn has_country_prefix(tva: &str) -> bool {
+    tva.len() > 2 && tva.chars().take(2).all(|character| character.is_ascii_uppercase())
+}
+
+fn is_iban(iban: &str) -> bool {
+    let iban = iban.replace(' ', "");
+
+    (15..=34).contains(&iban.len())
+        && has_country_prefix(&iban)
+        && iban.chars().all(|character| character.is_ascii_alphanumeric())
+}
+
+impl fmt::Display for Ubl<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (root, type_code) = if self.is_credit_note() {
+            ("CreditNote", "cbc:CreditNoteTypeCode")
+        } else {
+            ("Invoice", "cbc:InvoiceTypeCode")
+        };
+        let namespace = format!("urn:oasis:names:specification:ubl:schema:xsd:{}-2", root);
+
+        let mut writer = XmlWriter::new();
+
+        writer
+            .open(
+                root,
+                &[
+                    ("xmlns", &namespace),
+                    ("xmlns:cac", "urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2"),
+                    ("xmlns:cbc", "urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2"),
+                ],
+            )
+            .leaf("cbc:CustomizationID", &[], CUSTOMIZATION_ID)
+            .leaf("cbc:ProfileID", &[], PROFILE_ID)
+            .leaf("cbc:ID", &[], &self.invoice.get_ref().unwrap_or_default())
+            .leaf("cbc:IssueDate", &[], &self.invoice.date.format("%Y-%m-%d").to_string());
+        if !self.is_credit_note() {
+            writer.leaf("cbc:DueDate", &[], &self.invoice.get_due_date().format("%Y-%m-%d").to_string());
+        }
+        writer.leaf(type_code, &[], get_type_code(self.invoice.kind));
+        if !self.settings.law_rules.is_empty() {
+            writer.leaf("cbc:Note", &[], &self.settings.law_rules);
+        }
+        writer
+            .leaf("cbc:DocumentCurrencyCode", &[], "EUR")
+            .leaf("cbc:BuyerReference", &[], &self.invoice.customer_id);
+        if let Some(credited_invoice_ref) = &self.invoice.credited_invoice_ref {
+            writer
+                .open("cac:BillingReference", &[])
+                .open("cac:InvoiceDocumentReference", &[])
+                .leaf("cbc:ID", &[], credited_invoice_ref)
+                .close()
+                .close();
+        }
+
+        self.write_parties(&mut writer);
+        self.write_payment_means(&mut writer);
+        self.write_totals(&mut writer);
+        self.write_lines(&mut writer);
+
+        write!(f, "{}", writer.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::product::Product;
+    use crate::entities::quantity::Quantity;
+    use crate::entities::siren::Siren;
+
+    use super::*;
+
+    fn generate_customer() -> Customer {
+        let mut customer = Customer::simple_customer();
+        customer.siren = Some(Siren::from(987654321));
+        customer
+    }
+
+    #[test]
+    fn invoice_with_vat_and_credit_transfer() {
+        let mut settings = Settings::generate_simple_settings();
+        settings.default_vat_rate = Some(VatRate::Normal);
+        settings.enterprise.tva = "FR12123456789".to_string();
+        settings.enterprise.iban = Some("FR76 3000 6000 0112 3456 7890 189".to_string());
+        let invoice = Invoice::generate_simple_invoice_example();
+        let customer = generate_customer();
+
+        let ubl = Ubl::new(&invoice, &customer, &settings);
+        assert_eq!(ubl.validate(), Vec::<String>::new());
+
+        let xml = ubl.to_string();
+        assert!(xml.contains("<Invoice xmlns=\"urn:oasis:names:specification:ubl:schema:xsd:Invoice-2\""));
+        assert!(xml.contains(&format!("<cbc:CustomizationID>{}</cbc:CustomizationID>", CUSTOMIZATION_ID)));
+        assert!(xml.contains("<cbc:IssueDate>2015-03-14</cbc:IssueDate>\n  <cbc:DueDate>2015-04-13</cbc:DueDate>\n  <cbc:InvoiceTypeCode>380</cbc:InvoiceTypeCode>"));
+        assert!(xml.contains("<cbc:EndpointID schemeID=\"0002\">123456789</cbc:EndpointID>"));
+        assert!(xml.contains("<cbc:EndpointID schemeID=\"0002\">987654321</cbc:EndpointID>"));
+        assert!(xml.contains("<cbc:CompanyID>FR12123456789</cbc:CompanyID>"));
+        assert!(xml.contains("<cbc:PaymentMeansCode>30</cbc:PaymentMeansCode>"));
+        assert!(xml.contains("<cbc:ID>FR7630006000011234567890189</cbc:ID>"));
+        assert!(xml.contains("<cbc:TaxAmount currencyID=\"EUR\">70.00</cbc:TaxAmount>"));
+        assert!(xml.contains("<cbc:PayableAmount currencyID=\"EUR\">420.00</cbc:PayableAmount>"));
+        assert!(xml.contains("<cbc:InvoicedQuantity unitCode=\"C62\">1</cbc:InvoicedQuantity>"));
+        assert!(xml.contains("<cbc:ID>S</cbc:ID>\n        <cbc:Percent>20.00</cbc:Percent>"));
+        assert!(xml.ends_with("</cac:InvoiceLine>\n</Invoice>\n"));
+    }
+
+    #[test]
+    fn exempt_credit_note() {
+        let settings = Settings::generate_simple_settings();
+        let invoice = Invoice::generate_simple_invoice_example();
+        let mut credit_note = invoice.create_credit_note(NaiveDate::from_ymd_opt(2015, 4, 1).unwrap(), invoice.products.clone());
+        credit_note.invoice_day_id = Some(InvoiceDayId::new("01").unwrap());
+        let customer = generate_customer();
+
+        let ubl = Ubl::new(&credit_note, &customer, &settings);
+        assert_eq!(ubl.validate(), Vec::<String>::new());
+
+        let xml = ubl.to_string();
+        assert!(xml.contains("<CreditNote xmlns=\"urn:oasis:names:specification:ubl:schema:xsd:CreditNote-2\""));
+        assert!(xml.contains("<cbc:CreditNoteTypeCode>381</cbc:CreditNoteTypeCode>"));
+        assert!(!xml.contains("cbc:DueDate"));
+        assert!(xml.contains("<cac:InvoiceDocumentReference>\n      <cbc:ID>2015031401</cbc:ID>"));
+        assert!(xml.contains("<cbc:TaxExemptionReason>TVA non applicable, art. 293 B du CGI</cbc:TaxExemptionReason>"));
+        assert!(xml.contains("<cbc:PaymentMeansCode>1</cbc:PaymentMeansCode>"));
+        assert!(xml.contains("<cbc:CreditedQuantity unitCode=\"C62\">1</cbc:CreditedQuantity>"));
+        assert!(xml.contains("<cbc:PriceAmount currencyID=\"EUR\">350.00</cbc:PriceAmount>"));
+        assert!(!xml.contains("<cbc:PriceAmount currencyID=\"EUR\">-"));
+    }
+
+    #[test]
+    fn deducted_deposit_has_a_negative_quantity() {
+        let settings = Settings::generate_simple_settings();
+        let mut invoice = Invoice::generate_simple_invoice_example();
+        invoice.products.push(Product {
+            description: "Acompte 2015031301 déjà facturé".to_string(),
+            quantity: Quantity::from(1),
+            price: Amount::new("-100").unwrap(),
+            vat: None,
+            activity: None,
+        });
+        let customer = generate_customer();
+
+        let ubl = Ubl::new(&invoice, &customer, &settings);
+        assert_eq!(ubl.validate(), Vec::<String>::new());
+
+        // BR-27: the price is never negative, the sign goes on the quantity
+        let xml = ubl.to_string();
+        assert!(!xml.contains("<cbc:PriceAmount currencyID=\"EUR\">-"));
+        assert!(xml.contains("<cbc:InvoicedQuantity unitCode=\"C62\">-1</cbc:InvoicedQuantity>\n    <cbc:LineExtensionAmount currencyID=\"EUR\">-100.00</cbc:LineExtensionAmount>"));
+        assert!(xml.contains("<cbc:PriceAmount currencyID=\"EUR\">100.00</cbc:PriceAmount>"));
+        assert!(xml.contains("<cbc:PayableAmount currencyID=\"EUR\">250.00</cbc:PayableAmount>"));
+    }
+
+    #[test]
+    fn validate_reports_broken_rules() {
+        let mut settings = Settings::generate_simple_settings();
+        settings.default_vat_rate = Some(VatRate::Normal);
+        settings.enterprise.iban = Some("1234".to_string());
+        let invoice = Invoice::generate_simple_invoice_example();
+        let mut customer = Customer::simple_customer();
+        customer.tva = Some("123".to_string());
+
+        assert_eq!(
+            Ubl::new(&invoice, &customer, &settings).validate(),
+            vec![
+                "PEPPOL-EN16931-R010: the buyer electronic address is missing, set the SIREN of the customer",
+                "BR-CO-09: the buyer TVA number does not start with its country code",
+                "BR-S-02: a line has a standard VAT rate but the seller has no TVA number",
+                "BR-61: the IBAN of the settings is invalid",
+            ]
+        );
+    }
+}
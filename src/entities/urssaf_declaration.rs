@@ -0,0 +1,184 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::entities::activity::{Activity, ContributionRates};
+use crate::entities::amount::Amount;
+use crate::entities::invoice::Invoice;
+use crate::entities::payment::Payment;
+use crate::entities::percentage::Percentage;
+use crate::entities::vat_rate::VatRate;
+
+/// Revenue cashed for an activity and the contributions it costs
+#[derive(Serialize, PartialEq, Debug)]
+pub struct DeclarationLine {
+    pub activity: Activity,
+    pub label: String,
+    /// HT, to the cent
+    pub revenue: Amount,
+    /// Amount to enter on the declaration, in whole euros
+    pub declared_revenue: Amount,
+    pub rate: Percentage,
+    pub contributions: Amount,
+}
+
+/// Revenue cashed between two dates, on the payment dates and not on the invoice dates.
+/// A payment is split between the activities of its invoice in proportion of their HT totals.
+#[derive(Serialize, PartialEq, Debug)]
+pub struct UrssafDeclaration {
+    pub payments: usize,
+    pub lines: Vec<DeclarationLine>,
+    pub total_declared_revenue: Amount,
+    pub total_contributions: Amount,
+}
+
+impl UrssafDeclaration {
+    pub fn new(
+        from: NaiveDate,
+        to: NaiveDate,
+        all_invoices: &[Invoice],
+        all_payments: &HashMap<String, Vec<Payment>>,
+        default_activity: Activity,
+        default_vat_rate: &VatRate,
+        contribution_rates: &ContributionRates,
+    ) -> Self {
+        let invoices_by_ref: HashMap<String, &Invoice> = all_invoices
+            .iter()
+            .filter_map(|invoice| invoice.get_ref().map(|invoice_ref| (invoice_ref, invoice)))
+            .collect();
+
+        let mut revenues: BTreeMap<Activity, Amount> = BTreeMap::from([(default_activity, Amount::ZERO)]);
+        let mut payments = 0;
+
+        for (invoice_ref, invoice_payments) in all_payments.iter() {
+            let Some(invoice) = invoices_by_ref.get(invoice_ref) else {
+                continue;
+            };
+
+            let total_ttc = invoice.get_totals(default_vat_rate).total_ttc;
+            if total_ttc == Amount::ZERO {
+                continue;
+            }
+
+            let mut activity_totals: BTreeMap<Activity, Amount> = BTreeMap::new();
+            for product in invoice.products.iter() {
+                *activity_totals.entry(product.get_activity(default_activity)).or_default() += product.get_total_price();
+            }
+
+            for payment in invoice_payments.iter().filter(|payment| payment.date >= from && payment.date <= to) {
+                payments += 1;
+
+                for (activity, activity_total) in activity_totals.iter() {
+                    *revenues.entry(*activity).or_default() += payment.amount.get_share(*activity_total, total_ttc);
+                }
+            }
+        }
+
+        let lines: Vec<DeclarationLine> = revenues
+            .into_iter()
+            .filter(|(activity, revenue)| *activity == default_activity || *revenue != Amount::ZERO)
+            .map(|(activity, revenue)| {
+                let declared_revenue = revenue.round_to_unit();
+                let rate = contribution_rates.get_rate(&activity);
+
+                DeclarationLine {
+                    activity,
+                    label: activity.get_label().to_string(),
+                    revenue,
+                    declared_revenue,
+                    rate,
+                    contributions: declared_revenue.get_percentage(rate.basis_points()),
+                }
+            })
+            .collect();
+
+        UrssafDeclaration {
+            payments,
+            total_declared_revenue: lines.iter().map(|line| line.declared_revenue).sum(),
+            total_contributions: lines.iter().map(|line| line.contributions).sum(),
+            lines,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::payment::PaymentMethod;
+    use crate::entities::product::Product;
+
+    use super::*;
+
+    fn payment(date: NaiveDate, amount: &str) -> Payment {
+        Payment { date, amount: Amount::new(amount).unwrap(), method: PaymentMethod::Transfer, reference: None }
+    }
+
+    #[test]
+    fn test_urssaf_declaration() {
+        let mut invoice = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2024, 1, 20).unwrap(),
+        );
+        invoice.products = vec![
+            Product::from_str("Development;1;750").unwrap(),
+            Product::from_str("Hardware;1;250;;sale").unwrap(),
+        ];
+
+        let all_payments = HashMap::from([(
+            "2024012001".to_string(),
+            vec![
+                payment(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(), "400"),
+                payment(NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(), "600"),
+            ],
+        )]);
+
+        let declaration = UrssafDeclaration::new(
+            NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            &[invoice],
+            &all_payments,
+            Activity::ServiceBnc,
+            &VatRate::Zero,
+            &ContributionRates::default(),
+        );
+
+        assert_eq!(declaration.payments, 1);
+        assert_eq!(declaration.lines.len(), 2);
+        assert_eq!(declaration.lines[0].activity, Activity::Sale);
+        assert_eq!(declaration.lines[0].declared_revenue, Amount::new("150").unwrap());
+        assert_eq!(declaration.lines[0].contributions, Amount::new("18.45").unwrap());
+        assert_eq!(declaration.lines[1].activity, Activity::ServiceBnc);
+        assert_eq!(declaration.lines[1].declared_revenue, Amount::new("450").unwrap());
+        assert_eq!(declaration.lines[1].contributions, Amount::new("117.45").unwrap());
+        assert_eq!(declaration.total_declared_revenue, Amount::new("600").unwrap());
+        assert_eq!(declaration.total_contributions, Amount::new("135.90").unwrap());
+    }
+
+    #[test]
+    fn test_urssaf_declaration_with_vat() {
+        let invoice = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2024, 1, 20).unwrap(),
+        );
+
+        let all_payments = HashMap::from([(
+            "2024012001".to_string(),
+            vec![payment(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(), "420")],
+        )]);
+
+        let declaration = UrssafDeclaration::new(
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            &[invoice],
+            &all_payments,
+            Activity::ServiceBic,
+            &VatRate::Normal,
+            &ContributionRates::default(),
+        );
+
+        assert_eq!(declaration.lines[0].revenue, Amount::new("350").unwrap());
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// French VAT rates applicable to a product line.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+#[serde(tag = "rate", rename_all = "snake_case")]
+pub enum VatRate {
+    /// Taux normal : 20 %
+    Normal,
+    /// Taux intermédiaire : 10 %
+    Intermediate,
+    /// Taux réduit : 5,5 %
+    Reduced,
+    /// Taux particulier : 2,1 %
+    SuperReduced,
+    /// Taux zéro
+    Zero,
+    /// Exonération, the reason is the legal mention printed on the invoice
+    Exempt { reason: String },
+}
+
+impl VatRate {
+    pub const FRANCHISE_REASON: &'static str = "TVA non applicable, art. 293 B du CGI";
+
+    pub fn all_rates() -> Vec<VatRate> {
+        vec![
+            VatRate::Normal,
+            VatRate::Intermediate,
+            VatRate::Reduced,
+            VatRate::SuperReduced,
+            VatRate::Zero,
+        ]
+    }
+
+    /// Rate in basis points (1 % = 100)
+    pub fn basis_points(&self) -> i64 {
+        match self {
+            VatRate::Normal => 2_000,
+            VatRate::Intermediate => 1_000,
+            VatRate::Reduced => 550,
+            VatRate::SuperReduced => 210,
+            VatRate::Zero | VatRate::Exempt { .. } => 0,
+        }
+    }
+
+    pub fn get_exemption_reason(&self) -> Option<&str> {
+        match self {
+            VatRate::Exempt { reason } => Some(reason),
+            _ => None,
+        }
+    }
+}
+
+/// Rate from its name (`normal`, `reduced`...) or its percentage (`20`, `5.5 %`...)
+impl FromStr for VatRate {
+    type Err = String;
+
+    fn from_str(rate: &str) -> Result<Self, Self::Err> {
+        let rate = rate.trim().trim_end_matches('%').trim().replace(',', ".");
+
+        VatRate::all_rates()
+            .into_iter()
+            .find(|vat_rate| {
+                serde_yaml::to_string(vat_rate).unwrap() == format!("rate: {}\n", rate)
+                    || vat_rate.to_string() == format!("{} %", rate)
+            })
+            .ok_or_else(|| format!("Invalid VAT rate: {} (20, 10, 5.5, 2.1 or 0)", rate))
+    }
+}
+
+impl fmt::Display for VatRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VatRate::Normal => write!(f, "20 %"),
+            VatRate::Intermediate => write!(f, "10 %"),
+            VatRate::Reduced => write!(f, "5.5 %"),
+            VatRate::SuperReduced => write!(f, "2.1 %"),
+            VatRate::Zero => write!(f, "0 %"),
+            VatRate::Exempt { .. } => write!(f, "Exonérée"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vat_rate_to_string() {
+        assert_eq!(serde_yaml::to_string(&VatRate::Normal).unwrap(), "rate: normal\n");
+        assert_eq!(
+            serde_yaml::to_string(&VatRate::SuperReduced).unwrap(),
+            "rate: super_reduced\n"
+        );
+        assert_eq!(
+            serde_yaml::to_string(&VatRate::Exempt { reason: "Art. 261 CGI".into() })
+                .unwrap(),
+            "rate: exempt\nreason: Art. 261 CGI\n"
+        );
+    }
+
+    #[test]
+    fn test_vat_rate_from_string() {
+        let vat_rate: VatRate = serde_yaml::from_str("rate: reduced\n").unwrap();
+        assert_eq!(vat_rate, VatRate::Reduced);
+
+        let vat_rate: VatRate =
+            serde_yaml::from_str("rate: exempt\nreason: Art. 261 CGI\n").unwrap();
+        assert_eq!(vat_rate.get_exemption_reason(), Some("Art. 261 CGI"));
+    }
+
+    #[test]
+    fn test_vat_rate_from_str() {
+        assert_eq!(VatRate::from_str("20"), Ok(VatRate::Normal));
+        assert_eq!(VatRate::from_str("5,5 %"), Ok(VatRate::Reduced));
+        assert_eq!(VatRate::from_str("super_reduced"), Ok(VatRate::SuperReduced));
+        assert!(VatRate::from_str("19.6").is_err());
+    }
+
+    #[test]
+    fn test_vat_rate_display() {
+        assert_eq!(VatRate::Reduced.to_string(), "5.5 %");
+        assert_eq!(VatRate::Exempt { reason: "".into() }.to_string(), "Exonérée");
+    }
+}
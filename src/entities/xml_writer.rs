@@ -0,0 +1,89 @@
+/// Indented XML document written element by element, the open elements are closed in order
+pub(crate) struct XmlWriter {
+    content: String,
+    open_elements: Vec<String>,
+}
+
+impl XmlWriter {
+    pub(crate) fn new() -> XmlWriter {
+        XmlWriter {
+            content: "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n".to_string(),
+            open_elements: vec![],
+        }
+    }
+
+    pub(crate) fn open(&mut self, name: &str, attributes: &[(&str, &str)]) -> &mut XmlWriter {
+        self.write_indent();
+        self.content += &format!("<{}{}>\n", name, format_attributes(attributes));
+        self.open_elements.push(name.to_string());
+        self
+    }
+
+    pub(crate) fn close(&mut self) -> &mut XmlWriter {
+        let name = self.open_elements.pop().expect("No XML element to close");
+        self.write_indent();
+        self.content += &format!("</{}>\n", name);
+        self
+    }
+
+    /// Element with only a text
+    pub(crate) fn leaf(&mut self, name: &str, attributes: &[(&str, &str)], text: &str) -> &mut XmlWriter {
+        self.write_indent();
+        self.content += &format!("<{}{}>{}</{}>\n", name, format_attributes(attributes), escape_xml(text), name);
+        self
+    }
+
+    /// Document with the elements still open closed
+    pub(crate) fn finish(mut self) -> String {
+        while !self.open_elements.is_empty() {
+            self.close();
+        }
+
+        self.content
+    }
+
+    fn write_indent(&mut self) {
+        self.content += &"  ".repeat(self.open_elements.len());
+    }
+}
+
+fn format_attributes(attributes: &[(&str, &str)]) -> String {
+    attributes
+        .iter()
+        .map(|(name, value)| format!(" {}=\"{}\"", name, escape_xml(value)))
+        .collect()
+}
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_nested_elements() {
+        let mut writer = XmlWriter::new();
+        writer
+            .open("rsm:Invoice", &[("xmlns:rsm", "urn:example")])
+            .leaf("ram:Name", &[], "Dupont & Fils <SARL>")
+            .open("ram:Amount", &[])
+            .leaf("ram:Total", &[("currencyID", "EUR")], "10.00");
+
+        assert_eq!(
+            writer.finish(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n".to_owned()
+                + "<rsm:Invoice xmlns:rsm=\"urn:example\">\n"
+                + "  <ram:Name>Dupont &amp; Fils &lt;SARL&gt;</ram:Name>\n"
+                + "  <ram:Amount>\n"
+                + "    <ram:Total currencyID=\"EUR\">10.00</ram:Total>\n"
+                + "  </ram:Amount>\n"
+                + "</rsm:Invoice>\n"
+        );
+    }
+}
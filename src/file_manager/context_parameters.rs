@@ -8,6 +8,10 @@ pub struct ContextParameters<'a> {
     pub(crate) config_file_path: Option<&'a Path>,
     pub(crate) build_path: Option<&'a Path>,
     pub(crate) target_path: Option<&'a Path>,
+    pub(crate) payment_file_path: Option<&'a Path>,
+    pub(crate) quote_path: Option<&'a Path>,
+    pub(crate) recurring_file_path: Option<&'a Path>,
+    pub(crate) draft_path: Option<&'a Path>,
 }
 
 impl<'a> From<&'a Path> for ContextParameters<'a> {
@@ -19,6 +23,10 @@ impl<'a> From<&'a Path> for ContextParameters<'a> {
             config_file_path: None,
             build_path: None,
             target_path: None,
+            payment_file_path: None,
+            quote_path: None,
+            recurring_file_path: None,
+            draft_path: None,
         }
     }
 }
\ No newline at end of file
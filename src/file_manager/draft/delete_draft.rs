@@ -0,0 +1,17 @@
+use std::fs;
+use std::path::Path;
+
+use super::draft_file_manager_error::DraftFileManagerError;
+use super::get_draft::get_draft_file_path;
+
+pub fn delete_draft(path: &Path, draft_id: &str) -> Result<(), DraftFileManagerError> {
+    let file_path = get_draft_file_path(path, draft_id)?;
+
+    if !file_path.is_file() {
+        return Err(DraftFileManagerError::DraftNotFound(draft_id.to_string()));
+    }
+
+    fs::remove_file(&file_path).map_err(|error| {
+        DraftFileManagerError::UnableToWriteDraftFile(file_path.to_string_lossy().to_string(), error)
+    })
+}
@@ -0,0 +1,23 @@
+use std::io::Error;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DraftFileManagerError {
+    #[error("Draft not found: {0}")]
+    DraftNotFound(String),
+
+    #[error("Invalid draft id: {0}")]
+    InvalidDraftId(String),
+
+    #[error("Unable to create draft directory: {0}")]
+    UnableToCreateDirectory(String, #[source] Error),
+
+    #[error("Unable to write draft file: {0}")]
+    UnableToWriteDraftFile(String, #[source] Error),
+
+    #[error("Unable to read path: {0}")]
+    UnableToReadPath(String, #[source] Error),
+
+    #[error("Invalid draft file: {0}")]
+    InvalidDraftFile(String, #[source] serde_yaml::Error),
+}
@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::entities::invoice::Invoice;
+
+use super::draft_file_manager_error::DraftFileManagerError;
+use super::get_draft::get_draft;
+
+/// Drafts by id. A missing draft directory means no draft saved yet.
+pub fn get_all_drafts(path: &Path) -> Result<HashMap<String, Invoice>, DraftFileManagerError> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let dir_content = fs::read_dir(path).map_err(|error| {
+        DraftFileManagerError::UnableToReadPath(path.to_string_lossy().to_string(), error)
+    })?;
+
+    dir_content
+        .map(|file| -> PathBuf { file.unwrap().path() })
+        .filter(|file| {
+            file.is_file() && !file.file_name().unwrap().to_string_lossy().starts_with('.')
+        })
+        .map(|file_path| {
+            let draft_id = file_path.file_stem().unwrap().to_string_lossy().to_string();
+            get_draft(path, &draft_id).map(|draft| (draft_id, draft))
+        })
+        .collect()
+}
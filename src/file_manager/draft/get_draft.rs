@@ -0,0 +1,32 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::entities::invoice::Invoice;
+use crate::entities::numbering_policy::validate_reference;
+
+use super::draft_file_manager_error::DraftFileManagerError;
+
+/// Drafts are files of the drafts folder, their id can not lead elsewhere
+pub(super) fn get_draft_file_path(path: &Path, draft_id: &str) -> Result<PathBuf, DraftFileManagerError> {
+    validate_reference(draft_id).map_err(|_| DraftFileManagerError::InvalidDraftId(draft_id.to_string()))?;
+
+    Ok(path.join(draft_id.to_string() + ".yaml"))
+}
+
+pub fn get_draft(path: &Path, draft_id: &str) -> Result<Invoice, DraftFileManagerError> {
+    let file_path = get_draft_file_path(path, draft_id)?;
+
+    if !file_path.is_file() {
+        return Err(DraftFileManagerError::DraftNotFound(draft_id.to_string()));
+    }
+
+    match fs::read_to_string(&file_path) {
+        Ok(draft_data) => serde_yaml::from_str(&draft_data).map_err(|error| {
+            DraftFileManagerError::InvalidDraftFile(file_path.to_string_lossy().to_string(), error)
+        }),
+        Err(e) => Err(DraftFileManagerError::UnableToReadPath(
+            file_path.to_string_lossy().to_string(),
+            e,
+        )),
+    }
+}
@@ -0,0 +1,5 @@
+pub(super) mod delete_draft;
+mod draft_file_manager_error;
+pub(super) mod get_all_drafts;
+pub(super) mod get_draft;
+pub(super) mod save_draft;
@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::Path;
+
+use crate::entities::invoice::Invoice;
+
+use super::draft_file_manager_error::DraftFileManagerError;
+use super::get_all_drafts::get_all_drafts;
+use super::get_draft::get_draft_file_path;
+
+const DRAFT_ID_PREFIX: &str = "draft-";
+
+fn get_next_draft_id(path: &Path) -> Result<String, DraftFileManagerError> {
+    let last_id = get_all_drafts(path)?
+        .keys()
+        .filter_map(|draft_id| draft_id.strip_prefix(DRAFT_ID_PREFIX)?.parse::<u32>().ok())
+        .max();
+
+    Ok(format!("{}{}", DRAFT_ID_PREFIX, last_id.unwrap_or(0) + 1))
+}
+
+/// Write the draft and return its id, a new id is given when `draft_id` is `None`
+pub fn save_draft(path: &Path, draft_id: Option<&str>, draft: &Invoice) -> Result<String, DraftFileManagerError> {
+    if !path.is_dir() {
+        if let Err(error) = fs::create_dir(path) {
+            return Err(DraftFileManagerError::UnableToCreateDirectory(
+                path.to_string_lossy().to_string(),
+                error,
+            ));
+        }
+    }
+
+    let draft_id = match draft_id {
+        Some(draft_id) => draft_id.to_string(),
+        None => get_next_draft_id(path)?,
+    };
+
+    let file_path = get_draft_file_path(path, &draft_id)?;
+
+    match fs::write(&file_path, serde_yaml::to_string(draft).unwrap()) {
+        Ok(()) => Ok(draft_id),
+        Err(error) => Err(DraftFileManagerError::UnableToWriteDraftFile(
+            file_path.to_string_lossy().to_string(),
+            error,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::invoice::Invoice;
+    use crate::file_manager::draft::delete_draft::delete_draft;
+    use crate::file_manager::draft::get_all_drafts::get_all_drafts;
+    use crate::file_manager::draft::get_draft::get_draft;
+
+    use super::*;
+
+    #[test]
+    fn test_save_draft() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let draft_path = temp_dir_assert_fs.path().join("drafts");
+
+        assert!(get_all_drafts(&draft_path).unwrap().is_empty());
+
+        let mut draft = Invoice {
+            invoice_day_id: None,
+            ..Invoice::generate_simple_invoice_example()
+        };
+
+        assert_eq!(save_draft(&draft_path, None, &draft).unwrap(), "draft-1");
+        assert_eq!(save_draft(&draft_path, None, &draft).unwrap(), "draft-2");
+
+        draft.title = "Edited draft".to_string();
+        save_draft(&draft_path, Some("draft-1"), &draft).expect("Unable to edit draft");
+
+        assert_eq!(get_draft(&draft_path, "draft-1").unwrap().title, "Edited draft");
+        assert!(get_draft(&draft_path, "draft-3").is_err());
+
+        delete_draft(&draft_path, "draft-2").expect("Unable to delete draft");
+        assert!(delete_draft(&draft_path, "draft-2").is_err());
+
+        let all_drafts = get_all_drafts(&draft_path).unwrap();
+        assert_eq!(all_drafts.len(), 1);
+        assert!(all_drafts.contains_key("draft-1"));
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+
+    #[test]
+    fn test_draft_invalid_input() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let draft_path = temp_dir_assert_fs.path().join("drafts");
+        let draft = Invoice {
+            invoice_day_id: None,
+            ..Invoice::generate_simple_invoice_example()
+        };
+
+        // Ids are file names of the drafts folder only
+        for draft_id in ["../invoices/2015031401", "drafts/draft-1", "draft-1.yaml", ""] {
+            assert!(matches!(
+                save_draft(&draft_path, Some(draft_id), &draft),
+                Err(DraftFileManagerError::InvalidDraftId(_))
+            ));
+            assert!(matches!(get_draft(&draft_path, draft_id), Err(DraftFileManagerError::InvalidDraftId(_))));
+            assert!(matches!(delete_draft(&draft_path, draft_id), Err(DraftFileManagerError::InvalidDraftId(_))));
+        }
+
+        assert!(matches!(get_draft(&draft_path, "draft-1"), Err(DraftFileManagerError::DraftNotFound(_))));
+
+        // The ids start again at 1 once all drafts are finalized
+        assert_eq!(save_draft(&draft_path, None, &draft).unwrap(), "draft-1");
+        delete_draft(&draft_path, "draft-1").unwrap();
+        assert_eq!(save_draft(&draft_path, None, &draft).unwrap(), "draft-1");
+
+        fs::write(draft_path.join("draft-2.yaml"), "title: [not a draft").unwrap();
+        assert!(matches!(get_draft(&draft_path, "draft-2"), Err(DraftFileManagerError::InvalidDraftFile(..))));
+        assert!(get_all_drafts(&draft_path).is_err());
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+}
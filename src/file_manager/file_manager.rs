@@ -3,27 +3,49 @@ use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate};
 use log::{error, info};
 
 use crate::entities::customer::Customer;
+use crate::entities::customer_statement::CustomerStatement;
+use crate::entities::factur_x::FacturX;
 use crate::entities::invoice::Invoice;
+use crate::entities::invoice_query::InvoiceQuery;
+use crate::entities::ledger::LedgerEntry;
+use crate::entities::payment::Payment;
+use crate::entities::quote::Quote;
+use crate::entities::recurring_invoice::RecurringInvoice;
 use crate::entities::settings::Settings;
 use crate::file_manager::context_parameters::ContextParameters;
 use crate::file_manager::customer::create_customer::create_customer;
 use crate::file_manager::customer::delete_customer::delete_customer;
 use crate::file_manager::customer::edit_customer::edit_customer;
 use crate::file_manager::customer::get_all_customers::get_all_customers;
+use crate::file_manager::draft::delete_draft::delete_draft;
+use crate::file_manager::draft::get_all_drafts::get_all_drafts;
+use crate::file_manager::draft::get_draft::get_draft;
+use crate::file_manager::draft::save_draft::save_draft;
 use crate::file_manager::invoice::create_invoice::create_invoice;
 use crate::file_manager::invoice::get_all_invoices::get_all_invoices;
 use crate::file_manager::invoice::get_all_invoices_by_day::get_all_invoices_by_day;
 use crate::file_manager::invoice::get_all_invoices_by_month::get_all_invoices_by_month;
+use crate::file_manager::invoice::get_all_invoices_by_range::get_all_invoices_by_range;
 use crate::file_manager::invoice::get_all_invoices_by_year::get_all_invoices_by_year;
 use crate::file_manager::invoice::get_invoice_by_filepath::get_invoice_by_file_path;
+use crate::file_manager::invoice::get_ledger::get_ledger;
+use crate::file_manager::invoice::seal_invoice::seal_existing_invoices;
 use crate::file_manager::invoice_manager_error::InvoiceManagerError;
+use crate::file_manager::payment::add_payment::add_payment;
+use crate::file_manager::payment::get_all_payments::get_all_payments;
+use crate::file_manager::quote::get_all_quotes::get_all_quotes;
+use crate::file_manager::quote::get_quote_by_file_path::get_quote_by_file_path;
+use crate::file_manager::quote::save_quote::save_quote;
+use crate::file_manager::recurring::delete_recurring_invoice::delete_recurring_invoice;
+use crate::file_manager::recurring::get_all_recurring_invoices::get_all_recurring_invoices;
+use crate::file_manager::recurring::save_recurring_invoice::{create_recurring_invoice, save_recurring_invoice};
 use crate::file_manager::settings::get_settings::get_settings;
 use crate::file_manager::settings::save_settings::save_settings;
-use crate::generator::generate_invoice::generate_invoice;
+use crate::generator::generate_invoice::{generate_invoice, generate_quote, generate_statement};
 use crate::invoice_manager::invoice_manager::InvoiceManager;
 
 pub struct FileManager {
@@ -32,6 +54,10 @@ pub struct FileManager {
     settings_file_path: PathBuf,
     build_path: PathBuf,
     target_path: PathBuf,
+    payment_file_path: PathBuf,
+    quote_path: PathBuf,
+    recurring_file_path: PathBuf,
+    draft_path: PathBuf,
 }
 
 impl FileManager {
@@ -40,6 +66,10 @@ impl FileManager {
     const DEFAULT_SETTINGS_FILE_PATH: &'static str = "settings.yaml";
     const DEFAULT_BUILD_PATH: &'static str = "build";
     const DEFAULT_TARGET_PATH: &'static str = "target";
+    const DEFAULT_PAYMENT_FILE_PATH: &'static str = "payments.yaml";
+    const DEFAULT_QUOTE_PATH: &'static str = "quotes";
+    const DEFAULT_RECURRING_FILE_PATH: &'static str = "recurring.yaml";
+    const DEFAULT_DRAFT_PATH: &'static str = "drafts";
 
     fn generate_instance(context_parameters: ContextParameters
     ) -> Result<Self, Box<dyn Error + Sync + Send + 'static>> {
@@ -50,6 +80,10 @@ impl FileManager {
             config_file_path: settings_file_path,
             build_path,
             target_path,
+            payment_file_path,
+            quote_path,
+            recurring_file_path,
+            draft_path,
         } = context_parameters;
 
         if !root_path.exists() && !root_path.parent().unwrap().exists() {
@@ -57,10 +91,9 @@ impl FileManager {
                 "Unable find parent of invoice directory in {}",
                 root_path.to_string_lossy()
             );
-            return Err(Box::try_from(InvoiceManagerError::UnableInitFolderInto(
+            return Err(Box::from(InvoiceManagerError::UnableInitFolderInto(
                 root_path.to_string_lossy().to_string(),
-            ))
-                .unwrap());
+            )));
         }
 
         let invoice_path = match invoice_path {
@@ -88,12 +121,36 @@ impl FileManager {
             None => root_path.to_owned().join(Self::DEFAULT_TARGET_PATH),
         };
 
+        let payment_file_path = match payment_file_path {
+            Some(payment_file_path_given) => payment_file_path_given.to_owned(),
+            None => root_path.to_owned().join(Self::DEFAULT_PAYMENT_FILE_PATH),
+        };
+
+        let quote_path = match quote_path {
+            Some(quote_path_given) => quote_path_given.to_owned(),
+            None => root_path.to_owned().join(Self::DEFAULT_QUOTE_PATH),
+        };
+
+        let recurring_file_path = match recurring_file_path {
+            Some(recurring_file_path_given) => recurring_file_path_given.to_owned(),
+            None => root_path.to_owned().join(Self::DEFAULT_RECURRING_FILE_PATH),
+        };
+
+        let draft_path = match draft_path {
+            Some(draft_path_given) => draft_path_given.to_owned(),
+            None => root_path.to_owned().join(Self::DEFAULT_DRAFT_PATH),
+        };
+
         Ok(FileManager {
             invoice_path,
             customer_file_path,
             settings_file_path,
             build_path,
             target_path,
+            payment_file_path,
+            quote_path,
+            recurring_file_path,
+            draft_path,
         })
     }
     pub fn new(context_parameters: ContextParameters) -> Result<Self, Box<dyn Error + Sync + Send + 'static>> {
@@ -104,6 +161,10 @@ impl FileManager {
             config_file_path: _settings_file_path,
             build_path: _build_path,
             target_path: _target_path,
+            payment_file_path: _payment_file_path,
+            quote_path: _quote_path,
+            recurring_file_path: _recurring_file_path,
+            draft_path: _draft_path,
         } = context_parameters;
 
         let file_manager = Self::generate_instance(context_parameters)?;
@@ -113,10 +174,9 @@ impl FileManager {
                 "Unable access Invoice directory{}\n Maybe Init before use",
                 root_path.to_string_lossy()
             );
-            return Err(Box::try_from(InvoiceManagerError::InvoiceStorePathNotFound(
+            return Err(Box::from(InvoiceManagerError::InvoiceStorePathNotFound(
                 root_path.to_string_lossy().to_string(),
-            ))
-                .unwrap());
+            )));
         }
 
         if !file_manager.invoice_path.is_dir() {
@@ -125,10 +185,9 @@ impl FileManager {
                 file_manager.invoice_path.to_string_lossy()
             );
 
-            return Err(Box::try_from(InvoiceManagerError::InvoiceStorePathNotFound(
+            return Err(Box::from(InvoiceManagerError::InvoiceStorePathNotFound(
                 file_manager.invoice_path.to_string_lossy().to_string(),
-            ))
-                .unwrap());
+            )));
         }
 
         if !file_manager.customer_file_path.exists() {
@@ -138,10 +197,9 @@ impl FileManager {
             );
 
             return Err(
-                Box::try_from(InvoiceManagerError::CustomerStorePathNotFound(
+                Box::from(InvoiceManagerError::CustomerStorePathNotFound(
                     file_manager.customer_file_path.to_string_lossy().to_string(),
-                ))
-                    .unwrap(),
+                )),
             );
         }
 
@@ -156,6 +214,10 @@ impl FileManager {
             config_file_path: _settings_file_path,
             build_path: _build_path,
             target_path: _target_path,
+            payment_file_path: _payment_file_path,
+            quote_path: _quote_path,
+            recurring_file_path: _recurring_file_path,
+            draft_path: _draft_path,
         } = context_parameters;
 
         let file_manager = Self::generate_instance(context_parameters)?;
@@ -167,11 +229,10 @@ impl FileManager {
                     "Unable create root directory in {}",
                     root_path.to_string_lossy()
                 );
-                return Err(Box::try_from(InvoiceManagerError::UnableToCreateDirectory(
+                return Err(Box::from(InvoiceManagerError::UnableToCreateDirectory(
                     root_path.to_string_lossy().to_string(),
                     error,
-                ))
-                    .unwrap());
+                )));
             }
         }
 
@@ -180,16 +241,15 @@ impl FileManager {
                 "Create invoice directory in {}",
                 &file_manager.invoice_path.to_string_lossy()
             );
-            if let Err(error) = fs::create_dir(&file_manager.invoice_path.clone()) {
+            if let Err(error) = fs::create_dir(&file_manager.invoice_path) {
                 error!(
                     "Unable create invoice directory in {}",
                     root_path.to_string_lossy()
                 );
-                return Err(Box::try_from(InvoiceManagerError::UnableToCreateDirectory(
+                return Err(Box::from(InvoiceManagerError::UnableToCreateDirectory(
                     file_manager.invoice_path.to_string_lossy().to_string(),
                     error,
-                ))
-                    .unwrap());
+                )));
             }
         }
 
@@ -203,13 +263,12 @@ impl FileManager {
                     "Unable to create customer file in {}",
                     root_path.to_string_lossy()
                 );
-                return Err(Box::try_from(
+                return Err(Box::from(
                     InvoiceManagerError::UnableToWriteCustomerFile(
                         file_manager.customer_file_path.to_string_lossy().to_string(),
                         error,
                     ),
-                )
-                    .unwrap());
+                ));
             }
         }
 
@@ -223,13 +282,84 @@ impl FileManager {
                     "Unable to create settings file in {}",
                     root_path.to_string_lossy()
                 );
-                return Err(Box::try_from(
+                return Err(Box::from(
                     InvoiceManagerError::UnableToWriteCustomerFile(
                         file_manager.settings_file_path.to_string_lossy().to_string(),
                         error,
                     ),
-                )
-                    .unwrap());
+                ));
+            }
+        }
+
+        if !&file_manager.payment_file_path.exists() {
+            info!(
+                "Create payment file in {}",
+                &file_manager.payment_file_path.to_string_lossy()
+            );
+            if let Err(error) = fs::write(&file_manager.payment_file_path, "") {
+                error!(
+                    "Unable to create payment file in {}",
+                    root_path.to_string_lossy()
+                );
+                return Err(Box::from(
+                    InvoiceManagerError::UnableToWritePaymentFile(
+                        file_manager.payment_file_path.to_string_lossy().to_string(),
+                        error,
+                    ),
+                ));
+            }
+        }
+
+        if !&file_manager.quote_path.is_dir() {
+            info!(
+                "Create quote directory in {}",
+                &file_manager.quote_path.to_string_lossy()
+            );
+            if let Err(error) = fs::create_dir(&file_manager.quote_path) {
+                error!(
+                    "Unable create quote directory in {}",
+                    root_path.to_string_lossy()
+                );
+                return Err(Box::from(InvoiceManagerError::UnableToCreateDirectory(
+                    file_manager.quote_path.to_string_lossy().to_string(),
+                    error,
+                )));
+            }
+        }
+
+        if !&file_manager.recurring_file_path.exists() {
+            info!(
+                "Create recurring invoice file in {}",
+                &file_manager.recurring_file_path.to_string_lossy()
+            );
+            if let Err(error) = fs::write(&file_manager.recurring_file_path, "") {
+                error!(
+                    "Unable to create recurring invoice file in {}",
+                    root_path.to_string_lossy()
+                );
+                return Err(Box::from(
+                    InvoiceManagerError::UnableToWriteRecurringFile(
+                        file_manager.recurring_file_path.to_string_lossy().to_string(),
+                        error,
+                    ),
+                ));
+            }
+        }
+
+        if !&file_manager.draft_path.is_dir() {
+            info!(
+                "Create draft directory in {}",
+                &file_manager.draft_path.to_string_lossy()
+            );
+            if let Err(error) = fs::create_dir(&file_manager.draft_path) {
+                error!(
+                    "Unable create draft directory in {}",
+                    root_path.to_string_lossy()
+                );
+                return Err(Box::from(InvoiceManagerError::UnableToCreateDirectory(
+                    file_manager.draft_path.to_string_lossy().to_string(),
+                    error,
+                )));
             }
         }
 
@@ -246,7 +376,12 @@ impl InvoiceManager for FileManager {
         &self,
         invoice: Invoice,
     ) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>> {
-        create_invoice(self.invoice_path.as_path(), invoice)
+        // Invalid settings stop the issue, the invoice would get the wrong number or rates otherwise
+        let settings = self.get_settings()?;
+
+        let invoice = invoice.resolve_vat_rates(&settings.get_default_vat_rate());
+
+        create_invoice(self.invoice_path.as_path(), invoice, settings.numbering.as_ref())
             .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
     }
     fn get_all_invoices(
@@ -267,6 +402,18 @@ impl InvoiceManager for FileManager {
         )
             .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
     }
+    fn get_ledger(
+        &self,
+    ) -> Result<Vec<LedgerEntry>, Box<dyn Error + Sync + Send + 'static>> {
+        get_ledger(self.invoice_path.as_path())
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+    fn seal_existing_invoices(
+        &self,
+    ) -> Result<usize, Box<dyn Error + Sync + Send + 'static>> {
+        seal_existing_invoices(self.invoice_path.as_path())
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
     fn get_invoice_by_date(
         &self,
         day: NaiveDate,
@@ -292,6 +439,25 @@ impl InvoiceManager for FileManager {
             .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
     }
 
+    fn get_invoice_by_range(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Invoice>, Box<dyn Error + Sync + Send + 'static>> {
+        get_all_invoices_by_range(self.invoice_path.as_path(), from, to)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+    fn search_invoices(
+        &self,
+        query: &InvoiceQuery,
+    ) -> Result<Vec<Invoice>, Box<dyn Error + Sync + Send + 'static>> {
+        let all_invoices = self.get_all_invoices()?;
+        let all_payments = self.get_all_payments()?;
+        let default_vat_rate = self.get_settings()?.get_default_vat_rate();
+
+        Ok(query.apply(&all_invoices, &all_payments, &default_vat_rate, Local::now().date_naive()))
+    }
+
     fn get_all_customers(
         &self,
     ) -> Result<HashMap<String, Customer>, Box<dyn Error + Sync + Send + 'static>> {
@@ -345,8 +511,145 @@ impl InvoiceManager for FileManager {
             .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
     }
 
-    fn generate_invoice<'a>(&self, invoice_path: &Path, filename: &str) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>> {
-        Ok(generate_invoice(&self.build_path, &self.settings_file_path, &self.customer_file_path, &invoice_path, &self.target_path.to_owned().join(filename))?.to_owned())
+    fn get_all_payments(
+        &self,
+    ) -> Result<HashMap<String, Vec<Payment>>, Box<dyn Error + Sync + Send + 'static>> {
+        get_all_payments(&self.payment_file_path)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn get_invoice_payments(
+        &self,
+        invoice_reference: &str,
+    ) -> Result<Vec<Payment>, Box<dyn Error + Sync + Send + 'static>> {
+        Ok(self
+            .get_all_payments()?
+            .remove(invoice_reference)
+            .unwrap_or_default())
+    }
+
+    fn add_payment(
+        &self,
+        invoice_reference: &str,
+        payment: Payment,
+    ) -> Result<Vec<Payment>, Box<dyn Error + Sync + Send + 'static>> {
+        add_payment(&self.payment_file_path, invoice_reference, payment)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn save_quote(
+        &self,
+        quote: Quote,
+    ) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>> {
+        save_quote(&self.quote_path, quote)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn get_all_quotes(&self) -> Result<Vec<Quote>, Box<dyn Error + Sync + Send + 'static>> {
+        get_all_quotes(&self.quote_path)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn get_quote_by_ref(
+        &self,
+        quote_reference: &str,
+    ) -> Result<Quote, Box<dyn Error + Sync + Send + 'static>> {
+        get_quote_by_file_path(&self.quote_path.join(quote_reference.to_string() + ".yaml"))
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn get_all_recurring_invoices(
+        &self,
+    ) -> Result<HashMap<String, RecurringInvoice>, Box<dyn Error + Sync + Send + 'static>> {
+        get_all_recurring_invoices(&self.recurring_file_path)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn create_recurring_invoice(
+        &self,
+        recurring_invoice: RecurringInvoice,
+    ) -> Result<RecurringInvoice, Box<dyn Error + Sync + Send + 'static>> {
+        create_recurring_invoice(&self.recurring_file_path, recurring_invoice)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn save_recurring_invoice(
+        &self,
+        recurring_invoice: RecurringInvoice,
+    ) -> Result<RecurringInvoice, Box<dyn Error + Sync + Send + 'static>> {
+        save_recurring_invoice(&self.recurring_file_path, recurring_invoice)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn remove_recurring_invoice(
+        &self,
+        recurring_ref: &str,
+    ) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+        delete_recurring_invoice(&self.recurring_file_path, recurring_ref)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn save_draft(
+        &self,
+        draft_id: Option<&str>,
+        draft: &Invoice,
+    ) -> Result<String, Box<dyn Error + Sync + Send + 'static>> {
+        save_draft(&self.draft_path, draft_id, draft)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn get_all_drafts(
+        &self,
+    ) -> Result<HashMap<String, Invoice>, Box<dyn Error + Sync + Send + 'static>> {
+        get_all_drafts(&self.draft_path)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn get_draft(
+        &self,
+        draft_id: &str,
+    ) -> Result<Invoice, Box<dyn Error + Sync + Send + 'static>> {
+        get_draft(&self.draft_path, draft_id)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn remove_draft(
+        &self,
+        draft_id: &str,
+    ) -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+        delete_draft(&self.draft_path, draft_id)
+            .map_err(|e| Box::new(e) as Box<dyn Error + Sync + Send + 'static>)
+    }
+
+    fn generate_invoice(&self, invoice_path: &Path, filename: &str) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>> {
+        let invoice = get_invoice_by_file_path(invoice_path)?;
+        let settings = self.get_settings()?;
+
+        // Only read for the Factur-X XML, which borrows the customer until the PDF is written
+        let customers = if settings.factur_x.is_some() { self.get_all_customers()? } else { HashMap::new() };
+        let factur_x = match settings.factur_x {
+            Some(profile) => {
+                let customer = customers
+                    .get(&invoice.customer_id)
+                    .ok_or_else(|| InvoiceManagerError::CustomerNotFound(invoice.customer_id.to_owned()))?;
+
+                Some(FacturX::new(&invoice, customer, &settings, profile))
+            }
+            None => None,
+        };
+
+        Ok(generate_invoice(&self.build_path, &self.settings_file_path, &self.customer_file_path, invoice_path, &invoice, &settings.get_default_vat_rate(), factur_x.as_ref(), &self.target_path.to_owned().join(filename))?.to_owned())
+    }
+
+    fn generate_quote(&self, quote_path: &Path, filename: &str) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>> {
+        let quote = get_quote_by_file_path(quote_path)?;
+        let default_vat_rate = self.get_settings()?.get_default_vat_rate();
+
+        Ok(generate_quote(&self.build_path, &self.settings_file_path, &self.customer_file_path, quote_path, &quote, &default_vat_rate, &self.target_path.to_owned().join(filename))?.to_owned())
+    }
+
+    fn generate_statement(&self, statement: &CustomerStatement, filename: &str) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>> {
+        Ok(generate_statement(&self.build_path, &self.settings_file_path, &self.customer_file_path, statement, &self.target_path.to_owned().join(filename))?.to_owned())
     }
 }
 
@@ -354,8 +657,13 @@ impl InvoiceManager for FileManager {
 mod tests {
     use chrono::NaiveDate;
 
+    use crate::entities::amount::Amount;
+    use crate::entities::document_kind::DocumentKind;
     use crate::entities::invoice::InvoiceDayId;
     use crate::entities::product::Product;
+    use crate::entities::quantity::Quantity;
+    use crate::entities::settings::Settings;
+    use crate::entities::vat_rate::VatRate;
 
     use super::*;
 
@@ -381,12 +689,16 @@ mod tests {
 
         let file_manager = FileManager::generate_instance(
             ContextParameters {
-                invoice_manager_path: &temp_dir,
+                invoice_manager_path: temp_dir,
                 invoice_path: Some(&(temp_dir.to_owned().join("custom_invoice_folder"))),
                 customer_file_path: Some(&(temp_dir.to_owned().join("custom_enterprise"))),
                 config_file_path: Some(&(temp_dir.to_owned().join("custom_settings"))),
                 build_path: Some(&(temp_dir.to_owned().join("custom_build"))),
                 target_path: Some(&(temp_dir.to_owned().join("custom_target"))),
+                payment_file_path: Some(&(temp_dir.to_owned().join("custom_payments"))),
+                quote_path: Some(&(temp_dir.to_owned().join("custom_quotes"))),
+                recurring_file_path: Some(&(temp_dir.to_owned().join("custom_recurring"))),
+                draft_path: Some(&(temp_dir.to_owned().join("custom_drafts"))),
             })
             .expect("Unable initiate file manager");
 
@@ -402,6 +714,22 @@ mod tests {
             file_manager.settings_file_path,
             temp_dir.to_owned().join("custom_settings")
         );
+        assert_eq!(
+            file_manager.payment_file_path,
+            temp_dir.to_owned().join("custom_payments")
+        );
+        assert_eq!(
+            file_manager.quote_path,
+            temp_dir.to_owned().join("custom_quotes")
+        );
+        assert_eq!(
+            file_manager.recurring_file_path,
+            temp_dir.to_owned().join("custom_recurring")
+        );
+        assert_eq!(
+            file_manager.draft_path,
+            temp_dir.to_owned().join("custom_drafts")
+        );
 
         temp_dir_assert_fs.close().unwrap();
     }
@@ -447,20 +775,38 @@ mod tests {
         assert!(temp_dir_assert_fs
             .join(FileManager::DEFAULT_SETTINGS_FILE_PATH)
             .exists());
+        assert!(temp_dir_assert_fs
+            .join(FileManager::DEFAULT_PAYMENT_FILE_PATH)
+            .exists());
+        assert!(temp_dir_assert_fs.join(FileManager::DEFAULT_QUOTE_PATH).exists());
+        assert!(temp_dir_assert_fs
+            .join(FileManager::DEFAULT_RECURRING_FILE_PATH)
+            .exists());
+        assert!(temp_dir_assert_fs.join(FileManager::DEFAULT_DRAFT_PATH).exists());
+
+        file_manager.edit_settings(Settings::generate_simple_settings()).unwrap();
 
         for index in 1..=5 {
             let simple_product = Product {
                 description: "Product example".to_string(),
-                quantity: index as f32,
-                price: 350.0,
+                quantity: Quantity::from(index),
+                price: Amount::new("350").unwrap(),
+                vat: None,
+                activity: None,
             };
 
             let invoice_example = Invoice {
                 invoice_day_id: Some(InvoiceDayId::new(&index.to_string()).unwrap()),
                 date: NaiveDate::from_ymd_opt(2015, 3, 14).unwrap(),
+                due_date: None,
                 customer_id: "king".to_string(),
                 title: "Test invoice for simple customer".to_string(),
                 products: vec![simple_product],
+                kind: DocumentKind::Invoice,
+                credited_invoice_ref: None,
+                quote_ref: None,
+                reference: None,
+                sequence: None,
             };
 
             file_manager
@@ -487,4 +833,66 @@ mod tests {
 
         temp_dir_assert_fs.close().unwrap();
     }
+
+    #[test]
+    pub fn issued_invoice_keeps_its_vat_rate() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+
+        let file_manager = FileManager::init(ContextParameters::from(temp_dir_assert_fs.path()))
+            .expect("Unable initiate file manager");
+
+        file_manager.edit_settings(Settings::generate_simple_settings()).unwrap();
+
+        let invoice_path = file_manager
+            .create_invoice(Invoice::generate_simple_invoice_with_id_and_date_example(
+                None,
+                NaiveDate::from_ymd_opt(2015, 3, 14).unwrap(),
+            ))
+            .unwrap();
+        let reference = invoice_path.file_stem().unwrap().to_string_lossy().to_string();
+
+        let invoice = file_manager.get_invoice_by_ref(&reference).unwrap();
+        assert_eq!(
+            invoice.products[0].vat,
+            Some(VatRate::Exempt { reason: VatRate::FRANCHISE_REASON.to_string() })
+        );
+
+        // Filling the TVA number changes the default rate of the next invoices only
+        let mut settings = Settings::generate_simple_settings();
+        settings.enterprise.tva = "FR12123456789".to_string();
+        let default_vat_rate = settings.get_default_vat_rate();
+        file_manager.edit_settings(settings).unwrap();
+
+        let invoice = file_manager.get_invoice_by_ref(&reference).unwrap();
+        assert_eq!(default_vat_rate, VatRate::Normal);
+        let totals = invoice.get_totals(&default_vat_rate);
+        assert_eq!(totals.total_vat, Amount::ZERO);
+        assert_eq!(totals.total_ttc, Amount::new("350").unwrap());
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+
+    #[test]
+    pub fn invalid_settings_stop_the_issue() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+
+        let file_manager = FileManager::init(ContextParameters::from(temp_dir_assert_fs.path()))
+            .expect("Unable initiate file manager");
+
+        let invoice = Invoice::generate_simple_invoice_with_id_and_date_example(
+            None,
+            NaiveDate::from_ymd_opt(2015, 3, 14).unwrap(),
+        );
+
+        // Not filled yet
+        assert!(file_manager.create_invoice(invoice.clone()).is_err());
+
+        let settings = serde_yaml::to_string(&Settings::generate_simple_settings()).unwrap();
+        fs::write(&file_manager.settings_file_path, settings + "numbering:\n  pattern: [FAC\n").unwrap();
+        assert!(file_manager.create_invoice(invoice).is_err());
+
+        assert!(file_manager.get_all_invoices().unwrap().is_empty());
+
+        temp_dir_assert_fs.close().unwrap();
+    }
 }
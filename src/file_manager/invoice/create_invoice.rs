@@ -1,39 +1,69 @@
+use super::get_all_invoices::get_all_invoices;
 use super::get_all_invoices_by_day::get_all_invoices_by_day;
 use super::invoice_file_manager_error::InvoiceFileManagerError;
+use super::seal_invoice::{check_ledger, seal_invoice};
 use crate::entities::invoice::{Invoice, InvoiceDayId};
+use crate::entities::numbering_policy::NumberingPolicy;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-pub fn assign_next_id_to_invoice(path: &Path, invoice: Invoice) -> Invoice {
-    let last_id = get_all_invoices_by_day(path, invoice.date)
-        .unwrap()
+/// Next day id of the kind of the invoice, an error once the 99 ids of the day are used
+pub fn assign_next_id_to_invoice(path: &Path, invoice: Invoice) -> Result<Invoice, InvoiceFileManagerError> {
+    let last_id = get_all_invoices_by_day(path, invoice.date)?
         .iter()
-        .map(|invoice| {
+        .filter(|invoice_of_day| invoice_of_day.kind == invoice.kind)
+        .filter_map(|invoice| {
             invoice
                 .invoice_day_id
-                .clone()
-                .unwrap()
-                .to_string()
-                .parse::<u8>()
-                .map_err(|_| "Invalid number id".to_string())
-                .unwrap_or(0)
+                .as_ref()
+                .map(|invoice_day_id| invoice_day_id.to_string().parse::<u8>().unwrap_or(0))
         })
-        .max();
+        .max()
+        .unwrap_or(0);
 
-    let invoice_day_id = match last_id {
-        Some(last_id) => InvoiceDayId::new(&(last_id + 1).to_string()).unwrap(),
-        None => InvoiceDayId::new("01").unwrap(),
-    };
+    let invoice_day_id = InvoiceDayId::new(&(last_id + 1).to_string())
+        .map_err(|_| InvoiceFileManagerError::NoInvoiceDayIdLeft(invoice.date.to_string()))?;
 
-    Invoice {
-        invoice_day_id: Some(InvoiceDayId::new(&invoice_day_id.to_string()).unwrap()),
+    Ok(Invoice {
+        invoice_day_id: Some(invoice_day_id),
         ..invoice
-    }
+    })
+}
+
+/// Next reference of the series of the invoice. The invoices without sequence were numbered
+/// before the policy or imported, those whose reference follows the pattern are part of the series.
+pub fn assign_next_number_to_invoice(
+    path: &Path,
+    invoice: Invoice,
+    numbering_policy: &NumberingPolicy,
+) -> Result<Invoice, InvoiceFileManagerError> {
+    numbering_policy
+        .validate()
+        .map_err(InvoiceFileManagerError::InvalidNumberingPolicy)?;
+
+    let last_sequence = get_all_invoices(path)?
+        .iter()
+        .filter(|other_invoice| {
+            other_invoice.kind == invoice.kind
+                && numbering_policy.is_same_series(other_invoice.date, invoice.date)
+        })
+        .filter_map(|other_invoice| numbering_policy.get_sequence(other_invoice))
+        .max()
+        .unwrap_or(0);
+
+    let sequence = last_sequence + 1;
+
+    Ok(Invoice {
+        reference: Some(numbering_policy.format_reference(invoice.kind, invoice.date, sequence)),
+        sequence: Some(sequence),
+        ..invoice
+    })
 }
 
 pub fn create_invoice(
     path: &Path,
     invoice: Invoice,
+    numbering_policy: Option<&NumberingPolicy>,
 ) -> Result<PathBuf, InvoiceFileManagerError> {
     if !path.is_dir() {
         return Err(InvoiceFileManagerError::InvoiceStorePathNotFound(
@@ -41,24 +71,42 @@ pub fn create_invoice(
         ));
     }
 
-    let invoice = match invoice.invoice_day_id {
-        Some(_) => invoice,
-        None => assign_next_id_to_invoice(path, invoice),
+    let invoice = match (invoice.get_ref(), numbering_policy) {
+        (Some(_), _) => invoice,
+        (None, Some(numbering_policy)) => assign_next_number_to_invoice(path, invoice, numbering_policy)?,
+        (None, None) => assign_next_id_to_invoice(path, invoice)?,
     };
 
     let file_path = path.to_owned().join(invoice.get_ref().unwrap() + ".yaml");
 
-    match fs::write(file_path.clone(), serde_yaml::to_string(&invoice).unwrap()) {
-        Ok(()) => Ok(file_path.as_path().to_owned()),
-        Err(error) => Err(InvoiceFileManagerError::UnableToWriteInvoiceFile(error)),
+    // An invoice is never replaced once issued
+    if file_path.exists() {
+        return Err(InvoiceFileManagerError::InvoiceAlreadyExists(
+            invoice.get_ref().unwrap(),
+        ));
     }
+
+    // Checked before writing, an invoice which can not be sealed is not issued
+    check_ledger(path, &invoice)?;
+
+    if let Err(error) = fs::write(file_path.clone(), serde_yaml::to_string(&invoice).unwrap()) {
+        return Err(InvoiceFileManagerError::UnableToWriteInvoiceFile(error));
+    }
+
+    seal_invoice(path, &invoice)?;
+
+    Ok(file_path)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::amount::Amount;
+    use crate::entities::document_kind::DocumentKind;
     use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::numbering_policy::CounterReset;
     use crate::entities::product::Product;
+    use crate::entities::quantity::Quantity;
     use chrono::NaiveDate;
     use std::env;
 
@@ -76,19 +124,27 @@ mod tests {
 
         let simple_product = Product {
             description: "Product example".to_string(),
-            quantity: 1.0,
-            price: 350.0,
+            quantity: Quantity::from(1),
+            price: Amount::new("350").unwrap(),
+            vat: None,
+            activity: None,
         };
 
         let invoice_example = Invoice {
             invoice_day_id: Some(InvoiceDayId::new("02").unwrap()),
             date: NaiveDate::from_ymd_opt(2020, 3, 14).unwrap(),
+            due_date: None,
             customer_id: "king".to_string(),
             title: "Test invoice reference".to_string(),
             products: vec![simple_product],
+            kind: DocumentKind::Invoice,
+            credited_invoice_ref: None,
+            quote_ref: None,
+            reference: None,
+            sequence: None,
         };
 
-        let invoice_created = create_invoice(&temp_dir, invoice_example);
+        let invoice_created = create_invoice(&temp_dir, invoice_example, None);
         assert!(invoice_created.is_ok());
 
         let invoice_path = invoice_created.unwrap();
@@ -98,6 +154,15 @@ mod tests {
         );
         assert!(invoice_path.exists());
 
+        let invoice_example = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("02").unwrap()),
+            NaiveDate::from_ymd_opt(2020, 3, 14).unwrap(),
+        );
+        assert!(
+            create_invoice(&temp_dir, invoice_example, None).is_err(),
+            "An existing invoice must not be replaced"
+        );
+
         fs::remove_dir_all(temp_dir).expect("Unable remove temp dir folder");
     }
 
@@ -137,7 +202,7 @@ mod tests {
         ]
         .into_iter()
         .for_each(|invoice| {
-            create_invoice(temp_dir.as_path(), invoice)
+            create_invoice(temp_dir.as_path(), invoice, None)
                 .expect("Unable create test invoice");
         });
 
@@ -149,7 +214,7 @@ mod tests {
             NaiveDate::from_ymd_opt(2020, 1, 2).unwrap(),
         );
 
-        let new_invoice = assign_next_id_to_invoice(temp_dir.as_path(), invoice_example);
+        let new_invoice = assign_next_id_to_invoice(temp_dir.as_path(), invoice_example).unwrap();
 
         assert_eq!(
             new_invoice.invoice_day_id,
@@ -162,7 +227,7 @@ mod tests {
             NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
         );
 
-        let new_invoice = assign_next_id_to_invoice(temp_dir.as_path(), invoice_example);
+        let new_invoice = assign_next_id_to_invoice(temp_dir.as_path(), invoice_example).unwrap();
 
         assert_eq!(
             new_invoice.invoice_day_id,
@@ -175,12 +240,178 @@ mod tests {
             NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(),
         );
 
-        let new_invoice = assign_next_id_to_invoice(temp_dir.as_path(), invoice_example);
+        let new_invoice = assign_next_id_to_invoice(temp_dir.as_path(), invoice_example).unwrap();
 
         assert_eq!(
             new_invoice.invoice_day_id,
             Some(InvoiceDayId::new("05").unwrap()),
             "Unable have multiple ID when we have multiple invoices on the day"
         );
+
+        let invoice_example = Invoice {
+            kind: DocumentKind::CreditNote,
+            ..Invoice::generate_simple_invoice_with_id_and_date_example(
+                None,
+                NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(),
+            )
+        };
+
+        let new_credit_note = assign_next_id_to_invoice(temp_dir.as_path(), invoice_example).unwrap();
+
+        assert_eq!(
+            new_credit_note.invoice_day_id,
+            Some(InvoiceDayId::new("01").unwrap()),
+            "Unable have a numbering series by document kind"
+        );
+        assert_eq!(new_credit_note.get_ref(), Some("AV2020030101".to_string()));
+    }
+
+    #[test]
+    pub fn test_assign_invoice_id_after_last_of_day() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let temp_dir = temp_dir_assert_fs.path();
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+
+        create_invoice(
+            temp_dir,
+            Invoice::generate_simple_invoice_with_id_and_date_example(Some(InvoiceDayId::new("99").unwrap()), date),
+            None,
+        )
+        .unwrap();
+
+        let result = create_invoice(
+            temp_dir,
+            Invoice::generate_simple_invoice_with_id_and_date_example(None, date),
+            None,
+        );
+        assert!(matches!(result, Err(InvoiceFileManagerError::NoInvoiceDayIdLeft(_))));
+
+        // Credit notes have their own day ids
+        let credit_note = Invoice {
+            kind: DocumentKind::CreditNote,
+            ..Invoice::generate_simple_invoice_with_id_and_date_example(None, date)
+        };
+        assert_eq!(
+            assign_next_id_to_invoice(temp_dir, credit_note).unwrap().get_ref(),
+            Some("AV2020010101".to_string())
+        );
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+
+    #[test]
+    pub fn test_assign_invoice_number() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let temp_dir = temp_dir_assert_fs.path();
+
+        let numbering_policy = NumberingPolicy::new("FAC-{YYYY}-{SEQ:5}", CounterReset::Yearly).unwrap();
+
+        create_invoice(
+            temp_dir,
+            Invoice::generate_simple_invoice_with_id_and_date_example(
+                Some(InvoiceDayId::new("01").unwrap()),
+                NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            ),
+            Some(&numbering_policy),
+        )
+        .expect("Unable create historical invoice");
+
+        [(2020, 1, 2), (2020, 3, 1), (2021, 3, 1)].iter().for_each(|(year, month, day)| {
+            create_invoice(
+                temp_dir,
+                Invoice::generate_simple_invoice_with_id_and_date_example(
+                    None,
+                    NaiveDate::from_ymd_opt(*year, *month, *day).unwrap(),
+                ),
+                Some(&numbering_policy),
+            )
+            .expect("Unable create numbered invoice");
+        });
+
+        assert!(temp_dir.join("2020010101.yaml").exists());
+        assert!(temp_dir.join("FAC-2020-00001.yaml").exists());
+        assert!(temp_dir.join("FAC-2020-00002.yaml").exists());
+        assert!(temp_dir.join("FAC-2021-00001.yaml").exists());
+
+        let new_invoice = assign_next_number_to_invoice(
+            temp_dir,
+            Invoice::generate_simple_invoice_with_id_and_date_example(
+                None,
+                NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            ),
+            &numbering_policy,
+        )
+        .unwrap();
+        assert_eq!(new_invoice.get_ref(), Some("FAC-2020-00003".to_string()));
+        assert_eq!(new_invoice.sequence, Some(3));
+
+        let new_credit_note = assign_next_number_to_invoice(
+            temp_dir,
+            Invoice {
+                kind: DocumentKind::CreditNote,
+                ..Invoice::generate_simple_invoice_with_id_and_date_example(
+                    None,
+                    NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                )
+            },
+            &numbering_policy,
+        )
+        .unwrap();
+        assert_eq!(new_credit_note.get_ref(), Some("AVFAC-2020-00001".to_string()));
+
+        let continuous_policy = NumberingPolicy::new("FAC-{YYYY}-{SEQ:5}", CounterReset::Continuous).unwrap();
+        let new_invoice = assign_next_number_to_invoice(
+            temp_dir,
+            Invoice::generate_simple_invoice_with_id_and_date_example(
+                None,
+                NaiveDate::from_ymd_opt(2021, 6, 1).unwrap(),
+            ),
+            &continuous_policy,
+        )
+        .unwrap();
+        assert_eq!(new_invoice.get_ref(), Some("FAC-2021-00003".to_string()));
+
+        // Historical invoice of the same day still get their day id
+        let legacy_invoice = assign_next_id_to_invoice(
+            temp_dir,
+            Invoice::generate_simple_invoice_with_id_and_date_example(
+                None,
+                NaiveDate::from_ymd_opt(2020, 1, 2).unwrap(),
+            ),
+        )
+        .unwrap();
+        assert_eq!(legacy_invoice.get_ref(), Some("2020010201".to_string()));
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+
+    #[test]
+    pub fn test_assign_invoice_number_after_import() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let temp_dir = temp_dir_assert_fs.path();
+        let date = NaiveDate::from_ymd_opt(2023, 5, 2).unwrap();
+
+        // Imported invoices keep their reference and have no sequence
+        create_invoice(
+            temp_dir,
+            Invoice {
+                reference: Some("FAC-2023-00012".to_string()),
+                ..Invoice::generate_simple_invoice_with_id_and_date_example(None, date)
+            },
+            None,
+        )
+        .expect("Unable create imported invoice");
+
+        let numbering_policy = NumberingPolicy::new("FAC-{YYYY}-{SEQ:5}", CounterReset::Yearly).unwrap();
+        let new_invoice = assign_next_number_to_invoice(
+            temp_dir,
+            Invoice::generate_simple_invoice_with_id_and_date_example(None, date),
+            &numbering_policy,
+        )
+        .unwrap();
+        assert_eq!(new_invoice.get_ref(), Some("FAC-2023-00013".to_string()));
+        assert_eq!(new_invoice.sequence, Some(13));
+
+        temp_dir_assert_fs.close().unwrap();
     }
 }
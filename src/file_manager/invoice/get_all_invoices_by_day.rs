@@ -1,26 +1,14 @@
-use super::get_all_invoices_path::get_all_invoices_path;
-use super::get_invoice_by_filepath::get_invoice_by_file_path;
+use super::get_all_invoices_by_period::get_all_invoices_by_period;
 use super::invoice_file_manager_error::InvoiceFileManagerError;
 use crate::entities::invoice::Invoice;
 use chrono::NaiveDate;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 pub fn get_all_invoices_by_day(
     path: &Path,
     date: NaiveDate,
 ) -> Result<Vec<Invoice>, InvoiceFileManagerError> {
-    get_all_invoices_path(path)
-        .unwrap()
-        .iter()
-        .filter(|file_path| {
-            file_path
-                .file_name()
-                .unwrap()
-                .to_string_lossy()
-                .starts_with(&date.format("%Y%m%d").to_string())
-        })
-        .map(|file_path: &PathBuf| get_invoice_by_file_path(file_path))
-        .collect()
+    get_all_invoices_by_period(path, date, "%Y%m%d")
 }
 
 #[cfg(test)]
@@ -38,6 +26,7 @@ mod tests {
             "2020030101.yaml".to_string(),
             ".gitignore".to_string(),
             "2020030102.yaml".to_string(),
+            "AV2020030101.yaml".to_string(),
             "2021031001.yaml".to_string(),
         ]
         .iter()
@@ -67,7 +56,7 @@ mod tests {
         assert!(all_invoices_path_result.is_ok());
 
         let all_invoices_path = all_invoices_path_result.unwrap();
-        assert_eq!(all_invoices_path.len(), 2);
+        assert_eq!(all_invoices_path.len(), 3);
 
         assert!(all_invoices_path
             .iter()
@@ -1,9 +1,8 @@
-use super::get_all_invoices_path::get_all_invoices_path;
-use super::get_invoice_by_filepath::get_invoice_by_file_path;
+use super::get_all_invoices_by_period::get_all_invoices_by_period;
 use super::invoice_file_manager_error::InvoiceFileManagerError;
 use crate::entities::invoice::Invoice;
 use chrono::NaiveDate;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 pub fn get_all_invoices_by_month(
     path: &Path,
@@ -12,18 +11,7 @@ pub fn get_all_invoices_by_month(
 ) -> Result<Vec<Invoice>, InvoiceFileManagerError> {
     let date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
 
-    get_all_invoices_path(path)
-        .unwrap()
-        .iter()
-        .filter(|file_path| {
-            file_path
-                .file_name()
-                .unwrap()
-                .to_string_lossy()
-                .starts_with(&date.format("%Y%m").to_string())
-        })
-        .map(|file_path: &PathBuf| get_invoice_by_file_path(file_path))
-        .collect()
+    get_all_invoices_by_period(path, date, "%Y%m")
 }
 
 #[cfg(test)]
@@ -0,0 +1,75 @@
+use super::get_all_invoices_by_range::get_reference_date;
+use super::get_all_invoices_path::get_all_invoices_path;
+use super::get_invoice_by_filepath::get_invoice_by_file_path;
+use super::invoice_file_manager_error::InvoiceFileManagerError;
+use crate::entities::invoice::Invoice;
+use chrono::NaiveDate;
+use std::path::Path;
+
+/// Invoices of the period of `date`, given by a format like `%Y%m` for its month.
+/// Dated references are filtered on their file name, references of a numbering
+/// policy need the invoice date.
+pub fn get_all_invoices_by_period(
+    path: &Path,
+    date: NaiveDate,
+    period_format: &str,
+) -> Result<Vec<Invoice>, InvoiceFileManagerError> {
+    let period = date.format(period_format).to_string();
+
+    get_all_invoices_path(path)?
+        .iter()
+        .filter_map(|file_path| {
+            let file_stem = file_path.file_stem().unwrap().to_string_lossy();
+            let reference = file_stem.trim_start_matches(char::is_alphabetic);
+
+            if get_reference_date(reference).is_some() {
+                return reference
+                    .starts_with(&period)
+                    .then(|| get_invoice_by_file_path(file_path));
+            }
+
+            match get_invoice_by_file_path(file_path) {
+                Ok(invoice) if invoice.date.format(period_format).to_string() != period => None,
+                result => Some(result),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    pub fn test_get_all_invoices_by_period() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let temp_dir = temp_dir_assert_fs.path();
+
+        let invoice_yaml = |day: &str, month: &str| {
+            "date:\n".to_owned()
+                + "  day: '" + day + "'\n"
+                + "  month: '" + month + "'\n"
+                + "  year: '2020'\n"
+                + "customer_id: king\n"
+                + "title: Test invoice for simple customer\n"
+                + "invoice_day_id: null\n"
+                + "products: []\n"
+                + "reference: FAC-2020-00001\n"
+                + "sequence: 1\n"
+        };
+
+        fs::write(temp_dir.join("2020030101.yaml"), invoice_yaml("01", "03")).unwrap();
+        fs::write(temp_dir.join("FAC-2020-00001.yaml"), invoice_yaml("01", "03")).unwrap();
+        fs::write(temp_dir.join("FAC-2020-00002.yaml"), invoice_yaml("15", "03")).unwrap();
+        fs::write(temp_dir.join("FAC-2020-00003.yaml"), invoice_yaml("01", "04")).unwrap();
+
+        let day = NaiveDate::from_ymd_opt(2020, 3, 1).unwrap();
+
+        assert_eq!(get_all_invoices_by_period(temp_dir, day, "%Y%m%d").unwrap().len(), 2);
+        assert_eq!(get_all_invoices_by_period(temp_dir, day, "%Y%m").unwrap().len(), 3);
+        assert_eq!(get_all_invoices_by_period(temp_dir, day, "%Y").unwrap().len(), 4);
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+}
@@ -0,0 +1,82 @@
+use super::get_all_invoices_path::get_all_invoices_path;
+use super::get_invoice_by_filepath::get_invoice_by_file_path;
+use super::invoice_file_manager_error::InvoiceFileManagerError;
+use crate::entities::invoice::Invoice;
+use chrono::NaiveDate;
+use std::path::Path;
+
+/// Date of a reference built from the date and the day id (`[prefix]YYYYMMDDNN`)
+pub(super) fn get_reference_date(reference: &str) -> Option<NaiveDate> {
+    if reference.len() != 10 || !reference.chars().all(|character| character.is_ascii_digit()) {
+        return None;
+    }
+
+    NaiveDate::parse_from_str(&reference[..8], "%Y%m%d").ok()
+}
+
+/// Invoices dated between `from` and `to` included, in one scan of the directory.
+/// Dated references are filtered on their file name, references of a numbering
+/// policy need the invoice date.
+pub fn get_all_invoices_by_range(
+    path: &Path,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<Invoice>, InvoiceFileManagerError> {
+    let in_range = |date: NaiveDate| date >= from && date <= to;
+
+    get_all_invoices_path(path)?
+        .iter()
+        .filter_map(|file_path| {
+            let file_stem = file_path.file_stem().unwrap().to_string_lossy();
+            let reference = file_stem.trim_start_matches(char::is_alphabetic);
+
+            if let Some(date) = get_reference_date(reference) {
+                return in_range(date).then(|| get_invoice_by_file_path(file_path));
+            }
+
+            match get_invoice_by_file_path(file_path) {
+                Ok(invoice) if !in_range(invoice.date) => None,
+                result => Some(result),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    pub fn test_get_all_invoices_by_range() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let temp_dir = temp_dir_assert_fs.path();
+
+        let invoice_yaml = |day: &str, month: &str| {
+            "date:\n".to_owned()
+                + "  day: '" + day + "'\n"
+                + "  month: '" + month + "'\n"
+                + "  year: '2020'\n"
+                + "customer_id: king\n"
+                + "title: Test invoice for simple customer\n"
+                + "invoice_day_id: null\n"
+                + "products: []\n"
+                + "reference: FAC-2020-00001\n"
+                + "sequence: 1\n"
+        };
+
+        fs::write(temp_dir.join("2020022901.yaml"), invoice_yaml("29", "02")).unwrap();
+        fs::write(temp_dir.join("2020030101.yaml"), invoice_yaml("01", "03")).unwrap();
+        fs::write(temp_dir.join("FAC-2020-00001.yaml"), invoice_yaml("15", "03")).unwrap();
+        fs::write(temp_dir.join("FAC-2020-00002.yaml"), invoice_yaml("01", "04")).unwrap();
+
+        let from = NaiveDate::from_ymd_opt(2020, 3, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2020, 3, 31).unwrap();
+
+        assert_eq!(get_all_invoices_by_range(temp_dir, from, to).unwrap().len(), 2);
+        assert_eq!(get_all_invoices_by_range(temp_dir, from, from).unwrap().len(), 1);
+        assert_eq!(get_all_invoices_by_range(temp_dir, to, from).unwrap().len(), 0);
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+}
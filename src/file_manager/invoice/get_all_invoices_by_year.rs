@@ -1,25 +1,14 @@
-use super::get_all_invoices_path::get_all_invoices_path;
-use super::get_invoice_by_filepath::get_invoice_by_file_path;
+use super::get_all_invoices_by_period::get_all_invoices_by_period;
 use super::invoice_file_manager_error::InvoiceFileManagerError;
 use crate::entities::invoice::Invoice;
-use std::path::{Path, PathBuf};
+use chrono::NaiveDate;
+use std::path::Path;
 
 pub fn get_all_invoices_by_year(
     path: &Path,
     year: i32,
 ) -> Result<Vec<Invoice>, InvoiceFileManagerError> {
-    get_all_invoices_path(path)
-        .unwrap()
-        .iter()
-        .filter(|file_path| {
-            file_path
-                .file_name()
-                .unwrap()
-                .to_string_lossy()
-                .starts_with(&year.to_string())
-        })
-        .map(|file_path: &PathBuf| get_invoice_by_file_path(file_path))
-        .collect()
+    get_all_invoices_by_period(path, NaiveDate::from_ymd_opt(year, 1, 1).unwrap(), "%Y")
 }
 
 #[cfg(test)]
@@ -4,7 +4,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 pub fn is_hidden_file(file_name: &OsStr) -> bool {
-    file_name.to_string_lossy().chars().next().unwrap() == '.'
+    file_name.to_string_lossy().starts_with('.')
 }
 
 pub fn get_all_invoices_path(
@@ -1,10 +1,10 @@
 use super::invoice_file_manager_error::InvoiceFileManagerError;
 use crate::entities::invoice::Invoice;
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 
 pub fn get_invoice_by_file_path(
-    file_path: &PathBuf,
+    file_path: &Path,
 ) -> Result<Invoice, InvoiceFileManagerError> {
     match fs::read_to_string(file_path) {
         Ok(invoice_data) => {
@@ -21,6 +21,7 @@ pub fn get_invoice_by_file_path(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::amount::Amount;
     use crate::entities::invoice::InvoiceDayId;
     use chrono::NaiveDate;
     use std::env;
@@ -67,7 +68,7 @@ mod tests {
         );
         assert_eq!(invoice_data.title, "Test invoice for simple customer");
         assert_eq!(invoice_data.products.len(), 1);
-        assert_eq!(invoice_data.get_total_price(), 350.0);
+        assert_eq!(invoice_data.get_total_price(), Amount::new("350").unwrap());
 
         fs::remove_dir_all(temp_dir).expect("Unable remove temp dir folder");
     }
@@ -0,0 +1,30 @@
+use super::invoice_file_manager_error::InvoiceFileManagerError;
+use crate::entities::ledger::LedgerEntry;
+use std::fs;
+use std::path::Path;
+
+/// Hidden, so it is never read as an invoice
+pub const LEDGER_FILE_NAME: &str = ".ledger.yaml";
+
+/// Links of the hash chain of the invoices in `path`, empty before the first seal
+pub fn get_ledger(path: &Path) -> Result<Vec<LedgerEntry>, InvoiceFileManagerError> {
+    let ledger_file_path = path.join(LEDGER_FILE_NAME);
+
+    if !ledger_file_path.exists() {
+        return Ok(vec![]);
+    }
+
+    match fs::read_to_string(&ledger_file_path) {
+        Ok(ledger_data) if ledger_data.trim().is_empty() => Ok(vec![]),
+        Ok(ledger_data) => serde_yaml::from_str(&ledger_data).map_err(|error| {
+            InvoiceFileManagerError::InvalidLedgerFile(
+                ledger_file_path.to_string_lossy().to_string(),
+                error,
+            )
+        }),
+        Err(e) => Err(InvoiceFileManagerError::UnableToReadPath(
+            ledger_file_path.to_string_lossy().to_string(),
+            e,
+        )),
+    }
+}
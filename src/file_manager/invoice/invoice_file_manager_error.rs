@@ -11,4 +11,25 @@ pub enum InvoiceFileManagerError {
 
     #[error("Unable to read path: {0}")]
     UnableToReadPath(String, #[source] Error),
+
+    #[error("Invoice already exists: {0}")]
+    InvoiceAlreadyExists(String),
+
+    #[error("Unable to parse ledger file: {0}")]
+    InvalidLedgerFile(String, #[source] serde_yaml::Error),
+
+    #[error("Unable to write ledger file: {0}")]
+    UnableToWriteLedgerFile(String, #[source] Error),
+
+    #[error("Invalid numbering policy: {0}")]
+    InvalidNumberingPolicy(String),
+
+    #[error("Ledger not found in {0}, seal the invoices issued before it with `verify --init`")]
+    LedgerNotFound(String),
+
+    #[error("Ledger already exists in {0}, the invoices are sealed when issued")]
+    LedgerAlreadyExists(String),
+
+    #[error("No invoice id left on {0}, the day ids stop at 99, set a numbering policy")]
+    NoInvoiceDayIdLeft(String),
 }
@@ -2,7 +2,11 @@ pub(super) mod create_invoice;
 pub(super) mod get_all_invoices;
 pub(super) mod get_all_invoices_by_day;
 pub(super) mod get_all_invoices_by_month;
+pub(super) mod get_all_invoices_by_range;
+mod get_all_invoices_by_period;
 pub(super) mod get_all_invoices_by_year;
 pub(super) mod get_all_invoices_path;
 pub(super) mod get_invoice_by_filepath;
+pub(super) mod get_ledger;
 mod invoice_file_manager_error;
+pub(super) mod seal_invoice;
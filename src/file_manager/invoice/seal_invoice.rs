@@ -0,0 +1,152 @@
+use super::get_all_invoices::get_all_invoices;
+use super::get_ledger::{get_ledger, LEDGER_FILE_NAME};
+use super::invoice_file_manager_error::InvoiceFileManagerError;
+use crate::entities::invoice::Invoice;
+use crate::entities::ledger::LedgerEntry;
+use std::fs;
+use std::path::Path;
+
+/// A store without ledger may only be empty: the invoices issued before the
+/// ledger are sealed once by `seal_existing_invoices`, never silently
+pub fn check_ledger(path: &Path, invoice: &Invoice) -> Result<(), InvoiceFileManagerError> {
+    if path.join(LEDGER_FILE_NAME).exists() {
+        return Ok(());
+    }
+
+    let has_other_invoices = get_all_invoices(path)?
+        .iter()
+        .any(|existing_invoice| existing_invoice.get_ref() != invoice.get_ref());
+
+    if has_other_invoices {
+        return Err(InvoiceFileManagerError::LedgerNotFound(path.to_string_lossy().to_string()));
+    }
+
+    Ok(())
+}
+
+/// Add the invoice at the end of the hash chain
+pub fn seal_invoice(path: &Path, invoice: &Invoice) -> Result<LedgerEntry, InvoiceFileManagerError> {
+    check_ledger(path, invoice)?;
+
+    let mut ledger = get_ledger(path)?;
+
+    let entry = LedgerEntry::new(invoice, get_last_digest(&ledger));
+    ledger.push(entry.clone());
+
+    write_ledger(path, &ledger)?;
+
+    Ok(entry)
+}
+
+/// Seal in reference order the invoices issued before the ledger existed,
+/// refused once the ledger is created
+pub fn seal_existing_invoices(path: &Path) -> Result<usize, InvoiceFileManagerError> {
+    if path.join(LEDGER_FILE_NAME).exists() {
+        return Err(InvoiceFileManagerError::LedgerAlreadyExists(path.to_string_lossy().to_string()));
+    }
+
+    let mut ledger: Vec<LedgerEntry> = vec![];
+
+    get_all_invoices(path)?.iter().for_each(|invoice| {
+        let entry = LedgerEntry::new(invoice, get_last_digest(&ledger));
+        ledger.push(entry);
+    });
+
+    write_ledger(path, &ledger)?;
+
+    Ok(ledger.len())
+}
+
+fn write_ledger(path: &Path, ledger: &[LedgerEntry]) -> Result<(), InvoiceFileManagerError> {
+    let ledger_file_path = path.join(LEDGER_FILE_NAME);
+
+    fs::write(&ledger_file_path, serde_yaml::to_string(ledger).unwrap()).map_err(|error| {
+        InvoiceFileManagerError::UnableToWriteLedgerFile(ledger_file_path.to_string_lossy().to_string(), error)
+    })
+}
+
+fn get_last_digest(ledger: &[LedgerEntry]) -> &str {
+    ledger
+        .last()
+        .map(|entry| entry.digest.as_str())
+        .unwrap_or(LedgerEntry::GENESIS_DIGEST)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::invoice::InvoiceDayId;
+    use crate::entities::ledger::verify_ledger;
+    use crate::file_manager::invoice::create_invoice::create_invoice;
+    use chrono::NaiveDate;
+
+    #[test]
+    pub fn test_seal_invoice() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let temp_dir = temp_dir_assert_fs.path();
+
+        // Invoice issued before the ledger existed
+        let historical_invoice = Invoice::generate_simple_invoice_with_id_and_date_example(
+            Some(InvoiceDayId::new("01").unwrap()),
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+        );
+        fs::write(
+            temp_dir.join("2020010101.yaml"),
+            serde_yaml::to_string(&historical_invoice).unwrap(),
+        )
+        .unwrap();
+
+        let new_invoice = |month: u32| {
+            Invoice::generate_simple_invoice_with_id_and_date_example(
+                None,
+                NaiveDate::from_ymd_opt(2020, month, 1).unwrap(),
+            )
+        };
+
+        // Never sealed silently with the next invoice
+        assert!(matches!(
+            create_invoice(temp_dir, new_invoice(2), None),
+            Err(InvoiceFileManagerError::LedgerNotFound(_))
+        ));
+        assert_eq!(get_all_invoices(temp_dir).unwrap().len(), 1);
+
+        assert_eq!(seal_existing_invoices(temp_dir).unwrap(), 1);
+
+        [2, 3].iter().for_each(|month| {
+            create_invoice(temp_dir, new_invoice(*month), None).expect("Unable create test invoice");
+        });
+
+        assert!(matches!(
+            seal_existing_invoices(temp_dir),
+            Err(InvoiceFileManagerError::LedgerAlreadyExists(_))
+        ));
+
+        let ledger = get_ledger(temp_dir).expect("Unable to read ledger");
+        let all_invoices = get_all_invoices(temp_dir).unwrap();
+
+        assert_eq!(ledger.len(), 3);
+        assert_eq!(ledger[0].reference, "2020010101");
+        assert_eq!(ledger[0].previous_digest, LedgerEntry::GENESIS_DIGEST);
+        assert_eq!(ledger[2].previous_digest, ledger[1].digest);
+        assert!(verify_ledger(&ledger, &all_invoices).is_empty());
+
+        fs::write(
+            temp_dir.join("2020020101.yaml"),
+            serde_yaml::to_string(&historical_invoice).unwrap(),
+        )
+        .unwrap();
+
+        let all_invoices = get_all_invoices(temp_dir).unwrap();
+        assert!(!verify_ledger(&ledger, &all_invoices).is_empty());
+
+        // Removing the ledger does not seal the modified invoice
+        fs::remove_file(temp_dir.join(LEDGER_FILE_NAME)).unwrap();
+        assert!(matches!(
+            create_invoice(temp_dir, new_invoice(4), None),
+            Err(InvoiceFileManagerError::LedgerNotFound(_))
+        ));
+        assert!(!temp_dir.join(LEDGER_FILE_NAME).exists());
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+}
@@ -9,6 +9,9 @@ pub enum InvoiceManagerError {
     #[error("Customer store path not found: {0}")]
     CustomerStorePathNotFound(String),
 
+    #[error("Customer {0} of the invoice not found")]
+    CustomerNotFound(String),
+
     #[error("Unable to initialize folder in: {0}")]
     UnableInitFolderInto(String),
 
@@ -17,4 +20,10 @@ pub enum InvoiceManagerError {
 
     #[error("Unable to write customer file: {0}")]
     UnableToWriteCustomerFile(String, #[source] Error),
+
+    #[error("Unable to write payment file: {0}")]
+    UnableToWritePaymentFile(String, #[source] Error),
+
+    #[error("Unable to write recurring invoice file: {0}")]
+    UnableToWriteRecurringFile(String, #[source] Error),
 }
@@ -1,6 +1,11 @@
 mod customer;
+mod draft;
+#[allow(clippy::module_inception)]
 pub mod file_manager;
 mod invoice;
 pub mod invoice_manager_error;
+mod payment;
+mod quote;
+mod recurring;
 mod settings;
 pub mod context_parameters;
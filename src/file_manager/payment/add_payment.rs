@@ -0,0 +1,81 @@
+use std::fs;
+use std::path::Path;
+
+use crate::entities::payment::Payment;
+
+use super::get_all_payments::get_all_payments;
+use super::payment_file_manager_error::PaymentFileManagerError;
+
+pub fn add_payment(
+    payment_file_path: &Path,
+    invoice_ref: &str,
+    payment: Payment,
+) -> Result<Vec<Payment>, PaymentFileManagerError> {
+    let mut all_payments = get_all_payments(payment_file_path)?;
+
+    let invoice_payments = all_payments.entry(invoice_ref.to_string()).or_default();
+    invoice_payments.push(payment);
+    invoice_payments.sort_by_key(|payment| payment.date);
+
+    let invoice_payments = invoice_payments.clone();
+
+    match fs::write(
+        payment_file_path,
+        serde_yaml::to_string(&all_payments).unwrap(),
+    ) {
+        Ok(()) => Ok(invoice_payments),
+        Err(error) => Err(PaymentFileManagerError::UnableToWritePaymentFile(
+            payment_file_path.to_string_lossy().to_string(),
+            error,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::amount::Amount;
+    use crate::entities::payment::Payment;
+    use crate::file_manager::payment::add_payment::add_payment;
+    use crate::file_manager::payment::get_all_payments::get_all_payments;
+
+    #[test]
+    fn test_add_payment() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let temp_payment_file_path = temp_dir_assert_fs.path().join("payments.yaml");
+
+        add_payment(
+            &temp_payment_file_path,
+            "2015031401",
+            Payment::generate_simple_payment_example("100"),
+        )
+        .expect("Unable to add first payment");
+
+        let invoice_payments = add_payment(
+            &temp_payment_file_path,
+            "2015031401",
+            Payment::generate_simple_payment_example("250"),
+        )
+        .expect("Unable to add second payment");
+
+        assert_eq!(invoice_payments.len(), 2);
+
+        add_payment(
+            &temp_payment_file_path,
+            "2015031402",
+            Payment::generate_simple_payment_example("50"),
+        )
+        .expect("Unable to add payment on another invoice");
+
+        let all_payments = get_all_payments(&temp_payment_file_path)
+            .expect("Unable to read payments");
+
+        assert_eq!(all_payments.len(), 2);
+        assert_eq!(all_payments.get("2015031401").unwrap().len(), 2);
+        assert_eq!(
+            all_payments.get("2015031402").unwrap()[0].amount,
+            Amount::new("50").unwrap()
+        );
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::entities::payment::Payment;
+
+use super::payment_file_manager_error::PaymentFileManagerError;
+
+/// Payments by invoice reference. A missing file means no payment recorded yet.
+pub fn get_all_payments(
+    payment_file_path: &Path,
+) -> Result<HashMap<String, Vec<Payment>>, PaymentFileManagerError> {
+    if !payment_file_path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    match fs::read_to_string(payment_file_path) {
+        Ok(payment_data) if payment_data.trim().is_empty() => Ok(HashMap::new()),
+        Ok(payment_data) => serde_yaml::from_str(&payment_data).map_err(|error| {
+            PaymentFileManagerError::InvalidPaymentFile(
+                payment_file_path.to_string_lossy().to_string(),
+                error,
+            )
+        }),
+        Err(e) => Err(PaymentFileManagerError::UnableToReadPath(
+            payment_file_path.to_string_lossy().to_string(),
+            e,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::entities::amount::Amount;
+    use crate::file_manager::payment::get_all_payments::get_all_payments;
+
+    #[test]
+    fn test_get_all_payments() {
+        let payment_map_yaml = "'2015031401':\n".to_owned()
+            + "- date:\n"
+            + "    day: '20'\n"
+            + "    month: '03'\n"
+            + "    year: '2015'\n"
+            + "  amount: 100.0\n"
+            + "  method: transfer\n"
+            + "- date:\n"
+            + "    day: '21'\n"
+            + "    month: '03'\n"
+            + "    year: '2015'\n"
+            + "  amount: 250.0\n"
+            + "  method: check\n"
+            + "  reference: '0001'\n";
+
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let temp_payment_file_path = temp_dir_assert_fs.path().join("payments.yaml");
+
+        let all_payments = get_all_payments(&temp_payment_file_path)
+            .expect("Missing payment file must be read as empty");
+        assert!(all_payments.is_empty());
+
+        fs::write(&temp_payment_file_path, payment_map_yaml)
+            .expect("Unable to write payment file");
+
+        let all_payments = get_all_payments(&temp_payment_file_path)
+            .expect("Unable to read payment file example");
+
+        let invoice_payments = all_payments.get("2015031401").unwrap();
+        assert_eq!(invoice_payments.len(), 2);
+        assert_eq!(invoice_payments[1].amount, Amount::new("250").unwrap());
+        assert_eq!(invoice_payments[1].reference, Some("0001".to_string()));
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+}
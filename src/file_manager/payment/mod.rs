@@ -0,0 +1,3 @@
+pub(super) mod add_payment;
+pub(super) mod get_all_payments;
+mod payment_file_manager_error;
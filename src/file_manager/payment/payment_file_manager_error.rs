@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PaymentFileManagerError {
+    #[error("Unable to read payment file: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Unable to parse payment file: {0}")]
+    InvalidPaymentFile(String, #[source] serde_yaml::Error),
+
+    #[error("Unable to write payment file: {0}")]
+    UnableToWritePaymentFile(String, #[source] std::io::Error),
+}
@@ -0,0 +1,32 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::entities::quote::Quote;
+
+use super::get_quote_by_file_path::get_quote_by_file_path;
+use super::quote_file_manager_error::QuoteFileManagerError;
+
+/// Quotes sorted by reference. A missing quote directory means no quote created yet.
+pub fn get_all_quotes(path: &Path) -> Result<Vec<Quote>, QuoteFileManagerError> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let dir_content = fs::read_dir(path).map_err(|error| {
+        QuoteFileManagerError::UnableToReadPath(path.to_string_lossy().to_string(), error)
+    })?;
+
+    let mut paths: Vec<PathBuf> = dir_content
+        .map(|file| -> PathBuf { file.unwrap().path() })
+        .filter(|file| {
+            file.is_file() && !file.file_name().unwrap().to_string_lossy().starts_with('.')
+        })
+        .collect();
+
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|file_path| get_quote_by_file_path(file_path))
+        .collect()
+}
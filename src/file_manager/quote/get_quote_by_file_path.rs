@@ -0,0 +1,24 @@
+use std::fs;
+use std::path::Path;
+
+use crate::entities::quote::Quote;
+
+use super::quote_file_manager_error::QuoteFileManagerError;
+
+pub fn get_quote_by_file_path(file_path: &Path) -> Result<Quote, QuoteFileManagerError> {
+    if !file_path.is_file() {
+        return Err(QuoteFileManagerError::QuoteNotFound(
+            file_path.to_string_lossy().to_string(),
+        ));
+    }
+
+    match fs::read_to_string(file_path) {
+        Ok(quote_data) => serde_yaml::from_str(&quote_data).map_err(|error| {
+            QuoteFileManagerError::InvalidQuoteFile(file_path.to_string_lossy().to_string(), error)
+        }),
+        Err(error) => Err(QuoteFileManagerError::UnableToReadPath(
+            file_path.to_string_lossy().to_string(),
+            error,
+        )),
+    }
+}
@@ -0,0 +1,4 @@
+pub(super) mod get_all_quotes;
+pub(super) mod get_quote_by_file_path;
+mod quote_file_manager_error;
+pub(super) mod save_quote;
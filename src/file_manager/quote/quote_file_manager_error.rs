@@ -0,0 +1,23 @@
+use std::io::Error;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum QuoteFileManagerError {
+    #[error("Quote not found: {0}")]
+    QuoteNotFound(String),
+
+    #[error("Unable to create quote directory: {0}")]
+    UnableToCreateDirectory(String, #[source] Error),
+
+    #[error("Unable to write quote file: {0}")]
+    UnableToWriteQuoteFile(String, #[source] Error),
+
+    #[error("Unable to read path: {0}")]
+    UnableToReadPath(String, #[source] Error),
+
+    #[error("Invalid quote file: {0}")]
+    InvalidQuoteFile(String, #[source] serde_yaml::Error),
+
+    #[error("No quote id left on {0}, the day ids stop at 99")]
+    NoQuoteDayIdLeft(String),
+}
@@ -0,0 +1,118 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+
+use crate::entities::invoice::InvoiceDayId;
+use crate::entities::quote::Quote;
+
+use super::get_all_quotes::get_all_quotes;
+use super::quote_file_manager_error::QuoteFileManagerError;
+
+fn get_next_quote_day_id(path: &Path, date: NaiveDate) -> Result<InvoiceDayId, QuoteFileManagerError> {
+    let last_id = get_all_quotes(path)?
+        .iter()
+        .filter(|quote| quote.date == date)
+        .filter_map(|quote| quote.quote_day_id.as_ref())
+        .filter_map(|quote_day_id| quote_day_id.to_string().parse::<u32>().ok())
+        .max();
+
+    InvoiceDayId::new(&(last_id.unwrap_or(0) + 1).to_string())
+        .map_err(|_| QuoteFileManagerError::NoQuoteDayIdLeft(date.to_string()))
+}
+
+/// Write the quote, a reference is assigned to new quotes
+pub fn save_quote(path: &Path, quote: Quote) -> Result<PathBuf, QuoteFileManagerError> {
+    if !path.is_dir() {
+        if let Err(error) = fs::create_dir(path) {
+            return Err(QuoteFileManagerError::UnableToCreateDirectory(
+                path.to_string_lossy().to_string(),
+                error,
+            ));
+        }
+    }
+
+    let quote = match quote.quote_day_id {
+        Some(_) => quote,
+        None => Quote {
+            quote_day_id: Some(get_next_quote_day_id(path, quote.date)?),
+            ..quote
+        },
+    };
+
+    let file_path = path.to_owned().join(quote.get_ref().unwrap() + ".yaml");
+
+    match fs::write(&file_path, serde_yaml::to_string(&quote).unwrap()) {
+        Ok(()) => Ok(file_path),
+        Err(error) => Err(QuoteFileManagerError::UnableToWriteQuoteFile(
+            file_path.to_string_lossy().to_string(),
+            error,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::quote::{Quote, QuoteStatus};
+    use crate::file_manager::quote::get_all_quotes::get_all_quotes;
+    use crate::file_manager::quote::get_quote_by_file_path::get_quote_by_file_path;
+
+    use super::*;
+
+    #[test]
+    fn test_save_quote() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let quote_path = temp_dir_assert_fs.path().join("quotes");
+
+        assert!(get_all_quotes(&quote_path).unwrap().is_empty());
+
+        let new_quote = Quote {
+            quote_day_id: None,
+            ..Quote::generate_simple_quote_example()
+        };
+
+        let first_quote_path = save_quote(&quote_path, new_quote.clone()).expect("Unable to save quote");
+        let second_quote_path = save_quote(&quote_path, new_quote).expect("Unable to save quote");
+
+        assert_eq!(first_quote_path, quote_path.join("DEV2015031401.yaml"));
+        assert_eq!(second_quote_path, quote_path.join("DEV2015031402.yaml"));
+
+        let mut quote = get_quote_by_file_path(&first_quote_path).unwrap();
+        quote.status = QuoteStatus::Accepted;
+        save_quote(&quote_path, quote).expect("Unable to edit quote");
+
+        let all_quotes = get_all_quotes(&quote_path).unwrap();
+        assert_eq!(all_quotes.len(), 2);
+        assert_eq!(all_quotes[0].status, QuoteStatus::Accepted);
+        assert_eq!(all_quotes[1].status, QuoteStatus::Pending);
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+
+    #[test]
+    fn test_save_quote_after_last_of_day() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let quote_path = temp_dir_assert_fs.path().join("quotes");
+
+        save_quote(
+            &quote_path,
+            Quote {
+                quote_day_id: Some(InvoiceDayId::new("99").unwrap()),
+                ..Quote::generate_simple_quote_example()
+            },
+        )
+        .unwrap();
+
+        let result = save_quote(
+            &quote_path,
+            Quote {
+                quote_day_id: None,
+                ..Quote::generate_simple_quote_example()
+            },
+        );
+        assert!(matches!(result, Err(QuoteFileManagerError::NoQuoteDayIdLeft(_))));
+        assert_eq!(get_all_quotes(&quote_path).unwrap().len(), 1);
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+}
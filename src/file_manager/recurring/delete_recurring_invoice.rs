@@ -0,0 +1,29 @@
+use std::fs;
+use std::path::Path;
+
+use super::get_all_recurring_invoices::get_all_recurring_invoices;
+use super::recurring_file_manager_error::RecurringFileManagerError;
+
+pub fn delete_recurring_invoice(
+    recurring_file_path: &Path,
+    recurring_ref: &str,
+) -> Result<(), RecurringFileManagerError> {
+    let mut all_recurring_invoices = get_all_recurring_invoices(recurring_file_path)?;
+
+    if all_recurring_invoices.remove(recurring_ref).is_none() {
+        return Err(RecurringFileManagerError::RecurringInvoiceNotFound(
+            recurring_ref.to_owned(),
+        ));
+    }
+
+    match fs::write(
+        recurring_file_path,
+        serde_yaml::to_string(&all_recurring_invoices).unwrap(),
+    ) {
+        Ok(()) => Ok(()),
+        Err(error) => Err(RecurringFileManagerError::UnableToWriteRecurringFile(
+            recurring_file_path.to_string_lossy().to_string(),
+            error,
+        )),
+    }
+}
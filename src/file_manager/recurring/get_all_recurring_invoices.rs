@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::entities::recurring_invoice::RecurringInvoice;
+
+use super::recurring_file_manager_error::RecurringFileManagerError;
+
+/// Recurring invoices by serialized name. A missing file means no schedule yet.
+pub fn get_all_recurring_invoices(
+    recurring_file_path: &Path,
+) -> Result<HashMap<String, RecurringInvoice>, RecurringFileManagerError> {
+    if !recurring_file_path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    match fs::read_to_string(recurring_file_path) {
+        Ok(recurring_data) if recurring_data.trim().is_empty() => Ok(HashMap::new()),
+        Ok(recurring_data) => serde_yaml::from_str(&recurring_data).map_err(|error| {
+            RecurringFileManagerError::InvalidRecurringFile(
+                recurring_file_path.to_string_lossy().to_string(),
+                error,
+            )
+        }),
+        Err(e) => Err(RecurringFileManagerError::UnableToReadPath(
+            recurring_file_path.to_string_lossy().to_string(),
+            e,
+        )),
+    }
+}
@@ -0,0 +1,4 @@
+pub(super) mod delete_recurring_invoice;
+pub(super) mod get_all_recurring_invoices;
+mod recurring_file_manager_error;
+pub(super) mod save_recurring_invoice;
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RecurringFileManagerError {
+    #[error("Unable to read recurring invoice file: {0}")]
+    UnableToReadPath(String, #[source] std::io::Error),
+
+    #[error("Unable to parse recurring invoice file: {0}")]
+    InvalidRecurringFile(String, #[source] serde_yaml::Error),
+
+    #[error("Unable to write recurring invoice file: {0}")]
+    UnableToWriteRecurringFile(String, #[source] std::io::Error),
+
+    #[error("Recurring invoice not found: {0}")]
+    RecurringInvoiceNotFound(String),
+
+    #[error("Recurring invoice already exists: {0}")]
+    RecurringInvoiceAlreadyExists(String),
+}
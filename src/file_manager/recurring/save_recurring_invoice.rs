@@ -0,0 +1,118 @@
+use std::fs;
+use std::path::Path;
+
+use crate::entities::recurring_invoice::RecurringInvoice;
+
+use super::get_all_recurring_invoices::get_all_recurring_invoices;
+use super::recurring_file_manager_error::RecurringFileManagerError;
+
+/// Create or replace the recurring invoice stored under its serialized name
+pub fn save_recurring_invoice(
+    recurring_file_path: &Path,
+    recurring_invoice: RecurringInvoice,
+) -> Result<RecurringInvoice, RecurringFileManagerError> {
+    let mut all_recurring_invoices = get_all_recurring_invoices(recurring_file_path)?;
+
+    all_recurring_invoices.insert(
+        recurring_invoice.serialized_name(),
+        recurring_invoice.clone(),
+    );
+
+    match fs::write(
+        recurring_file_path,
+        serde_yaml::to_string(&all_recurring_invoices).unwrap(),
+    ) {
+        Ok(()) => Ok(recurring_invoice),
+        Err(error) => Err(RecurringFileManagerError::UnableToWriteRecurringFile(
+            recurring_file_path.to_string_lossy().to_string(),
+            error,
+        )),
+    }
+}
+
+/// Save a new recurring invoice, refused when its serialized name is taken as replacing
+/// the schedule would lose its last generated period
+pub fn create_recurring_invoice(
+    recurring_file_path: &Path,
+    recurring_invoice: RecurringInvoice,
+) -> Result<RecurringInvoice, RecurringFileManagerError> {
+    let serialized_name = recurring_invoice.serialized_name();
+
+    if get_all_recurring_invoices(recurring_file_path)?.contains_key(&serialized_name) {
+        return Err(RecurringFileManagerError::RecurringInvoiceAlreadyExists(serialized_name));
+    }
+
+    save_recurring_invoice(recurring_file_path, recurring_invoice)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::entities::recurring_invoice::RecurringInvoice;
+    use crate::file_manager::recurring::delete_recurring_invoice::delete_recurring_invoice;
+    use crate::file_manager::recurring::get_all_recurring_invoices::get_all_recurring_invoices;
+    use crate::file_manager::recurring::save_recurring_invoice::{create_recurring_invoice, save_recurring_invoice};
+
+    #[test]
+    fn test_save_recurring_invoice() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let temp_recurring_file_path = temp_dir_assert_fs.path().join("recurring.yaml");
+
+        let all_recurring_invoices = get_all_recurring_invoices(&temp_recurring_file_path)
+            .expect("Missing recurring file must be read as empty");
+        assert!(all_recurring_invoices.is_empty());
+
+        let mut recurring_invoice = RecurringInvoice::generate_simple_recurring_invoice_example();
+        save_recurring_invoice(&temp_recurring_file_path, recurring_invoice.clone())
+            .expect("Unable to save recurring invoice");
+
+        recurring_invoice.last_generated = Some(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap());
+        save_recurring_invoice(&temp_recurring_file_path, recurring_invoice)
+            .expect("Unable to update recurring invoice");
+
+        let all_recurring_invoices = get_all_recurring_invoices(&temp_recurring_file_path)
+            .expect("Unable to read recurring invoices");
+        assert_eq!(all_recurring_invoices.len(), 1);
+        assert_eq!(
+            all_recurring_invoices.get("king_retainer").unwrap().last_generated,
+            Some(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+        );
+
+        assert!(delete_recurring_invoice(&temp_recurring_file_path, "unknown").is_err());
+        delete_recurring_invoice(&temp_recurring_file_path, "king_retainer")
+            .expect("Unable to delete recurring invoice");
+
+        let all_recurring_invoices = get_all_recurring_invoices(&temp_recurring_file_path)
+            .expect("Unable to read recurring invoices");
+        assert!(all_recurring_invoices.is_empty());
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+
+    #[test]
+    fn test_create_recurring_invoice_with_taken_name() {
+        let temp_dir_assert_fs = assert_fs::TempDir::new().unwrap();
+        let temp_recurring_file_path = temp_dir_assert_fs.path().join("recurring.yaml");
+
+        let mut recurring_invoice = RecurringInvoice::generate_simple_recurring_invoice_example();
+        recurring_invoice.last_generated = Some(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap());
+        create_recurring_invoice(&temp_recurring_file_path, recurring_invoice)
+            .expect("Unable to create recurring invoice");
+
+        // Same serialized name, the schedule and its last generated period are kept
+        let mut recurring_invoice = RecurringInvoice::generate_simple_recurring_invoice_example();
+        recurring_invoice.name = recurring_invoice.name.to_uppercase();
+        assert!(create_recurring_invoice(&temp_recurring_file_path, recurring_invoice).is_err());
+
+        let all_recurring_invoices = get_all_recurring_invoices(&temp_recurring_file_path)
+            .expect("Unable to read recurring invoices");
+        assert_eq!(all_recurring_invoices.len(), 1);
+        assert_eq!(
+            all_recurring_invoices.get("king_retainer").unwrap().last_generated,
+            Some(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+        );
+
+        temp_dir_assert_fs.close().unwrap();
+    }
+}
@@ -8,7 +8,12 @@ pub fn get_settings(
     settings_file_path: &Path,
 ) -> Result<Settings, SettingsFileManagerError> {
     match fs::read_to_string(settings_file_path) {
-        Ok(invoice_data) => Ok(serde_yaml::from_str(&invoice_data).unwrap()),
+        Ok(invoice_data) => serde_yaml::from_str(&invoice_data).map_err(|error| {
+            SettingsFileManagerError::InvalidSettingsFile(
+                settings_file_path.to_string_lossy().to_string(),
+                error,
+            )
+        }),
         Err(e) => Err(SettingsFileManagerError::UnableToReadPath(
             settings_file_path.to_string_lossy().to_string(),
             e,
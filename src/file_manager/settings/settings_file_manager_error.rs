@@ -6,4 +6,6 @@ pub enum SettingsFileManagerError {
     UnableToWriteFile(String, #[source] std::io::Error),
     #[error("Unable to read settings file: {0}")]
     UnableToReadPath(String, #[source] std::io::Error),
+    #[error("Unable to parse settings file: {0}")]
+    InvalidSettingsFile(String, #[source] serde_yaml::Error),
 }
@@ -1,7 +1,7 @@
 // The project function defines how your document looks.
 // It takes your content and some metadata and formats it.
 // Go ahead and customize it to your liking!
-#let project(title: "", date: (), invoice_day_id: "", enterprise: (), customer: (), lawRules: "", politeness: "", tva: "", body) = {
+#let project(title: "", documentTitle: "Facture", date: (), reference: "", creditedReference: "", quoteReference: "", dueDate: "", validityDate: "", enterprise: (), customer: (), lawRules: "", politeness: "", tva: "", body) = {
   // Set the document's basic properties.
   set document(author: enterprise.name, title: title)
   set text(font: "Libertinus Serif", lang: "fr", blue.darken(80%))
@@ -40,9 +40,11 @@
           columns: (8em, 10em),
           gutter: 1em,
           [*Référence :*],
-          [#date.display("[year][month][day]")#invoice_day_id],
+          [#reference],
           [*Date :*],
-          date.display("[day]/[month]/[year]")
+          date.display("[day]/[month]/[year]"),
+          ..if dueDate != "" { ([*Échéance :*], [#dueDate]) } else { () },
+          ..if validityDate != "" { ([*Valable jusqu'au :*], [#validityDate]) } else { () },
           ))
         ])
         ),
@@ -54,7 +56,12 @@
   )
 
     // Title row.
-    pad(top: 4em, block([#text(weight: 600, 1.2em, [Intitulé :]) #title]))
+    pad(top: 4em, block([
+      #text(weight: 700, 1.6em, documentTitle) \
+      #if creditedReference != "" [Sur la facture n° #creditedReference \ ]
+      #if quoteReference != "" [Suivant devis n° #quoteReference \ ]
+      #text(weight: 600, 1.2em, [Intitulé :]) #title
+    ]))
 
     body
 
@@ -73,48 +80,75 @@
   ])))
 }
 
-#let productsDetails(products, TVANumber) = {
-  let isTVAConcerned = TVANumber != ""
-
-  let productsWithTotal = for product in products {
-      ([#product.quantity], [#product.description], [#product.price €], [#{product.quantity * product.price} €])
+#let productsDetails(invoice) = {
+  let productsWithTotal = for product in invoice.products {
+      ([#product.quantity], [#product.description], [#product.price €], [#product.vat_rate], [#product.total €])
   }
 
-  let totalHT = products.fold(0, (acc, product) => {
-    acc + product.quantity * product.price
-  })
-
   pad(top: 2em,
-    table(columns: (auto, 1fr, auto, auto),
+    table(columns: (auto, 1fr, auto, auto, auto),
     stroke: blue.darken(95%),
       fill: (_, row) => if (row == 0) { blue.darken(10%) } else if calc.even(row) { blue.lighten(90%) } else { white },
-    text(white, "Quantité"), text(white, "Désignation"), text(white, "Prix unitaire HT"), text(white, "Prix total HT"),
+    text(white, "Quantité"), text(white, "Désignation"), text(white, "Prix unitaire HT"), text(white, "TVA"), text(white, "Prix total HT"),
       ..productsWithTotal
     )
   )
 
-  let TVA_indication = if isTVAConcerned {
-    []
-  } else {
-    [TVA non applicable, art. 293 B du CGI]
+  let TVA = for vat_line in invoice.vat_lines {
+    ([*TVA #vat_line.rate* (base #vat_line.base €)], [#vat_line.vat €])
   }
 
-  let TVA = if isTVAConcerned {
-   ([*TVA*], [#(totalHT * 0.2) €])
+  let Total_TTC = if invoice.vat_lines.len() > 0 {
+   ([*Total TTC*], [#invoice.total_ttc €])
    } else {()}
 
-  let Total_TTC = if isTVAConcerned {
-   ([*Total TTC*], [#(totalHT * 1.2) €])
-   } else {()}
+  let TVA_indication = for mention in invoice.vat_mentions [#mention \ ]
 
   pad(top: 2em,
   align(end + top,
     box(inset: 0.3em, align(start + top, [
-      #table(columns: (6em, 6em), align: (left, right),
+      #table(columns: (auto, 6em), align: (left, right),
       stroke: none,
-     [*Total HT*], [#totalHT €], ..TVA, ..Total_TTC)
-    #text(0.95em, TVA_indication)
+     [*Total HT*], [#invoice.total_ht €], ..TVA, ..Total_TTC)
+    #text(0.95em)[#TVA_indication]
     ])
   )
   ))
 }
+
+#let quoteAcceptance(quote) = {
+  pad(top: 2em,
+    grid(
+      columns: (1fr, 1fr),
+      [],
+      rect(width: 100%, inset: 1em, [
+        *Bon pour accord* \
+        Date et signature du client : \ \ \
+      ])
+    )
+  )
+}
+
+#let statementDetails(statement) = {
+  let statementLines = for line in statement.lines {
+      ([#line.date], [#line.reference], [#line.label], [#if line.debit != "" [#line.debit €]], [#if line.credit != "" [#line.credit €]], [#line.balance €])
+  }
+
+  pad(top: 2em,
+    table(columns: (auto, auto, 1fr, auto, auto, auto),
+    align: (left, left, left, right, right, right),
+    stroke: blue.darken(95%),
+      fill: (_, row) => if (row == 0) { blue.darken(10%) } else if calc.even(row) { blue.lighten(90%) } else { white },
+    text(white, "Date"), text(white, "Référence"), text(white, "Libellé"), text(white, "Débit TTC"), text(white, "Crédit TTC"), text(white, "Solde"),
+      [], [], [*Solde initial*], [], [], [#statement.opening_balance €],
+      ..statementLines
+    )
+  )
+
+  pad(top: 2em,
+  align(end + top,
+    table(columns: (auto, 6em), align: (left, right),
+    stroke: none,
+    [*Solde dû*], [#statement.closing_balance €])
+  ))
+}
@@ -0,0 +1,9 @@
+
+// Factur-X : the XML of the invoice is attached as the alternative representation of the PDF/A-3
+#pdf.embed(
+  "factur-x.xml",
+  read("{{ FACTUR_X_PATH }}", encoding: none),
+  relationship: "alternative",
+  mime-type: "text/xml",
+  description: "Factur-X invoice",
+)
@@ -0,0 +1,21 @@
+#import "{{ TEMPLATE_PATH }}": *
+
+#let general = yaml("{{ SETTINGS_PATH }}")
+#let customers = yaml("{{ CUSTOMERS_PATH }}")
+#let statement_context = yaml("{{ CONTEXT_PATH }}")
+
+// Take a look at the file `template.typ` in the file panel
+// to customize this template and discover how it works.
+#show: project.with(
+  title: statement_context.title,
+  date: datetime(day: int(statement_context.date.day), month: int(statement_context.date.month),
+  year: int(statement_context.date.year)),
+  documentTitle: statement_context.document_title,
+  reference: statement_context.customer_id,
+  enterprise: general.enterprise,
+  customer: customers.at(statement_context.customer_id),
+  lawRules: general.law_rules,
+  politeness: general.politeness
+)
+
+#statementDetails(statement_context)
\ No newline at end of file
@@ -3,6 +3,7 @@
 #let general = yaml("{{ SETTINGS_PATH }}")
 #let customers = yaml("{{ CUSTOMERS_PATH }}")
 #let invoice_data = yaml("{{ INVOICE_PATH }}")
+#let invoice_context = yaml("{{ CONTEXT_PATH }}")
 
 // Take a look at the file `template.typ` in the file panel
 // to customize this template and discover how it works.
@@ -10,13 +11,15 @@
   title: invoice_data.title,
   date: datetime(day: int(invoice_data.date.day), month: int(invoice_data.date.month),
   year: int(invoice_data.date.year)),
-  invoice_day_id: invoice_data.invoice_day_id,
+  documentTitle: invoice_context.document_title,
+  reference: invoice_context.reference,
+  creditedReference: invoice_context.credited_invoice_ref,
+  quoteReference: invoice_context.quote_ref,
+  dueDate: invoice_context.due_date,
   enterprise: general.enterprise,
   customer: customers.at(invoice_data.customer_id),
   lawRules: general.law_rules,
   politeness: general.politeness
 )
 
-#let products = invoice_data.products
-
-#productsDetails(products, general.enterprise.tva)
\ No newline at end of file
+#productsDetails(invoice_context)
\ No newline at end of file
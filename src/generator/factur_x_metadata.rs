@@ -0,0 +1,204 @@
+use crate::entities::factur_x::FacturXProfile;
+
+const FACTUR_X_NAMESPACE: &str = "urn:factur-x:pdfa:CrossIndustryDocument:invoice:1p0#";
+
+/// Factur-X XMP metadata and the PDF/A extension schema declaring them, inserted in the
+/// `rdf:RDF` of the document metadata
+fn generate_factur_x_descriptions(profile: FacturXProfile) -> String {
+    let property = |name: &str, description: &str| {
+        format!(
+            "<rdf:li rdf:parseType=\"Resource\"><pdfaProperty:name>{}</pdfaProperty:name><pdfaProperty:valueType>Text</pdfaProperty:valueType><pdfaProperty:category>external</pdfaProperty:category><pdfaProperty:description>{}</pdfaProperty:description></rdf:li>",
+            name, description
+        )
+    };
+
+    format!(
+        concat!(
+            "<rdf:Description rdf:about=\"\" xmlns:fx=\"{namespace}\">",
+            "<fx:DocumentType>INVOICE</fx:DocumentType>",
+            "<fx:DocumentFileName>factur-x.xml</fx:DocumentFileName>",
+            "<fx:Version>1.0</fx:Version>",
+            "<fx:ConformanceLevel>{level}</fx:ConformanceLevel>",
+            "</rdf:Description>",
+            "<rdf:Description rdf:about=\"\" xmlns:pdfaExtension=\"http://www.aiim.org/pdfa/ns/extension/\" xmlns:pdfaSchema=\"http://www.aiim.org/pdfa/ns/schema#\" xmlns:pdfaProperty=\"http://www.aiim.org/pdfa/ns/property#\">",
+            "<pdfaExtension:schemas><rdf:Bag><rdf:li rdf:parseType=\"Resource\">",
+            "<pdfaSchema:schema>Factur-X PDFA Extension Schema</pdfaSchema:schema>",
+            "<pdfaSchema:namespaceURI>{namespace}</pdfaSchema:namespaceURI>",
+            "<pdfaSchema:prefix>fx</pdfaSchema:prefix>",
+            "<pdfaSchema:property><rdf:Seq>{properties}</rdf:Seq></pdfaSchema:property>",
+            "</rdf:li></rdf:Bag></pdfaExtension:schemas>",
+            "</rdf:Description>",
+        ),
+        namespace = FACTUR_X_NAMESPACE,
+        level = profile.get_conformance_level(),
+        properties = [
+            property("DocumentFileName", "name of the embedded XML invoice file"),
+            property("DocumentType", "INVOICE"),
+            property("Version", "version of the Factur-X XML schema"),
+            property("ConformanceLevel", "Factur-X profile of the XML invoice"),
+        ]
+        .concat(),
+    )
+}
+
+fn find(data: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    data.get(from..)?.windows(needle.len()).position(|window| window == needle).map(|position| position + from)
+}
+
+fn rfind(data: &[u8], needle: &[u8]) -> Option<usize> {
+    data.windows(needle.len()).rposition(|window| window == needle)
+}
+
+/// Number after the whitespaces at `from`, with the position following it
+fn read_number(data: &[u8], from: usize) -> Option<(usize, usize)> {
+    let start = from + data.get(from..)?.iter().take_while(|byte| byte.is_ascii_whitespace()).count();
+    let end = start + data[start..].iter().take_while(|byte| byte.is_ascii_digit()).count();
+
+    std::str::from_utf8(&data[start..end]).ok()?.parse().ok().map(|number| (number, end))
+}
+
+/// Object number of the document metadata, referenced by the catalog as `/Metadata n 0 R`
+fn find_metadata_object(pdf: &[u8]) -> Option<usize> {
+    let mut from = 0;
+
+    while let Some(position) = find(pdf, b"/Metadata", from) {
+        from = position + 1;
+        if let Some((object, end)) = read_number(pdf, position + b"/Metadata".len()) {
+            if pdf[end..].starts_with(b" 0 R") {
+                return Some(object);
+            }
+        }
+    }
+
+    None
+}
+
+/// Add the Factur-X XMP metadata to a PDF/A-3 written by Typst, which has no way to declare them.
+/// The metadata stream is redefined in an incremental update, the original bytes are kept as they are.
+pub fn add_factur_x_metadata(pdf: &[u8], profile: FacturXProfile) -> Result<Vec<u8>, String> {
+    let object = find_metadata_object(pdf).ok_or("the PDF has no XMP metadata")?;
+    let object_start = rfind(pdf, format!("\n{} 0 obj", object).as_bytes()).ok_or("the XMP metadata object is missing")? + 1;
+    let stream_keyword = find(pdf, b"stream", object_start).ok_or("the XMP metadata object is not a stream")?;
+
+    if find(&pdf[object_start..stream_keyword], b"/Filter", 0).is_some() {
+        return Err("the XMP metadata stream is compressed".to_string());
+    }
+
+    let content_start = stream_keyword + b"stream".len();
+    let content_start = content_start + [b"\r\n".as_slice(), b"\n"].iter().find(|eol| pdf[content_start..].starts_with(eol)).map_or(0, |eol| eol.len());
+    let content_end = find(pdf, b"endstream", content_start).ok_or("the XMP metadata stream has no end")?;
+    let metadata = std::str::from_utf8(&pdf[content_start..content_end]).map_err(|_| "the XMP metadata are not UTF-8")?.trim_end();
+
+    if metadata.contains(FACTUR_X_NAMESPACE) {
+        return Ok(pdf.to_vec());
+    }
+
+    let rdf_end = metadata.rfind("</rdf:RDF>").ok_or("the XMP metadata have no rdf:RDF")?;
+    let metadata = [&metadata[..rdf_end], &generate_factur_x_descriptions(profile), &metadata[rdf_end..]].concat();
+
+    let startxref = rfind(pdf, b"startxref").ok_or("the PDF has no startxref")?;
+    let (previous_xref, _) = read_number(pdf, startxref + b"startxref".len()).ok_or("the startxref offset is invalid")?;
+    let trailer = rfind(&pdf[..startxref], b"trailer").ok_or("cross-reference streams are not supported")?;
+    let trailer_dictionary = std::str::from_utf8(&pdf[trailer + b"trailer".len()..startxref]).map_err(|_| "the trailer is invalid")?.trim();
+    let trailer_entries = trailer_dictionary
+        .strip_prefix("<<")
+        .ok_or("the trailer is invalid")?
+        .lines()
+        .filter(|entry| !entry.trim_start().starts_with("/Prev"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut output = pdf.to_vec();
+    if !output.ends_with(b"\n") {
+        output.push(b'\n');
+    }
+
+    let object_offset = output.len();
+    output.extend_from_slice(format!("{} 0 obj\n<<\n  /Length {}\n  /Type /Metadata\n  /Subtype /XML\n>>\nstream\n", object, metadata.len()).as_bytes());
+    output.extend_from_slice(metadata.as_bytes());
+    output.extend_from_slice(b"\nendstream\nendobj\n\n");
+
+    // Only the metadata object changes, the previous section keeps the other ones
+    let xref_offset = output.len();
+    output.extend_from_slice(
+        format!(
+            "xref\n{} 1\n{:010} 00000 n\r\ntrailer\n<<\n  /Prev {}{}\nstartxref\n{}\n%%EOF\n",
+            object, object_offset, previous_xref, trailer_entries, xref_offset
+        )
+        .as_bytes(),
+    );
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?><x:xmpmeta xmlns:x=\"adobe:ns:meta/\"><rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\"><rdf:Description rdf:about=\"\" xmlns:pdfaid=\"http://www.aiim.org/pdfa/ns/id/\"><pdfaid:part>3</pdfaid:part><pdfaid:conformance>B</pdfaid:conformance></rdf:Description></rdf:RDF></x:xmpmeta><?xpacket end=\"r\"?>";
+
+    /// PDF laid out as Typst writes it: uncompressed metadata stream and a cross-reference table
+    fn generate_pdf() -> Vec<u8> {
+        let objects = [
+            "<<\n  /Type /Catalog\n  /Pages 2 0 R\n  /Metadata 3 0 R\n>>".to_string(),
+            "<<\n  /Type /Pages\n  /Kids []\n  /Count 0\n>>".to_string(),
+            format!("<<\n  /Length {}\n  /Type /Metadata\n  /Subtype /XML\n>>\nstream\n{}\nendstream", METADATA.len(), METADATA),
+        ];
+        let mut pdf = b"%PDF-1.7\n%\x80\x80\x80\x80\n\n".to_vec();
+        let mut offsets = vec![];
+
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n\n", index + 1, object).as_bytes());
+        }
+
+        let xref_offset = pdf.len();
+        pdf.extend_from_slice(b"xref\n0 4\n0000000000 65535 f\r\n");
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{:010} 00000 n\r\n", offset).as_bytes());
+        }
+        pdf.extend_from_slice(format!("trailer\n<<\n  /Size 4\n  /Root 1 0 R\n  /ID [<01> <01>]\n>>\nstartxref\n{}\n%%EOF", xref_offset).as_bytes());
+        pdf
+    }
+
+    #[test]
+    fn metadata_redefined_in_an_incremental_update() {
+        let pdf = generate_pdf();
+        let output = add_factur_x_metadata(&pdf, FacturXProfile::En16931).unwrap();
+
+        assert!(output.starts_with(&pdf));
+        let update = std::str::from_utf8(&output[pdf.len()..]).unwrap();
+        assert!(update.starts_with("\n3 0 obj\n"));
+        assert!(update.contains("<pdfaid:part>3</pdfaid:part>"));
+        assert!(update.contains("<fx:ConformanceLevel>EN 16931</fx:ConformanceLevel>"));
+        assert!(update.contains("<pdfaSchema:prefix>fx</pdfaSchema:prefix>"));
+        assert!(update.contains("</rdf:Description></rdf:RDF></x:xmpmeta>"));
+
+        let previous_xref = find(&pdf, b"\nxref\n", 0).unwrap() + 1;
+        assert!(update.contains(&format!("trailer\n<<\n  /Prev {}\n  /Size 4\n  /Root 1 0 R\n  /ID [<01> <01>]\n>>", previous_xref)));
+
+        // The new cross-reference section points to the new metadata object
+        let xref_offset = read_number(&output, rfind(&output, b"startxref").unwrap() + b"startxref".len()).unwrap().0;
+        assert!(output[xref_offset..].starts_with(b"xref\n3 1\n"));
+        let object_offset = read_number(&output, xref_offset + b"xref\n3 1\n".len()).unwrap().0;
+        assert!(output[object_offset..].starts_with(b"3 0 obj\n"));
+
+        let length = read_number(&output, find(&output, b"/Length", object_offset).unwrap() + b"/Length".len()).unwrap().0;
+        let content_start = find(&output, b"stream\n", object_offset).unwrap() + b"stream\n".len();
+        assert!(output[content_start + length..].starts_with(b"\nendstream"));
+    }
+
+    #[test]
+    fn metadata_added_once() {
+        let output = add_factur_x_metadata(&generate_pdf(), FacturXProfile::Basic).unwrap();
+
+        assert_eq!(add_factur_x_metadata(&output, FacturXProfile::Basic).unwrap(), output);
+    }
+
+    #[test]
+    fn compressed_metadata_refused() {
+        let pdf = String::from_utf8_lossy(&generate_pdf()).replace("/Subtype /XML", "/Subtype /XML\n  /Filter /FlateDecode");
+
+        assert_eq!(add_factur_x_metadata(pdf.as_bytes(), FacturXProfile::Basic), Err("the XMP metadata stream is compressed".to_string()));
+    }
+}
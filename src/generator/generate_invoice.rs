@@ -3,24 +3,62 @@ use std::fs;
 use std::path::Path;
 use std::process::Command;
 
+use chrono::Local;
 use log::{error, info};
+use serde::Serialize;
 
+use crate::entities::customer_statement::CustomerStatement;
+use crate::entities::factur_x::FacturX;
+use crate::entities::invoice::Invoice;
+use crate::entities::quote::Quote;
+use crate::entities::vat_rate::VatRate;
+use crate::generator::factur_x_metadata::add_factur_x_metadata;
 use crate::generator::generator_error::GeneratorError;
+use crate::generator::invoice_context::InvoiceContext;
+use crate::generator::statement_context::StatementContext;
 
 fn generate_default_template() -> String {
     include_str!("assets/default_template.typ").to_string()
 }
 
-fn generate_main_template(settings_path: &Path, customer_path: &Path, invoice_path: &Path, template_path: &Path) -> String {
-    let main_template = include_str!("assets/main_template.typ");
+fn generate_factur_x_embed(factur_x_path: &Path) -> String {
+    include_str!("assets/factur_x_embed.typ").replace("{{ FACTUR_X_PATH }}", factur_x_path.to_str().unwrap())
+}
 
+fn generate_main_template(main_template: &str, settings_path: &Path, customer_path: &Path, invoice_path: Option<&Path>, context_path: &Path, template_path: &Path) -> String {
     let main_template = main_template.replace("{{ TEMPLATE_PATH }}", template_path.to_str().unwrap());
     let main_template = main_template.replace("{{ SETTINGS_PATH }}", settings_path.to_str().unwrap());
     let main_template = main_template.replace("{{ CUSTOMERS_PATH }}", customer_path.to_str().unwrap());
-    main_template.replace("{{ INVOICE_PATH }}", invoice_path.to_str().unwrap())
+    let main_template = main_template.replace("{{ CONTEXT_PATH }}", context_path.to_str().unwrap());
+    match invoice_path {
+        Some(invoice_path) => main_template.replace("{{ INVOICE_PATH }}", invoice_path.to_str().unwrap()),
+        None => main_template,
+    }
+}
+
+/// With a Factur-X XML, the PDF is a PDF/A-3 embedding it and declaring it in its XMP metadata
+#[allow(clippy::too_many_arguments)]
+pub fn generate_invoice<'a>(build_path: &Path, settings_path: &Path, customer_path: &Path, invoice_path: &Path, invoice: &Invoice, default_vat_rate: &VatRate, factur_x: Option<&FacturX>, target_path: &'a Path) -> Result<&'a Path, Box<dyn Error + Sync + Send + 'static>> {
+    let context = InvoiceContext::new(invoice, default_vat_rate);
+
+    generate_document(build_path, settings_path, customer_path, Some(invoice_path), &context, include_str!("assets/main_template.typ"), factur_x, target_path)
+}
+
+pub fn generate_quote<'a>(build_path: &Path, settings_path: &Path, customer_path: &Path, quote_path: &Path, quote: &Quote, default_vat_rate: &VatRate, target_path: &'a Path) -> Result<&'a Path, Box<dyn Error + Sync + Send + 'static>> {
+    let context = InvoiceContext::from_quote(quote, default_vat_rate);
+
+    generate_document(build_path, settings_path, customer_path, Some(quote_path), &context, include_str!("assets/main_quote_template.typ"), None, target_path)
+}
+
+/// Statements are built from the context only, they have no document file
+pub fn generate_statement<'a>(build_path: &Path, settings_path: &Path, customer_path: &Path, statement: &CustomerStatement, target_path: &'a Path) -> Result<&'a Path, Box<dyn Error + Sync + Send + 'static>> {
+    let context = StatementContext::new(statement, Local::now().date_naive());
+
+    generate_document(build_path, settings_path, customer_path, None, &context, include_str!("assets/main_statement_template.typ"), None, target_path)
 }
 
-pub fn generate_invoice<'a>(build_path: &Path, settings_path: &Path, customer_path: &Path, invoice_path: &Path, target_path: &'a Path) -> Result<&'a Path, Box<dyn Error + Sync + Send + 'static>> {
+#[allow(clippy::too_many_arguments)]
+fn generate_document<'a, T: Serialize>(build_path: &Path, settings_path: &Path, customer_path: &Path, document_path: Option<&Path>, context: &T, main_template: &str, factur_x: Option<&FacturX>, target_path: &'a Path) -> Result<&'a Path, Box<dyn Error + Sync + Send + 'static>> {
     if !build_path.exists() && build_path.parent().unwrap().exists() {
         info!("Create build directory in {}", build_path.to_string_lossy());
         if let Err(error) = fs::create_dir(build_path) {
@@ -28,11 +66,10 @@ pub fn generate_invoice<'a>(build_path: &Path, settings_path: &Path, customer_pa
                     "Unable create root directory in {}",
                     build_path.to_string_lossy()
                 );
-            return Err(Box::try_from(GeneratorError::UnableToCreateOutputDirectory(
+            return Err(Box::new(GeneratorError::UnableToCreateOutputDirectory(
                 build_path.to_string_lossy().to_string(),
                 error,
-            ))
-                .unwrap());
+            )));
         }
     }
 
@@ -45,11 +82,10 @@ pub fn generate_invoice<'a>(build_path: &Path, settings_path: &Path, customer_pa
                     "Unable create target directory in {}",
                     target_folder_path.to_string_lossy()
                 );
-            return Err(Box::try_from(GeneratorError::UnableToCreateOutputDirectory(
+            return Err(Box::new(GeneratorError::UnableToCreateOutputDirectory(
                 target_folder_path.to_string_lossy().to_string(),
                 error,
-            ))
-                .unwrap());
+            )));
         }
     }
 
@@ -59,11 +95,68 @@ pub fn generate_invoice<'a>(build_path: &Path, settings_path: &Path, customer_pa
     let default_template_path = build_path.to_owned().join("default_invoice_template.typ");
     let main_template_path = build_path.to_owned().join(main_file_type_name.file_name().unwrap());
 
+    let mut context_path = main_template_path.to_owned();
+    context_path.set_extension("context.yaml");
+
+    let mut main_template = generate_main_template(main_template, settings_path, customer_path, document_path, &context_path, &default_template_path);
+
+    if let Some(factur_x) = factur_x {
+        let mut factur_x_path = main_template_path.to_owned();
+        factur_x_path.set_extension("factur-x.xml");
+
+        fs::write(&factur_x_path, factur_x.to_string())?;
+        main_template += &generate_factur_x_embed(&factur_x_path);
+    }
+
     fs::write(&default_template_path, generate_default_template())?;
-    fs::write(&main_template_path, generate_main_template(settings_path, customer_path, invoice_path, &default_template_path))?;
+    fs::write(&context_path, serde_yaml::to_string(context)?)?;
+    fs::write(&main_template_path, main_template)?;
 
+    let mut command = Command::new("typst");
+    command.arg("compile").arg("--root").arg("/");
+
+    if factur_x.is_some() {
+        command.arg("--pdf-standard").arg("a-3b");
+    }
+
+    // Waited for, a document which does not compile is an error and not a missing PDF
+    let output = command
+        .arg(main_template_path)
+        .arg(target_path)
+        .output()
+        .map_err(GeneratorError::UnableToRunTypst)?;
+
+    if !output.status.success() {
+        return Err(Box::new(GeneratorError::TypstFailed(
+            target_path.to_string_lossy().to_string(),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )));
+    }
 
-    Command::new("typst").arg("compile").arg("--root").arg("/").arg(main_template_path).arg(target_path).spawn()?;
+    // Typst can not write the Factur-X XMP metadata, they are added to the compiled PDF
+    if let Some(factur_x) = factur_x {
+        let pdf = add_factur_x_metadata(&fs::read(target_path)?, factur_x.get_profile())
+            .map_err(|error| GeneratorError::FacturXMetadataFailed(target_path.to_string_lossy().to_string(), error))?;
+        fs::write(target_path, pdf)?;
+    }
 
     Ok(target_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_template_separates_arguments() {
+        let template = generate_default_template();
+        let lines: Vec<&str> = template.lines().map(str::trim).collect();
+
+        // A spread followed by another argument needs a comma, typst rejects the template otherwise
+        for (line, next_line) in lines.iter().zip(lines.iter().skip(1)) {
+            if line.starts_with("..") && !next_line.starts_with(')') {
+                assert!(line.ends_with(','), "Missing comma after {}", line);
+            }
+        }
+    }
 }
\ No newline at end of file
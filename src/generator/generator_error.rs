@@ -5,4 +5,13 @@ use thiserror::Error;
 pub enum GeneratorError {
     #[error("Unable create output directory {0}")]
     UnableToCreateOutputDirectory(String, #[source] Error),
+
+    #[error("Unable to run typst, is it installed?")]
+    UnableToRunTypst(#[source] Error),
+
+    #[error("Unable to generate {0}: {1}")]
+    TypstFailed(String, String),
+
+    #[error("Unable to add the Factur-X metadata to {0}: {1}")]
+    FacturXMetadataFailed(String, String),
 }
\ No newline at end of file
@@ -0,0 +1,213 @@
+use serde::Serialize;
+
+use crate::entities::document_kind::DocumentKind;
+use crate::entities::invoice::Invoice;
+use crate::entities::invoice_totals::{InvoiceTotals, VatLine};
+use crate::entities::product::Product;
+use crate::entities::quote::Quote;
+use crate::entities::vat_rate::VatRate;
+
+#[derive(Serialize)]
+pub struct ProductContext {
+    pub description: String,
+    pub quantity: String,
+    pub price: String,
+    pub vat_rate: String,
+    pub total: String,
+}
+
+#[derive(Serialize)]
+pub struct VatLineContext {
+    pub rate: String,
+    pub base: String,
+    pub vat: String,
+}
+
+/// Values computed on the Rust side and given to the Typst template,
+/// so the PDF never has to do money arithmetic with floats.
+#[derive(Serialize)]
+pub struct InvoiceContext {
+    pub document_title: String,
+    pub reference: String,
+    pub credited_invoice_ref: String,
+    pub quote_ref: String,
+    pub due_date: String,
+    pub validity_date: String,
+    pub products: Vec<ProductContext>,
+    pub total_ht: String,
+    pub vat_lines: Vec<VatLineContext>,
+    pub total_vat: String,
+    pub total_ttc: String,
+    pub vat_mentions: Vec<String>,
+}
+
+impl ProductContext {
+    fn new(product: &Product, default_vat_rate: &VatRate) -> Self {
+        ProductContext {
+            description: product.description.to_owned(),
+            quantity: product.quantity.to_string(),
+            price: product.price.to_string(),
+            vat_rate: product.get_vat_rate(default_vat_rate).to_string(),
+            total: product.get_total_price().to_string(),
+        }
+    }
+}
+
+impl From<&VatLine> for VatLineContext {
+    fn from(vat_line: &VatLine) -> Self {
+        VatLineContext {
+            rate: vat_line.rate.to_string(),
+            base: vat_line.base.to_string(),
+            vat: vat_line.vat.to_string(),
+        }
+    }
+}
+
+impl InvoiceContext {
+    pub fn new(invoice: &Invoice, default_vat_rate: &VatRate) -> Self {
+        InvoiceContext {
+            document_title: invoice.kind.get_title().to_string(),
+            reference: invoice.get_ref().unwrap_or_default(),
+            credited_invoice_ref: invoice.credited_invoice_ref.clone().unwrap_or_default(),
+            quote_ref: invoice.quote_ref.clone().unwrap_or_default(),
+            due_date: match invoice.kind {
+                DocumentKind::Invoice | DocumentKind::Deposit => {
+                    invoice.get_due_date().format("%d/%m/%Y").to_string()
+                }
+                DocumentKind::CreditNote => String::new(),
+            },
+            ..Self::from_products(&invoice.products, default_vat_rate)
+        }
+    }
+
+    pub fn from_quote(quote: &Quote, default_vat_rate: &VatRate) -> Self {
+        InvoiceContext {
+            document_title: "Devis".to_string(),
+            reference: quote.get_ref().unwrap_or_default(),
+            validity_date: quote.validity_date.format("%d/%m/%Y").to_string(),
+            ..Self::from_products(&quote.products, default_vat_rate)
+        }
+    }
+
+    fn from_products(products: &[Product], default_vat_rate: &VatRate) -> Self {
+        let totals = InvoiceTotals::from_products(products, default_vat_rate);
+
+        InvoiceContext {
+            document_title: String::new(),
+            reference: String::new(),
+            credited_invoice_ref: String::new(),
+            quote_ref: String::new(),
+            due_date: String::new(),
+            validity_date: String::new(),
+            products: products
+                .iter()
+                .map(|product| ProductContext::new(product, default_vat_rate))
+                .collect(),
+            total_ht: totals.total_ht.to_string(),
+            vat_lines: totals
+                .vat_lines
+                .iter()
+                .filter(|vat_line| vat_line.rate.get_exemption_reason().is_none())
+                .map(VatLineContext::from)
+                .collect(),
+            total_vat: totals.total_vat.to_string(),
+            total_ttc: totals.total_ttc.to_string(),
+            vat_mentions: totals
+                .get_exemption_reasons()
+                .iter()
+                .map(|reason| reason.to_string())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::amount::Amount;
+    use crate::entities::quantity::Quantity;
+
+    use super::*;
+
+    #[test]
+    fn test_invoice_context_to_string() {
+        let invoice = Invoice::generate_simple_invoice_example();
+
+        let yaml = serde_yaml::to_string(&InvoiceContext::new(
+            &invoice,
+            &VatRate::Exempt { reason: VatRate::FRANCHISE_REASON.to_string() },
+        ))
+        .unwrap();
+
+        assert_eq!(
+            yaml,
+            "document_title: Facture\n".to_owned()
+                + "reference: '2015031401'\n"
+                + "credited_invoice_ref: ''\n"
+                + "quote_ref: ''\n"
+                + "due_date: 13/04/2015\n"
+                + "validity_date: ''\n"
+                + "products:\n"
+                + "- description: Product example\n"
+                + "  quantity: '1'\n"
+                + "  price: '350.00'\n"
+                + "  vat_rate: Exonérée\n"
+                + "  total: '350.00'\n"
+                + "total_ht: '350.00'\n"
+                + "vat_lines: []\n"
+                + "total_vat: '0.00'\n"
+                + "total_ttc: '350.00'\n"
+                + "vat_mentions:\n"
+                + "- TVA non applicable, art. 293 B du CGI\n"
+        );
+    }
+
+    #[test]
+    fn test_invoice_context_with_vat() {
+        let mut invoice = Invoice::generate_simple_invoice_example();
+        invoice.products.push(Product {
+            description: "Book".to_string(),
+            quantity: Quantity::from(1),
+            price: Amount::new("10").unwrap(),
+            vat: Some(VatRate::Reduced),
+            activity: None,
+        });
+
+        let context = InvoiceContext::new(&invoice, &VatRate::Normal);
+
+        assert_eq!(context.products[0].vat_rate, "20 %");
+        assert_eq!(context.products[1].vat_rate, "5.5 %");
+        assert_eq!(context.vat_lines.len(), 2);
+        assert_eq!(context.vat_lines[0].vat, "70.00");
+        assert_eq!(context.vat_lines[1].vat, "0.55");
+        assert_eq!(context.total_ht, "360.00");
+        assert_eq!(context.total_ttc, "430.55");
+        assert!(context.vat_mentions.is_empty());
+    }
+
+    #[test]
+    fn test_credit_note_context() {
+        let invoice = Invoice::generate_simple_invoice_example();
+        let credit_note = invoice.create_credit_note(invoice.date, invoice.products.clone());
+
+        let context = InvoiceContext::new(&credit_note, &VatRate::Normal);
+
+        assert_eq!(context.document_title, "Avoir");
+        assert_eq!(context.credited_invoice_ref, "2015031401");
+        assert_eq!(context.due_date, "");
+        assert_eq!(context.products[0].price, "-350.00");
+        assert_eq!(context.total_ttc, "-420.00");
+    }
+
+    #[test]
+    fn test_quote_context() {
+        let quote = Quote::generate_simple_quote_example();
+
+        let context = InvoiceContext::from_quote(&quote, &VatRate::Normal);
+
+        assert_eq!(context.document_title, "Devis");
+        assert_eq!(context.reference, "DEV2015031401");
+        assert_eq!(context.validity_date, "13/04/2015");
+        assert_eq!(context.due_date, "");
+        assert_eq!(context.total_ttc, "840.00");
+    }
+}
@@ -1,2 +1,5 @@
+mod factur_x_metadata;
 pub mod generate_invoice;
-mod generator_error;
\ No newline at end of file
+mod generator_error;
+mod invoice_context;
+mod statement_context;
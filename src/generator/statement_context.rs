@@ -0,0 +1,120 @@
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+
+use crate::entities::amount::Amount;
+use crate::entities::customer_statement::CustomerStatement;
+
+#[derive(Serialize)]
+pub struct DateContext {
+    pub day: u32,
+    pub month: u32,
+    pub year: i32,
+}
+
+#[derive(Serialize)]
+pub struct StatementLineContext {
+    pub date: String,
+    pub reference: String,
+    pub label: String,
+    pub debit: String,
+    pub credit: String,
+    pub balance: String,
+}
+
+/// Statement lines with their running balance, formatted for the Typst template
+#[derive(Serialize)]
+pub struct StatementContext {
+    pub document_title: String,
+    pub title: String,
+    pub customer_id: String,
+    pub date: DateContext,
+    pub opening_balance: String,
+    pub lines: Vec<StatementLineContext>,
+    pub closing_balance: String,
+}
+
+fn format_movement(amount: Amount) -> String {
+    if amount == Amount::ZERO {
+        String::new()
+    } else {
+        amount.to_string()
+    }
+}
+
+impl StatementContext {
+    pub fn new(statement: &CustomerStatement, date: NaiveDate) -> Self {
+        let mut balance = statement.opening_balance;
+
+        StatementContext {
+            document_title: "Relevé de compte".to_string(),
+            title: format!(
+                "Du {} au {}",
+                statement.from.format("%d/%m/%Y"),
+                statement.to.format("%d/%m/%Y")
+            ),
+            customer_id: statement.customer_id.to_owned(),
+            date: DateContext { day: date.day(), month: date.month(), year: date.year() },
+            opening_balance: statement.opening_balance.to_string(),
+            lines: statement
+                .lines
+                .iter()
+                .map(|line| {
+                    balance += line.debit - line.credit;
+
+                    StatementLineContext {
+                        date: line.date.format("%d/%m/%Y").to_string(),
+                        reference: line.reference.to_owned(),
+                        label: line.label.to_owned(),
+                        debit: format_movement(line.debit),
+                        credit: format_movement(line.credit),
+                        balance: balance.to_string(),
+                    }
+                })
+                .collect(),
+            closing_balance: statement.closing_balance.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::customer_statement::StatementLine;
+
+    use super::*;
+
+    #[test]
+    fn test_statement_context() {
+        let statement = CustomerStatement {
+            customer_id: "king".to_string(),
+            from: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            to: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            opening_balance: Amount::new("100").unwrap(),
+            lines: vec![
+                StatementLine {
+                    date: NaiveDate::from_ymd_opt(2024, 2, 10).unwrap(),
+                    reference: "2024021001".to_string(),
+                    label: "Facture".to_string(),
+                    debit: Amount::new("420").unwrap(),
+                    credit: Amount::ZERO,
+                },
+                StatementLine {
+                    date: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+                    reference: "2024021001".to_string(),
+                    label: "Règlement".to_string(),
+                    debit: Amount::ZERO,
+                    credit: Amount::new("500").unwrap(),
+                },
+            ],
+            closing_balance: Amount::new("20").unwrap(),
+        };
+
+        let context = StatementContext::new(&statement, NaiveDate::from_ymd_opt(2025, 1, 2).unwrap());
+
+        assert_eq!(context.title, "Du 01/01/2024 au 31/12/2024");
+        assert_eq!(context.lines[0].debit, "420.00");
+        assert_eq!(context.lines[0].credit, "");
+        assert_eq!(context.lines[0].balance, "520.00");
+        assert_eq!(context.lines[1].balance, "20.00");
+        assert_eq!(context.closing_balance, "20.00");
+    }
+}
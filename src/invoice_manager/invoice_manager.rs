@@ -5,7 +5,13 @@ use std::path::{Path, PathBuf};
 use chrono::NaiveDate;
 
 use crate::entities::customer::Customer;
+use crate::entities::customer_statement::CustomerStatement;
 use crate::entities::invoice::Invoice;
+use crate::entities::invoice_query::InvoiceQuery;
+use crate::entities::ledger::LedgerEntry;
+use crate::entities::payment::Payment;
+use crate::entities::quote::Quote;
+use crate::entities::recurring_invoice::RecurringInvoice;
 use crate::entities::settings::Settings;
 
 pub trait InvoiceManager {
@@ -20,6 +26,12 @@ pub trait InvoiceManager {
         &self,
         invoice_reference: &str,
     ) -> Result<Invoice, Box<dyn Error + Sync + Send + 'static>>;
+    fn get_ledger(
+        &self,
+    ) -> Result<Vec<LedgerEntry>, Box<dyn Error + Sync + Send + 'static>>;
+    fn seal_existing_invoices(
+        &self,
+    ) -> Result<usize, Box<dyn Error + Sync + Send + 'static>>;
     fn get_invoice_by_date(
         &self,
         date: NaiveDate,
@@ -33,6 +45,17 @@ pub trait InvoiceManager {
         &self,
         year: i32,
     ) -> Result<Vec<Invoice>, Box<dyn Error + Sync + Send + 'static>>;
+    /// Invoices dated between `from` and `to` included
+    fn get_invoice_by_range(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Invoice>, Box<dyn Error + Sync + Send + 'static>>;
+    /// Invoices matching the filters of the query, sorted and limited
+    fn search_invoices(
+        &self,
+        query: &InvoiceQuery,
+    ) -> Result<Vec<Invoice>, Box<dyn Error + Sync + Send + 'static>>;
     fn get_all_customers(
         &self,
     ) -> Result<HashMap<String, Customer>, Box<dyn Error + Sync + Send + 'static>>;
@@ -45,9 +68,9 @@ pub trait InvoiceManager {
         customer_ref: String,
         customer: Customer,
     ) -> Result<Customer, Box<dyn Error + Sync + Send + 'static>>;
-    fn remove_customer<'a>(
+    fn remove_customer(
         &self,
-        customer_ref: &'a str,
+        customer_ref: &str,
     ) -> Result<(), Box<dyn Error + Sync + Send + 'static>>;
     fn create_settings(
         &self,
@@ -58,6 +81,60 @@ pub trait InvoiceManager {
         settings: Settings,
     ) -> Result<(), Box<dyn Error + Sync + Send + 'static>>;
     fn get_settings(&self) -> Result<Settings, Box<dyn Error + Sync + Send + 'static>>;
+    fn get_all_payments(
+        &self,
+    ) -> Result<HashMap<String, Vec<Payment>>, Box<dyn Error + Sync + Send + 'static>>;
+    fn get_invoice_payments(
+        &self,
+        invoice_reference: &str,
+    ) -> Result<Vec<Payment>, Box<dyn Error + Sync + Send + 'static>>;
+    fn add_payment(
+        &self,
+        invoice_reference: &str,
+        payment: Payment,
+    ) -> Result<Vec<Payment>, Box<dyn Error + Sync + Send + 'static>>;
+    fn save_quote(
+        &self,
+        quote: Quote,
+    ) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>>;
+    fn get_all_quotes(&self) -> Result<Vec<Quote>, Box<dyn Error + Sync + Send + 'static>>;
+    fn get_quote_by_ref(
+        &self,
+        quote_reference: &str,
+    ) -> Result<Quote, Box<dyn Error + Sync + Send + 'static>>;
+    fn get_all_recurring_invoices(
+        &self,
+    ) -> Result<HashMap<String, RecurringInvoice>, Box<dyn Error + Sync + Send + 'static>>;
+    fn create_recurring_invoice(
+        &self,
+        recurring_invoice: RecurringInvoice,
+    ) -> Result<RecurringInvoice, Box<dyn Error + Sync + Send + 'static>>;
+    fn save_recurring_invoice(
+        &self,
+        recurring_invoice: RecurringInvoice,
+    ) -> Result<RecurringInvoice, Box<dyn Error + Sync + Send + 'static>>;
+    fn remove_recurring_invoice(
+        &self,
+        recurring_ref: &str,
+    ) -> Result<(), Box<dyn Error + Sync + Send + 'static>>;
+    fn save_draft(
+        &self,
+        draft_id: Option<&str>,
+        draft: &Invoice,
+    ) -> Result<String, Box<dyn Error + Sync + Send + 'static>>;
+    fn get_all_drafts(
+        &self,
+    ) -> Result<HashMap<String, Invoice>, Box<dyn Error + Sync + Send + 'static>>;
+    fn get_draft(
+        &self,
+        draft_id: &str,
+    ) -> Result<Invoice, Box<dyn Error + Sync + Send + 'static>>;
+    fn remove_draft(
+        &self,
+        draft_id: &str,
+    ) -> Result<(), Box<dyn Error + Sync + Send + 'static>>;
 
-    fn generate_invoice<'a>(&self, invoice_path: &Path, output: &str) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>>;
+    fn generate_invoice(&self, invoice_path: &Path, output: &str) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>>;
+    fn generate_quote(&self, quote_path: &Path, output: &str) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>>;
+    fn generate_statement(&self, statement: &CustomerStatement, output: &str) -> Result<PathBuf, Box<dyn Error + Sync + Send + 'static>>;
 }
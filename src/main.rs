@@ -1,28 +1,72 @@
 use std::env;
 use std::error::Error;
 use std::path::PathBuf;
+use std::process;
 
+use chrono::NaiveDate;
 use clap::{Parser, Subcommand};
 use log::LevelFilter;
 
 use crate::cli::cli_error::CliError;
+use crate::cli::accept_quote::accept_quote;
+use crate::cli::audit_numbering::audit_numbering;
+use crate::cli::bill_quote::bill_quote;
 use crate::cli::create_customer::create_customer;
 use crate::cli::create_invoice::create_invoice;
+use crate::cli::create_quote::create_quote;
+use crate::cli::create_recurring::create_recurring;
+use crate::cli::customer_input::CustomerInput;
+use crate::cli::customer_statement::customer_statement;
+use crate::cli::customer_stats::customer_stats;
+use crate::cli::credit_invoice::credit_invoice;
 use crate::cli::day_stats::day_stats;
+use crate::cli::declare::{declare_month, declare_quarter};
 use crate::cli::delete_customer::delete_customer;
+use crate::cli::deposit_quote::deposit_quote;
 use crate::cli::delete_invoice::cancel_invoice;
+use crate::cli::delete_recurring::delete_recurring;
 use crate::cli::edit_customer::edit_customer;
+use crate::cli::edit_draft::edit_draft;
 use crate::cli::edit_settings::edit_settings;
+use crate::cli::export_csv::export_csv;
+use crate::cli::export_factur_x::export_factur_x;
+use crate::cli::export_ubl::export_ubl;
+use crate::cli::export_fec::export_fec;
+use crate::cli::import_csv::import_csv;
+use crate::cli::import_e_invoice::import_e_invoice;
+use crate::cli::finalize_draft::finalize_draft;
 use crate::cli::generate_all_invoice::generate_all_invoice;
 use crate::cli::generate_invoice::generate_invoice;
 use crate::cli::get_customer::get_customer;
 use crate::cli::get_invoice::get_invoice;
+use crate::cli::get_quote::get_quote;
 use crate::cli::get_settings::get_settings;
 use crate::cli::init::initiate_invoice_directory;
+use crate::cli::invoice_query_input::InvoiceQueryInput;
 use crate::cli::list_customers::list_customers;
+use crate::cli::list_drafts::list_drafts;
 use crate::cli::list_invoices::list_invoices;
+use crate::cli::list_quotes::list_quotes;
+use crate::cli::list_recurring::list_recurring;
 use crate::cli::month_stats::month_stats;
+use crate::cli::output::output_format::OutputFormat;
+use crate::cli::overdue_stats::overdue_stats;
+use crate::cli::pay_invoice::pay_invoice;
+use crate::cli::quarter_stats::quarter_stats;
+use crate::cli::range_stats::range_stats;
+use crate::cli::refuse_quote::refuse_quote;
+use crate::cli::run_recurring::run_recurring;
+use crate::cli::settings_input::SettingsInput;
+use crate::cli::thresholds_stats::thresholds_stats;
+use crate::cli::verify_invoices::verify_invoices;
 use crate::cli::year_stats::year_stats;
+use crate::entities::amount::Amount;
+use crate::entities::payment::PaymentMethod;
+use crate::entities::product::Product;
+use crate::entities::csv_export::{CsvContent, CsvRowsPer};
+use crate::entities::csv_import::ColumnMapping;
+use crate::entities::factur_x::FacturXProfile;
+use crate::entities::range_stats::Breakdown;
 use crate::file_manager::context_parameters::ContextParameters;
 
 mod cli;
@@ -58,6 +102,26 @@ struct Cli {
     #[arg(long, value_name = "FILE")]
     target_path: Option<PathBuf>,
 
+    /// Sets a custom payment file
+    #[arg(long, value_name = "FILE")]
+    payment_file_path: Option<PathBuf>,
+
+    /// Sets a custom quote folder
+    #[arg(long, value_name = "FILE")]
+    quote_path: Option<PathBuf>,
+
+    /// Sets a custom recurring invoice file
+    #[arg(long, value_name = "FILE")]
+    recurring_file_path: Option<PathBuf>,
+
+    /// Sets a custom draft folder
+    #[arg(long, value_name = "FILE")]
+    draft_path: Option<PathBuf>,
+
+    /// Format of the list, get and stats commands
+    #[arg(long, value_enum, global = true, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+
     /// Turn debugging information on
     #[arg(short, long, action = clap::ArgAction::Count)]
     debug: u8,
@@ -69,26 +133,65 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Init invoices path
-    Init,
+    Init {
+        #[command(flatten)]
+        settings: SettingsInput,
+    },
     /// Manage Invoice
     Invoice {
         #[command(subcommand)]
-        action: Option<CrudAction>,
+        action: Option<InvoiceAction>,
+    },
+    /// Manage Quote
+    Quote {
+        #[command(subcommand)]
+        action: Option<QuoteAction>,
+    },
+    /// Manage recurring invoices
+    Recurring {
+        #[command(subcommand)]
+        action: Option<RecurringAction>,
     },
     /// Manage Customer
     Customer {
         #[command(subcommand)]
-        action: Option<CrudAction>,
+        action: Option<CustomerAction>,
     },
     /// Show stats
     Stats {
         #[command(subcommand)]
         action: Option<StatsAction>
     },
+    /// Revenue cashed to declare to the URSSAF and the estimated contributions
+    Declare {
+        #[command(subcommand)]
+        action: Option<DeclareAction>
+    },
+    /// Export the invoices for the accounting
+    Export {
+        #[command(subcommand)]
+        action: Option<ExportAction>
+    },
+    /// Import customers or historical invoices
+    Import {
+        #[command(subcommand)]
+        action: Option<ImportAction>
+    },
     /// Manage settings and enterprise informations
     Settings {
         #[command(subcommand)]
-        action: Option<CrudAction>,
+        action: Option<SettingsAction>,
+    },
+    /// Check the invoices against the legal requirements
+    Audit {
+        #[command(subcommand)]
+        action: Option<AuditAction>
+    },
+    /// Check no issued invoice was modified, removed or inserted
+    Verify {
+        /// Seal the invoices issued before the ledger existed, only once
+        #[arg(long)]
+        init: bool,
     },
     /// Generate PDF for a invoice
     Generate {
@@ -99,20 +202,171 @@ enum Commands {
 }
 
 #[derive(Subcommand)]
-enum CrudAction {
-    Create,
+enum CustomerAction {
+    /// Create a customer, values missing from the flags are asked
+    Create {
+        #[command(flatten)]
+        customer: CustomerInput,
+    },
     List,
     Get {
         element: Option<String>
     },
+    /// Edit a customer, values missing from the flags are asked or kept
     Edit {
+        element: Option<String>,
+        #[command(flatten)]
+        customer: CustomerInput,
+    },
+    Delete {
+        element: Option<String>
+    },
+    /// Generate a PDF account statement of the invoices, credit notes and payments of a customer
+    Statement {
+        element: Option<String>,
+        /// First day of the statement, January 1st of the current year by default (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last day of the statement, today by default (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SettingsAction {
+    /// Init invoices path, values missing from the flags are asked
+    Create {
+        #[command(flatten)]
+        settings: SettingsInput,
+    },
+    List,
+    Get {
         element: Option<String>
     },
+    /// Edit settings, values missing from the flags are asked or kept
+    Edit {
+        element: Option<String>,
+        #[command(flatten)]
+        settings: SettingsInput,
+    },
     Delete {
         element: Option<String>
     },
 }
 
+#[derive(Subcommand)]
+enum InvoiceAction {
+    Create {
+        /// Save a draft to edit and finalize later instead of issuing the invoice
+        #[arg(long)]
+        draft: bool,
+        /// Customer id, asked if missing
+        #[arg(long)]
+        customer: Option<String>,
+        /// Invoice title, asked if missing
+        #[arg(long)]
+        title: Option<String>,
+        /// Product line "description;quantity;price[;vat rate]", can be repeated
+        #[arg(long = "line", value_name = "LINE")]
+        lines: Vec<Product>,
+        /// Read the invoice from a YAML or JSON file, the other flags override its values
+        #[arg(long, value_name = "FILE")]
+        from_file: Option<PathBuf>,
+        /// Invoice date (YYYY-MM-DD), today if missing
+        #[arg(long)]
+        date: Option<NaiveDate>,
+    },
+    /// List invoices matching the filters
+    List {
+        /// Show drafts instead of issued invoices, the filters only apply to issued invoices
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "unpaid", "customer", "from", "to", "min_amount", "max_amount",
+                "search", "status", "kind", "sort", "desc", "limit",
+            ]
+        )]
+        drafts: bool,
+        #[command(flatten)]
+        query: InvoiceQueryInput,
+    },
+    Get {
+        element: Option<String>
+    },
+    /// Edit a draft, issued invoices can not be edited
+    Edit {
+        element: Option<String>
+    },
+    /// Issue a draft with its final reference and generate its PDF
+    Finalize {
+        element: Option<String>
+    },
+    /// Cancel a invoice with a credit note of all its products not yet credited
+    Delete {
+        element: Option<String>
+    },
+    /// Create a credit note on some products or amounts of a invoice
+    Credit {
+        element: Option<String>
+    },
+    /// Record a payment for a invoice
+    Pay {
+        element: Option<String>,
+        /// Amount received, asked if missing
+        #[arg(long)]
+        amount: Option<Amount>,
+        /// Payment date (YYYY-MM-DD), today if missing
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// transfer, check, card, cash or other, asked if missing
+        #[arg(long)]
+        method: Option<PaymentMethod>,
+        /// Bank or check reference
+        #[arg(long)]
+        reference: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum QuoteAction {
+    Create,
+    List,
+    Get {
+        element: Option<String>
+    },
+    /// Accept a quote and create the invoice of its products
+    Accept {
+        element: Option<String>,
+        /// Only bill a deposit of this percentage, the final invoice is created with `bill`
+        #[arg(long, value_name = "PERCENT")]
+        deposit: Option<u32>,
+    },
+    /// Bill another deposit of an accepted quote
+    Deposit {
+        element: Option<String>,
+        #[arg(long)]
+        percent: u32,
+    },
+    /// Create the final invoice of an accepted quote, minus its deposits
+    Bill {
+        element: Option<String>
+    },
+    Refuse {
+        element: Option<String>
+    },
+}
+
+#[derive(Subcommand)]
+enum RecurringAction {
+    Create,
+    List,
+    Delete {
+        element: Option<String>
+    },
+    /// Create all invoices due until today and their PDF, dated today with the period in their title
+    Run,
+}
 
 #[derive(Subcommand)]
 enum StatsAction {
@@ -128,6 +382,125 @@ enum StatsAction {
     Year {
         year: Option<i32>
     },
+    /// Quarter 1 to 4, the current one by default, compared to the same quarter last year
+    Quarter {
+        quarter: Option<u32>,
+        year: Option<i32>,
+        /// month or customer
+        #[arg(long, default_value_t = Breakdown::Month)]
+        by: Breakdown,
+    },
+    /// Invoices between two dates included, compared to the same dates last year
+    Range {
+        /// First day (YYYY-MM-DD)
+        #[arg(long)]
+        from: NaiveDate,
+        /// Last day (YYYY-MM-DD)
+        #[arg(long)]
+        to: NaiveDate,
+        /// month or customer
+        #[arg(long, default_value_t = Breakdown::Month)]
+        by: Breakdown,
+    },
+    /// Invoices not fully paid after their due date
+    Overdue,
+    /// Revenue of the year against the VAT franchise and micro-enterprise ceilings
+    Thresholds {
+        year: Option<i32>
+    },
+    /// Lifetime revenue, invoices per year and outstanding balance of a customer
+    Customer {
+        customer: Option<String>
+    },
+}
+
+#[derive(Subcommand)]
+enum DeclareAction {
+    /// Monthly declaration, the previous month by default
+    Month {
+        month: Option<u32>,
+        year: Option<i32>,
+    },
+    /// Quarterly declaration, the previous quarter by default
+    Quarter {
+        quarter: Option<u32>,
+        year: Option<i32>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ExportAction {
+    /// Fichier des Écritures Comptables of the sales and the payments
+    Fec {
+        /// Fiscal year, the previous year by default
+        #[arg(long)]
+        year: Option<i32>,
+        /// Path of the file, `SirenFECYYYY1231.txt` in the current directory by default
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Factur-X XML of an invoice, the one embedded in its PDF
+    FacturX {
+        invoice: Option<String>,
+        /// minimum, basic or en16931, the profile of the settings by default
+        #[arg(long)]
+        profile: Option<FacturXProfile>,
+        /// Path of the file, `{ref}.xml` in the current directory by default
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Peppol BIS Billing 3.0 UBL of an invoice, checked before being written
+    Ubl {
+        invoice: Option<String>,
+        /// Path of the file, `{ref}.ubl.xml` in the current directory by default
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Invoices or customers as CSV
+    Csv {
+        /// invoices or customers
+        content: CsvContent,
+        /// Rows of the invoices: invoice or line
+        #[arg(long, default_value_t = CsvRowsPer::Invoice)]
+        per: CsvRowsPer,
+        /// Path of the file, the standard output by default
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ImportAction {
+    /// Customers or invoices from a CSV file with a header line.
+    /// The rows of the invoices with the same reference are the lines of one invoice.
+    Csv {
+        /// invoices or customers
+        content: CsvContent,
+        /// Path of the CSV file
+        file: PathBuf,
+        /// Column of the file read as a field, `column=field`. Columns named as a field need no mapping
+        #[arg(long = "map")]
+        mappings: Vec<ColumnMapping>,
+        /// Validate and list what would be imported without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Invoices from CII or UBL XML files or Factur-X PDF, with a report of the fields not imported.
+    /// The buyers not found among the customers are created.
+    EInvoice {
+        /// Paths of the XML or PDF files
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Validate and list what would be imported without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuditAction {
+    /// Report gaps, duplicates and out of order dates in the invoice numbering
+    Numbering,
 }
 
 fn main() {
@@ -150,55 +523,128 @@ fn main() {
         customer_file_path: cli.customer_file_path.as_deref(),
         build_path: cli.build_path.as_deref(),
         target_path: cli.target_path.as_deref(),
+        payment_file_path: cli.payment_file_path.as_deref(),
+        quote_path: cli.quote_path.as_deref(),
+        recurring_file_path: cli.recurring_file_path.as_deref(),
+        draft_path: cli.draft_path.as_deref(),
     };
 
     let result: Result<(), Box<dyn Error + Sync + Send + 'static>> = match &cli.command {
-        Some(Commands::Init) => initiate_invoice_directory(
-            parameters
+        Some(Commands::Init { settings }) => initiate_invoice_directory(
+            parameters,
+            settings
         ),
         Some(Commands::Invoice { action }) => match action {
-            Some(CrudAction::List) => list_invoices(parameters),
-            Some(CrudAction::Get { element }) => get_invoice(parameters, element),
-            Some(CrudAction::Create) => create_invoice(parameters),
-            Some(CrudAction::Edit { element: _element }) => {
-                Err(Box::new(CliError::CommandNotExists("You can't edit a invoice. You can only cancel the old invoice and create another".to_string())))
+            Some(InvoiceAction::List { drafts: true, query: _ }) => list_drafts(parameters, cli.output),
+            Some(InvoiceAction::List { drafts: false, query }) => list_invoices(parameters, cli.output, query),
+            Some(InvoiceAction::Get { element }) => get_invoice(parameters, cli.output, element),
+            Some(InvoiceAction::Create { draft, customer, title, lines, from_file, date }) => {
+                create_invoice(parameters, *draft, customer, title, lines, from_file, date)
+            }
+            Some(InvoiceAction::Edit { element }) => edit_draft(parameters, element),
+            Some(InvoiceAction::Finalize { element }) => finalize_draft(parameters, element),
+            Some(InvoiceAction::Delete { element }) => cancel_invoice(parameters, element),
+            Some(InvoiceAction::Credit { element }) => credit_invoice(parameters, element),
+            Some(InvoiceAction::Pay { element, amount, date, method, reference }) => {
+                pay_invoice(parameters, element, amount, date, method, reference)
+            }
+            None => {
+                Err(Box::new(CliError::CommandNotExists("You can get, create, edit, finalize, delete, credit or pay invoice".to_string())))
+            }
+        },
+        Some(Commands::Quote { action }) => match action {
+            Some(QuoteAction::Create) => create_quote(parameters),
+            Some(QuoteAction::List) => list_quotes(parameters, cli.output),
+            Some(QuoteAction::Get { element }) => get_quote(parameters, cli.output, element),
+            Some(QuoteAction::Accept { element, deposit }) => accept_quote(parameters, element, deposit),
+            Some(QuoteAction::Deposit { element, percent }) => deposit_quote(parameters, element, *percent),
+            Some(QuoteAction::Bill { element }) => bill_quote(parameters, element),
+            Some(QuoteAction::Refuse { element }) => refuse_quote(parameters, element),
+            None => {
+                Err(Box::new(CliError::CommandNotExists("You can create, list, get, accept, refuse, deposit or bill quote".to_string())))
             }
-            Some(CrudAction::Delete { element }) => cancel_invoice(parameters, element),
+        },
+        Some(Commands::Recurring { action }) => match action {
+            Some(RecurringAction::Create) => create_recurring(parameters),
+            Some(RecurringAction::List) => list_recurring(parameters, cli.output),
+            Some(RecurringAction::Delete { element }) => delete_recurring(parameters, element),
+            Some(RecurringAction::Run) => run_recurring(parameters),
             None => {
-                Err(Box::new(CliError::CommandNotExists("You can get, create or delete invoice".to_string())))
+                Err(Box::new(CliError::CommandNotExists("You can create, list, delete or run recurring invoices".to_string())))
             }
         },
         Some(Commands::Customer { action }) => match action {
-            Some(CrudAction::List) => list_customers(parameters),
-            Some(CrudAction::Get { element }) => get_customer(parameters, element),
-            Some(CrudAction::Create) => create_customer(parameters),
-            Some(CrudAction::Edit { element }) => { edit_customer(parameters, element) }
-            Some(CrudAction::Delete { element }) => { delete_customer(parameters, element) }
+            Some(CustomerAction::List) => list_customers(parameters, cli.output),
+            Some(CustomerAction::Get { element }) => get_customer(parameters, cli.output, element),
+            Some(CustomerAction::Create { customer }) => create_customer(parameters, customer),
+            Some(CustomerAction::Edit { element, customer }) => { edit_customer(parameters, element, customer) }
+            Some(CustomerAction::Delete { element }) => { delete_customer(parameters, element) }
+            Some(CustomerAction::Statement { element, from, to }) => customer_statement(parameters, element, from, to),
             None => { Err(Box::new(CliError::NotImplementedYet())) }
         },
         Some(Commands::Stats { action }) => {
             match action {
-                Some(StatsAction::Day { day, month, year }) => { day_stats(parameters, day, month, year) }
-                Some(StatsAction::Month { month, year }) => { month_stats(parameters, month, year) }
-                Some(StatsAction::Year { year }) => { year_stats(parameters, year) }
+                Some(StatsAction::Day { day, month, year }) => { day_stats(parameters, cli.output, day, month, year) }
+                Some(StatsAction::Month { month, year }) => { month_stats(parameters, cli.output, month, year) }
+                Some(StatsAction::Year { year }) => { year_stats(parameters, cli.output, year) }
+                Some(StatsAction::Quarter { quarter, year, by }) => { quarter_stats(parameters, cli.output, quarter, year, by) }
+                Some(StatsAction::Range { from, to, by }) => { range_stats(parameters, cli.output, from, to, by) }
+                Some(StatsAction::Overdue) => { overdue_stats(parameters, cli.output) }
+                Some(StatsAction::Thresholds { year }) => { thresholds_stats(parameters, cli.output, year) }
+                Some(StatsAction::Customer { customer }) => { customer_stats(parameters, cli.output, customer) }
                 None => { Err(Box::new(CliError::NotImplementedYet())) }
             }
         }
+        Some(Commands::Declare { action }) => match action {
+            Some(DeclareAction::Month { month, year }) => declare_month(parameters, cli.output, month, year),
+            Some(DeclareAction::Quarter { quarter, year }) => declare_quarter(parameters, cli.output, quarter, year),
+            None => {
+                Err(Box::new(CliError::CommandNotExists("You can declare a month or a quarter".to_string())))
+            }
+        },
+        Some(Commands::Export { action }) => match action {
+            Some(ExportAction::Fec { year, file }) => export_fec(parameters, year, file),
+            Some(ExportAction::FacturX { invoice, profile, file }) => export_factur_x(parameters, invoice, profile, file),
+            Some(ExportAction::Ubl { invoice, file }) => export_ubl(parameters, invoice, file),
+            Some(ExportAction::Csv { content, per, file }) => export_csv(parameters, content, per, file),
+            None => {
+                Err(Box::new(CliError::CommandNotExists("You can export a FEC, a Factur-X XML, a UBL or a CSV".to_string())))
+            }
+        },
+        Some(Commands::Import { action }) => match action {
+            Some(ImportAction::Csv { content, file, mappings, dry_run }) => {
+                import_csv(parameters, content, file, mappings, *dry_run)
+            }
+            Some(ImportAction::EInvoice { files, dry_run }) => import_e_invoice(parameters, files, *dry_run),
+            None => {
+                Err(Box::new(CliError::CommandNotExists("You can import a CSV or e-invoices".to_string())))
+            }
+        },
         Some(Commands::Settings { action }) => match action {
-            Some(CrudAction::List) => todo!("Not implemented"),
-            Some(CrudAction::Get { element: _element }) => get_settings(parameters),
-            Some(CrudAction::Create) => {
+            Some(SettingsAction::List) => todo!("Not implemented"),
+            Some(SettingsAction::Get { element: _element }) => get_settings(parameters, cli.output),
+            Some(SettingsAction::Create { settings }) => {
                 println!("This command init a new folder invoice. Don't use it on a already initiated folder");
-                initiate_invoice_directory(parameters)
+                initiate_invoice_directory(parameters, settings)
             }
-            Some(CrudAction::Edit { element: _element }) => { edit_settings(parameters) }
-            Some(CrudAction::Delete { element: _element }) => Err(Box::new(CliError::CommandNotExists("Not implemented, If you want delete the folder you can delete all files manually".to_string()))),
+            Some(SettingsAction::Edit { element: _element, settings }) => { edit_settings(parameters, settings) }
+            Some(SettingsAction::Delete { element: _element }) => Err(Box::new(CliError::CommandNotExists("Not implemented, If you want delete the folder you can delete all files manually".to_string()))),
             None => { Err(Box::new(CliError::NotImplementedYet())) }
         },
+        Some(Commands::Audit { action }) => match action {
+            Some(AuditAction::Numbering) => audit_numbering(parameters),
+            None => {
+                Err(Box::new(CliError::CommandNotExists("You can audit numbering".to_string())))
+            }
+        },
+        Some(Commands::Verify { init }) => verify_invoices(parameters, init),
         Some(Commands::Generate { invoice }) => generate_invoice(parameters, invoice),
         Some(Commands::GenerateAll) => generate_all_invoice(parameters),
         None => Err(Box::new(CliError::CommandNotExists("The option is not correct. Try to get help".to_string())))
     };
 
-    result.unwrap_or_else(|error| println!("Error : {}", error));
+    if let Err(error) = result {
+        println!("Error : {}", error);
+        process::exit(1);
+    }
 }